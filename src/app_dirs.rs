@@ -1,20 +1,76 @@
 use directories::ProjectDirs;
 use std::path::PathBuf;
 
-/// Centralized application directory resolution
+/// Centralized application directory resolution. Every path takes the `--profile` name:
+/// `None` is the default profile at the original locations, and a named profile gets a
+/// subdirectory of its own.
 pub struct AppDirs;
 
 impl AppDirs {
-    pub fn db_path() -> Option<PathBuf> {
+    pub fn db_path(profile: Option<&str>) -> Option<PathBuf> {
+        Self::state_dir(profile).map(|dir| dir.join("stats.db"))
+    }
+
+    /// Path of the unfinished-test checkpoint written with `--checkpoint`
+    pub fn checkpoint_path(profile: Option<&str>) -> Option<PathBuf> {
+        Self::state_dir(profile).map(|dir| dir.join("checkpoint.json"))
+    }
+
+    fn state_dir(profile: Option<&str>) -> Option<PathBuf> {
+        Self::base_state_dir().map(|dir| in_profile(dir, profile))
+    }
+
+    fn base_state_dir() -> Option<PathBuf> {
         if let Ok(home) = std::env::var("HOME") {
-            let state_dir = PathBuf::from(home)
-                .join(".local")
-                .join("state")
-                .join("klik");
-            Some(state_dir.join("stats.db"))
+            Some(
+                PathBuf::from(home)
+                    .join(".local")
+                    .join("state")
+                    .join("klik"),
+            )
         } else {
             ProjectDirs::from("", "", "klik")
-                .map(|proj_dirs| proj_dirs.data_local_dir().join("stats.db"))
+                .map(|proj_dirs| proj_dirs.data_local_dir().to_path_buf())
         }
     }
+
+    /// Path of the CSV session log written after each test
+    pub fn log_path(profile: Option<&str>) -> Option<PathBuf> {
+        ProjectDirs::from("", "", "klik").map(|proj_dirs| {
+            in_profile(proj_dirs.config_dir().to_path_buf(), profile).join("log.csv")
+        })
+    }
+}
+
+fn in_profile(dir: PathBuf, profile: Option<&str>) -> PathBuf {
+    match profile {
+        Some(name) => dir.join(name),
+        None => dir,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles_get_their_own_paths() {
+        let default = AppDirs::db_path(None).unwrap();
+        let alice = AppDirs::db_path(Some("alice")).unwrap();
+        let bob = AppDirs::db_path(Some("bob")).unwrap();
+        assert!(default.ends_with("klik/stats.db"));
+        assert!(alice.ends_with("klik/alice/stats.db"));
+        assert!(bob.ends_with("klik/bob/stats.db"));
+
+        let log = AppDirs::log_path(None).unwrap();
+        let alice_log = AppDirs::log_path(Some("alice")).unwrap();
+        assert_eq!(
+            alice_log,
+            log.parent().unwrap().join("alice").join("log.csv")
+        );
+        assert_eq!(
+            AppDirs::checkpoint_path(Some("alice")).unwrap(),
+            alice.with_file_name("checkpoint.json")
+        );
+    }
 }
@@ -1,6 +1,30 @@
+use clap::ValueEnum;
 use rand::seq::SliceRandom;
 use std::time::SystemTime;
 
+/// Which effect plays over a celebrated result, chosen with `--celebration`
+#[derive(Debug, Copy, Clone, Default, PartialEq, ValueEnum, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum CelebrationStyle {
+    /// Sparkles thrown up around the message, falling back down
+    #[default]
+    Confetti,
+    /// Bursts of sparks radiating from points above the message
+    Fireworks,
+    /// No celebration at all
+    None,
+}
+
+impl CelebrationStyle {
+    /// Downward pull on decorative particles; sparks drift rather than drop
+    fn gravity(self) -> f64 {
+        match self {
+            CelebrationStyle::Fireworks => 4.0,
+            _ => 15.0,
+        }
+    }
+}
+
 /// Particle for celebration animation
 #[derive(Debug, Clone)]
 pub struct CelebrationParticle {
@@ -65,7 +89,27 @@ impl CelebrationParticle {
         }
     }
 
-    fn update(&mut self, dt: f64) -> bool {
+    /// A spark flying out of a firework burst at `angle` radians
+    fn new_spark(x: f64, y: f64, angle: f64, speed: f64, color_index: usize) -> Self {
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+
+        Self {
+            x,
+            y,
+            vel_x: angle.cos() * speed * 2.0, // Cells are about twice as tall as wide
+            vel_y: angle.sin() * speed,
+            symbol: *['*', '✦', '✧', '·', '+'].choose(&mut rng).unwrap_or(&'*'),
+            color_index,
+            age: 0.0,
+            max_age: rng.gen_range(1.0..2.0),
+            is_text: false,
+            target_x: x,
+            target_y: y,
+        }
+    }
+
+    fn update(&mut self, dt: f64, gravity: f64) -> bool {
         if self.is_text {
             // Text particles move towards target and then stay
             let dist_to_target =
@@ -87,7 +131,7 @@ impl CelebrationParticle {
             // Regular particles with physics
             self.x += self.vel_x * dt;
             self.y += self.vel_y * dt;
-            self.vel_y += 15.0 * dt; // Increased gravity for more dramatic fall
+            self.vel_y += gravity * dt;
         }
 
         self.age += dt;
@@ -104,10 +148,15 @@ pub struct CelebrationAnimation {
     pub is_active: bool,
     pub terminal_width: f64,
     pub terminal_height: f64,
+    pub style: CelebrationStyle,
 }
 
 impl CelebrationAnimation {
     pub fn new() -> Self {
+        Self::with_style(CelebrationStyle::default())
+    }
+
+    pub fn with_style(style: CelebrationStyle) -> Self {
         Self {
             particles: Vec::new(),
             start_time: SystemTime::now(),
@@ -115,13 +164,37 @@ impl CelebrationAnimation {
             is_active: false,
             terminal_width: 80.0,
             terminal_height: 24.0,
+            style,
         }
     }
 
     pub fn start(&mut self, width: u16, height: u16) {
+        // Choose a random encouraging word
+        let words = [
+            "PERFECT!",
+            "AMAZING!",
+            "EXCELLENT!",
+            "FLAWLESS!",
+            "SUPERB!",
+            "BRILLIANT!",
+        ];
+        let chosen_word = words.choose(&mut rand::thread_rng()).unwrap_or(&"PERFECT!");
+        self.start_with_text(width, height, chosen_word);
+    }
+
+    /// Celebrate beating the all-time best WPM
+    pub fn start_personal_best(&mut self, width: u16, height: u16) {
+        self.start_with_text(width, height, "NEW BEST!");
+    }
+
+    fn start_with_text(&mut self, width: u16, height: u16, text: &str) {
         use rand::Rng;
         let mut rng = rand::thread_rng();
 
+        if self.style == CelebrationStyle::None {
+            return;
+        }
+
         self.particles.clear();
         self.start_time = SystemTime::now();
         self.is_active = true;
@@ -131,28 +204,41 @@ impl CelebrationAnimation {
         let center_x = width as f64 / 2.0;
         let center_y = height as f64 / 2.0;
 
-        // Choose a random encouraging word
-        let words = [
-            "PERFECT!",
-            "AMAZING!",
-            "EXCELLENT!",
-            "FLAWLESS!",
-            "SUPERB!",
-            "BRILLIANT!",
-        ];
-        let chosen_word = words.choose(&mut rng).unwrap_or(&"PERFECT!");
-
         // Create text particles for the chosen word
-        self.create_text_particles(chosen_word, center_x, center_y, &mut rng);
-
-        // Add some decorative particles around the text with more spread
-        for _ in 0..25 {
-            let offset_x = rng.gen_range(-15.0..15.0);
-            let offset_y = rng.gen_range(-8.0..8.0);
-            self.particles.push(CelebrationParticle::new(
-                center_x + offset_x,
-                center_y + offset_y,
-            ));
+        self.create_text_particles(text, center_x, center_y, &mut rng);
+
+        match self.style {
+            CelebrationStyle::Confetti => {
+                // Add some decorative particles around the text with more spread
+                for _ in 0..25 {
+                    let offset_x = rng.gen_range(-15.0..15.0);
+                    let offset_y = rng.gen_range(-8.0..8.0);
+                    self.particles.push(CelebrationParticle::new(
+                        center_x + offset_x,
+                        center_y + offset_y,
+                    ));
+                }
+            }
+            CelebrationStyle::Fireworks => {
+                // A few bursts above the text, each a ring of sparks in one color
+                for _ in 0..3 {
+                    let burst_x = center_x + rng.gen_range(-20.0..20.0);
+                    let burst_y = center_y - rng.gen_range(4.0..8.0);
+                    let color = rng.gen_range(0..7);
+                    let sparks = 12;
+                    for i in 0..sparks {
+                        let angle = i as f64 * std::f64::consts::TAU / sparks as f64;
+                        self.particles.push(CelebrationParticle::new_spark(
+                            burst_x,
+                            burst_y,
+                            angle,
+                            rng.gen_range(2.0..4.0),
+                            color,
+                        ));
+                    }
+                }
+            }
+            CelebrationStyle::None => {}
         }
     }
 
@@ -189,7 +275,8 @@ impl CelebrationAnimation {
         }
     }
 
-    pub fn update(&mut self) {
+    /// Advance the particles by `dt` seconds, one tick of the event loop
+    pub fn update(&mut self, dt: f64) {
         if !self.is_active {
             return;
         }
@@ -201,9 +288,9 @@ impl CelebrationAnimation {
             return;
         }
 
-        let dt = 0.1; // Fixed timestep for animation
+        let gravity = self.style.gravity();
         self.particles.retain_mut(|particle| {
-            let still_alive = particle.update(dt);
+            let still_alive = particle.update(dt, gravity);
 
             // Remove decorative particles that fall off screen (text particles should stay)
             if !particle.is_text {
@@ -237,7 +324,7 @@ mod tests {
         let initial_vel_y = particle.vel_y;
 
         // Update particle with physics
-        let still_alive = particle.update(0.1);
+        let still_alive = particle.update(0.1, CelebrationStyle::Confetti.gravity());
 
         // Particle should still be alive
         assert!(still_alive);
@@ -263,7 +350,7 @@ mod tests {
 
         // Update several times to move towards target
         for _ in 0..10 {
-            text_particle.update(0.1);
+            text_particle.update(0.1, CelebrationStyle::Confetti.gravity());
         }
 
         // Should be closer to target
@@ -290,13 +377,53 @@ mod tests {
 
         // Update celebration a few times
         for _ in 0..10 {
-            celebration.update();
+            celebration.update(0.1);
         }
 
         // Celebration should still be active (duration is 3 seconds)
         assert!(celebration.is_active);
     }
 
+    #[test]
+    fn test_none_style_never_celebrates() {
+        let mut celebration = CelebrationAnimation::with_style(CelebrationStyle::None);
+        celebration.start(80, 24);
+        celebration.start_personal_best(80, 24);
+        celebration.update(0.1);
+        assert!(!celebration.is_active);
+        assert!(celebration.particles.is_empty());
+    }
+
+    #[test]
+    fn test_styles_populate_particles_differently() {
+        let decorations = |style| {
+            let mut celebration = CelebrationAnimation::with_style(style);
+            celebration.start(80, 24);
+            assert!(celebration.is_active);
+            celebration
+                .particles
+                .into_iter()
+                .filter(|p| !p.is_text)
+                .collect::<Vec<_>>()
+        };
+
+        // Confetti is thrown upwards from around the text
+        let confetti = decorations(CelebrationStyle::Confetti);
+        assert_eq!(confetti.len(), 25);
+        assert!(confetti.iter().all(|p| p.vel_y < 0.0));
+
+        // Fireworks burst outwards in every direction from three points
+        let fireworks = decorations(CelebrationStyle::Fireworks);
+        assert_eq!(fireworks.len(), 36);
+        assert!(fireworks.iter().any(|p| p.vel_y > 0.0));
+        let mut origins: Vec<(u64, u64)> = fireworks
+            .iter()
+            .map(|p| (p.x.to_bits(), p.y.to_bits()))
+            .collect();
+        origins.dedup();
+        assert!(origins.len() <= 3);
+    }
+
     #[test]
     fn test_celebration_animation_imperfect_session() {
         let celebration = CelebrationAnimation::new();
@@ -341,7 +468,7 @@ mod tests {
 
         // Update animation several times
         for _ in 0..5 {
-            celebration.update();
+            celebration.update(0.1);
         }
 
         // Check that particles have moved
@@ -372,7 +499,7 @@ mod tests {
 
         // Update animation - off-screen particles should be removed
         for _ in 0..10 {
-            celebration.update();
+            celebration.update(0.1);
         }
 
         // Should have fewer particles now (the off-screen one should be removed)
@@ -3,44 +3,348 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// Persistent defaults for CLI flags, stored as TOML in the app config dir.
+/// Missing keys fall back to the built-in defaults, so partial files are fine.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
 pub struct Config {
     pub number_of_words: usize,
+    pub number_of_sentences: Option<usize>,
     pub number_of_secs: Option<usize>,
     pub supported_language: String,
     pub random_words: bool,
     pub capitalize: bool,
     pub strict: bool,
+    pub policy: String,
     pub symbols: bool,
+    pub balanced_symbols: bool,
+    pub uppercase_drill: bool,
+    pub title_case: bool,
+    pub progression: bool,
+    pub targeted_sentences: bool,
     pub substitute: bool,
+    pub fill_time: bool,
+    pub zen: bool,
+    pub numbers: bool,
+    pub number_min: Option<u64>,
+    pub number_max: Option<u64>,
+    pub number_groups: Option<String>,
+    pub number_separator: String,
+    pub sentence_words: bool,
+    pub keys: Option<String>,
+    pub mix: Option<String>,
+    pub target_aggression: f64,
+    pub recency_halflife: Option<f64>,
+    pub target: String,
+    pub seed: Option<u64>,
+    pub idle_timeout: f64,
+    pub tick_ms: u64,
+    pub countdown: Option<u64>,
+    pub bell: bool,
+    pub repeat: bool,
+    pub warmup: bool,
+    pub checkpoint: bool,
+    pub stats_letters_only: bool,
+    pub quote: bool,
+    pub wordlist: Option<PathBuf>,
+    pub prompt_file: Option<PathBuf>,
+    pub incorrect_glyph: Option<char>,
+    pub show_expected: bool,
+    pub theme: String,
+    pub cursor: String,
+    pub layout: String,
+    pub goal_wpm: Option<f64>,
+    pub smooth: Option<usize>,
+    pub good_ms: f64,
+    pub slow_ms: f64,
+    pub good_miss: f64,
+    pub bad_miss: f64,
+    pub minimal: bool,
+    pub share_template: String,
+    pub blink_ms: u64,
+    pub spell_out: bool,
+    pub hide_cursor_on_idle: bool,
+    pub min_accuracy: Option<f64>,
+    pub auto_advance: Option<u64>,
+    pub max_errors: Option<usize>,
+    pub forgive_corrected: bool,
+    pub ghost: bool,
+    pub celebration: String,
+    pub pomodoro: Option<u64>,
+    pub rounds: Option<usize>,
+    pub rounds_wait: bool,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             number_of_words: 15,
+            number_of_sentences: None,
             number_of_secs: None,
             supported_language: "english".to_string(),
             random_words: false,
             capitalize: false,
             strict: false,
+            policy: "normal".to_string(),
             symbols: false,
+            balanced_symbols: false,
+            uppercase_drill: false,
+            title_case: false,
+            progression: false,
+            targeted_sentences: false,
             substitute: false,
+            fill_time: false,
+            zen: false,
+            numbers: false,
+            number_min: None,
+            number_max: None,
+            number_groups: None,
+            number_separator: "dash".to_string(),
+            sentence_words: false,
+            keys: None,
+            mix: None,
+            target_aggression: 0.3,
+            recency_halflife: None,
+            target: "combined".to_string(),
+            seed: None,
+            idle_timeout: crate::session::DEFAULT_IDLE_TIMEOUT_SECS,
+            tick_ms: crate::thok::TICK_RATE_MS,
+            countdown: None,
+            bell: false,
+            repeat: false,
+            warmup: false,
+            checkpoint: false,
+            stats_letters_only: false,
+            quote: false,
+            wordlist: None,
+            prompt_file: None,
+            incorrect_glyph: None,
+            show_expected: false,
+            theme: "default".to_string(),
+            cursor: "underline".to_string(),
+            layout: "qwerty".to_string(),
+            goal_wpm: None,
+            smooth: None,
+            good_ms: 150.0,
+            slow_ms: 250.0,
+            good_miss: 0.0,
+            bad_miss: 10.0,
+            minimal: false,
+            share_template: crate::share::DEFAULT_SHARE_TEMPLATE.to_string(),
+            blink_ms: crate::DEFAULT_BLINK_MS,
+            spell_out: false,
+            hide_cursor_on_idle: false,
+            min_accuracy: None,
+            auto_advance: None,
+            max_errors: None,
+            forgive_corrected: false,
+            ghost: false,
+            celebration: "confetti".to_string(),
+            pomodoro: None,
+            rounds: None,
+            rounds_wait: false,
         }
     }
 }
 
+impl Config {
+    /// Overwrite the settings that can be toggled from the results screen
+    pub fn apply_runtime_settings(&mut self, rs: &crate::RuntimeSettings) {
+        self.number_of_words = rs.number_of_words;
+        self.number_of_secs = rs.number_of_secs;
+        self.supported_language = rs.supported_language.to_string().to_lowercase();
+        self.random_words = rs.random_words;
+        self.capitalize = rs.capitalize;
+        self.strict = rs.policy == crate::typing_policy::TypingPolicy::Strict;
+        self.policy = rs.policy.to_string();
+        self.symbols = rs.symbols;
+        self.substitute = rs.substitute;
+    }
+
+    /// Install these values as clap defaults, so flags given on the command line still win
+    pub fn apply_defaults(&self, cmd: clap::Command) -> clap::Command {
+        self.default_args()
+            .into_iter()
+            .fold(cmd, |cmd, (id, value)| {
+                cmd.mut_arg(id, |arg| arg.default_value(value))
+            })
+    }
+
+    fn default_args(&self) -> Vec<(&'static str, String)> {
+        let mut args = vec![
+            ("number_of_words", self.number_of_words.to_string()),
+            ("supported_language", self.supported_language.clone()),
+            ("idle_timeout", self.idle_timeout.to_string()),
+            ("tick_ms", self.tick_ms.to_string()),
+            ("target_aggression", self.target_aggression.to_string()),
+            ("target", self.target.clone()),
+            ("theme", self.theme.clone()),
+            ("cursor", self.cursor.clone()),
+            ("layout", self.layout.clone()),
+            ("policy", self.policy.clone()),
+            ("good_ms", self.good_ms.to_string()),
+            ("slow_ms", self.slow_ms.to_string()),
+            ("good_miss", self.good_miss.to_string()),
+            ("bad_miss", self.bad_miss.to_string()),
+            ("share_template", self.share_template.clone()),
+            ("blink_ms", self.blink_ms.to_string()),
+            ("number_separator", self.number_separator.clone()),
+            ("celebration", self.celebration.clone()),
+        ];
+        let optional = [
+            (
+                "number_of_sentences",
+                self.number_of_sentences.map(|v| v.to_string()),
+            ),
+            ("number_of_secs", self.number_of_secs.map(|v| v.to_string())),
+            (
+                "recency_halflife",
+                self.recency_halflife.map(|v| v.to_string()),
+            ),
+            (
+                "wordlist",
+                self.wordlist.as_ref().map(|p| p.display().to_string()),
+            ),
+            (
+                "prompt_file",
+                self.prompt_file.as_ref().map(|p| p.display().to_string()),
+            ),
+            ("incorrect_glyph", self.incorrect_glyph.map(String::from)),
+            ("goal_wpm", self.goal_wpm.map(|v| v.to_string())),
+            ("smooth", self.smooth.map(|v| v.to_string())),
+            ("min_accuracy", self.min_accuracy.map(|v| v.to_string())),
+            ("auto_advance", self.auto_advance.map(|v| v.to_string())),
+            ("max_errors", self.max_errors.map(|v| v.to_string())),
+            ("pomodoro", self.pomodoro.map(|v| v.to_string())),
+            ("rounds", self.rounds.map(|v| v.to_string())),
+            ("countdown", self.countdown.map(|v| v.to_string())),
+            ("keys", self.keys.clone()),
+            ("number_min", self.number_min.map(|v| v.to_string())),
+            ("number_max", self.number_max.map(|v| v.to_string())),
+            ("number_groups", self.number_groups.clone()),
+            ("mix", self.mix.clone()),
+            ("seed", self.seed.map(|v| v.to_string())),
+        ];
+        args.extend(
+            optional
+                .into_iter()
+                .filter_map(|(id, v)| v.map(|v| (id, v))),
+        );
+        let flags = [
+            ("random_words", self.random_words),
+            ("capitalize", self.capitalize),
+            ("strict", self.strict),
+            ("symbols", self.symbols),
+            ("balanced_symbols", self.balanced_symbols),
+            ("uppercase_drill", self.uppercase_drill),
+            ("title_case", self.title_case),
+            ("progression", self.progression),
+            ("targeted_sentences", self.targeted_sentences),
+            ("substitute", self.substitute),
+            ("fill_time", self.fill_time),
+            ("zen", self.zen),
+            ("bell", self.bell),
+            ("repeat", self.repeat),
+            ("warmup", self.warmup),
+            ("checkpoint", self.checkpoint),
+            ("stats_letters_only", self.stats_letters_only),
+            ("numbers", self.numbers),
+            ("sentence_words", self.sentence_words),
+            ("quote", self.quote),
+            ("show_expected", self.show_expected),
+            ("spell_out", self.spell_out),
+            ("hide_cursor_on_idle", self.hide_cursor_on_idle),
+            ("minimal", self.minimal),
+            ("rounds_wait", self.rounds_wait),
+            ("forgive_corrected", self.forgive_corrected),
+            ("ghost", self.ghost),
+        ];
+        args.extend(
+            flags
+                .into_iter()
+                .filter(|(_, on)| *on)
+                .map(|(id, _)| (id, "true".to_string())),
+        );
+        args
+    }
+}
+
 impl From<&crate::RuntimeSettings> for Config {
     fn from(rs: &crate::RuntimeSettings) -> Self {
+        let mut config = Self::default();
+        config.apply_runtime_settings(rs);
+        config
+    }
+}
+
+impl From<&crate::Cli> for Config {
+    fn from(cli: &crate::Cli) -> Self {
         Self {
-            number_of_words: rs.number_of_words,
-            number_of_secs: rs.number_of_secs,
-            supported_language: rs.supported_language.to_string().to_lowercase(),
-            random_words: rs.random_words,
-            capitalize: rs.capitalize,
-            strict: rs.strict,
-            symbols: rs.symbols,
-            substitute: rs.substitute,
+            number_of_words: cli.number_of_words,
+            number_of_sentences: cli.number_of_sentences,
+            number_of_secs: cli.number_of_secs,
+            supported_language: cli.supported_language.to_string().to_lowercase(),
+            random_words: cli.random_words,
+            capitalize: cli.capitalize,
+            strict: cli.strict,
+            policy: cli.policy.to_string(),
+            symbols: cli.symbols,
+            balanced_symbols: cli.balanced_symbols,
+            uppercase_drill: cli.uppercase_drill,
+            title_case: cli.title_case,
+            progression: cli.progression,
+            targeted_sentences: cli.targeted_sentences,
+            substitute: cli.substitute,
+            fill_time: cli.fill_time,
+            zen: cli.zen,
+            numbers: cli.numbers,
+            number_min: cli.number_min,
+            number_max: cli.number_max,
+            number_groups: cli.number_groups.as_ref().map(ToString::to_string),
+            number_separator: cli.number_separator.to_string(),
+            sentence_words: cli.sentence_words,
+            keys: cli.keys.as_ref().map(ToString::to_string),
+            mix: cli.mix.map(|lang| lang.to_string().to_lowercase()),
+            target_aggression: cli.target_aggression,
+            recency_halflife: cli.recency_halflife,
+            target: cli.target.to_string(),
+            seed: cli.seed,
+            idle_timeout: cli.idle_timeout,
+            tick_ms: cli.tick_ms,
+            countdown: cli.countdown,
+            bell: cli.bell,
+            repeat: cli.repeat,
+            warmup: cli.warmup,
+            checkpoint: cli.checkpoint,
+            stats_letters_only: cli.stats_letters_only,
+            quote: cli.quote,
+            wordlist: cli.wordlist.clone(),
+            prompt_file: cli.prompt_file.clone(),
+            incorrect_glyph: cli.incorrect_glyph,
+            show_expected: cli.show_expected,
+            theme: cli.theme.to_string(),
+            cursor: cli.cursor.to_string(),
+            layout: cli.layout.to_string(),
+            goal_wpm: cli.goal_wpm,
+            smooth: cli.smooth,
+            good_ms: cli.good_ms,
+            slow_ms: cli.slow_ms,
+            good_miss: cli.good_miss,
+            bad_miss: cli.bad_miss,
+            minimal: cli.minimal,
+            share_template: cli.share_template.clone(),
+            blink_ms: cli.blink_ms,
+            spell_out: cli.spell_out,
+            hide_cursor_on_idle: cli.hide_cursor_on_idle,
+            min_accuracy: cli.min_accuracy,
+            auto_advance: cli.auto_advance,
+            max_errors: cli.max_errors,
+            forgive_corrected: cli.forgive_corrected,
+            ghost: cli.ghost,
+            celebration: cli.celebration.to_string(),
+            pomodoro: cli.pomodoro,
+            rounds: cli.rounds,
+            rounds_wait: cli.rounds_wait,
         }
     }
 }
@@ -58,9 +362,9 @@ pub struct FileConfigStore {
 impl FileConfigStore {
     pub fn new() -> Self {
         let path = if let Some(pd) = ProjectDirs::from("", "", "klik") {
-            pd.config_dir().join("config.json")
+            pd.config_dir().join("config.toml")
         } else {
-            PathBuf::from("klik_config.json")
+            PathBuf::from("klik_config.toml")
         };
         Self { path }
     }
@@ -70,6 +374,10 @@ impl FileConfigStore {
             path: p.as_ref().to_path_buf(),
         }
     }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
 }
 
 impl Default for FileConfigStore {
@@ -80,19 +388,18 @@ impl Default for FileConfigStore {
 
 impl ConfigStore for FileConfigStore {
     fn load(&self) -> Config {
-        if let Ok(bytes) = fs::read(&self.path) {
-            if let Ok(cfg) = serde_json::from_slice::<Config>(&bytes) {
-                return cfg;
-            }
-        }
-        Config::default()
+        // A missing or malformed file falls back to the built-in defaults
+        fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|text| toml::from_str::<Config>(&text).ok())
+            .unwrap_or_default()
     }
 
     fn save(&self, cfg: &Config) -> std::io::Result<()> {
         if let Some(parent) = self.path.parent() {
             fs::create_dir_all(parent)?;
         }
-        let data = serde_json::to_vec_pretty(cfg).unwrap_or_default();
+        let data = toml::to_string_pretty(cfg).map_err(std::io::Error::other)?;
         fs::write(&self.path, data)
     }
 }
@@ -105,7 +412,7 @@ mod tests {
     #[test]
     fn roundtrip_default_config() {
         let dir = tempdir().unwrap();
-        let path = dir.path().join("config.json");
+        let path = dir.path().join("config.toml");
         let store = FileConfigStore::with_path(&path);
         let cfg = Config::default();
         store.save(&cfg).unwrap();
@@ -116,7 +423,7 @@ mod tests {
     #[test]
     fn save_and_load_custom_config() {
         let dir = tempdir().unwrap();
-        let path = dir.path().join("config.json");
+        let path = dir.path().join("config.toml");
         let store = FileConfigStore::with_path(&path);
         let cfg = Config {
             number_of_words: 50,
@@ -127,9 +434,70 @@ mod tests {
             strict: true,
             symbols: true,
             substitute: true,
+            incorrect_glyph: Some('▢'),
+            min_accuracy: Some(92.5),
+            ..Config::default()
         };
         store.save(&cfg).unwrap();
         let loaded = store.load();
         assert_eq!(cfg, loaded);
     }
+
+    #[test]
+    fn malformed_or_partial_config_falls_back_to_defaults() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let store = FileConfigStore::with_path(&path);
+
+        fs::write(&path, "number_of_words = [oops").unwrap();
+        assert_eq!(store.load(), Config::default());
+
+        fs::write(&path, "strict = true\nnumber_of_secs = 30\n").unwrap();
+        let loaded = store.load();
+        assert!(loaded.strict);
+        assert_eq!(loaded.number_of_secs, Some(30));
+        assert_eq!(loaded.number_of_words, 15);
+    }
+
+    fn parse_with(config: &Config, args: &[&str]) -> crate::Cli {
+        use clap::{CommandFactory, FromArgMatches};
+        let matches = config
+            .apply_defaults(crate::Cli::command())
+            .try_get_matches_from(args)
+            .unwrap();
+        crate::Cli::from_arg_matches(&matches).unwrap()
+    }
+
+    #[test]
+    fn config_defaults_apply_but_cli_flags_win() {
+        let config = Config {
+            number_of_words: 40,
+            number_of_secs: Some(30),
+            supported_language: "english1k".into(),
+            strict: true,
+            spell_out: true,
+            ..Config::default()
+        };
+
+        let cli = parse_with(&config, &["klik"]);
+        assert_eq!(cli.number_of_words, 40);
+        assert_eq!(cli.number_of_secs, Some(30));
+        assert!(matches!(
+            cli.supported_language,
+            crate::SupportedLanguage::English1k
+        ));
+        assert!(cli.strict);
+        assert!(cli.spell_out);
+        assert!(!cli.capitalize);
+
+        let cli = parse_with(&config, &["klik", "-w", "5", "-s", "90"]);
+        assert_eq!(cli.number_of_words, 5);
+        assert_eq!(cli.number_of_secs, Some(90));
+    }
+
+    #[test]
+    fn default_config_matches_cli_defaults() {
+        let cli = parse_with(&Config::default(), &["klik"]);
+        assert_eq!(Config::from(&cli), Config::default());
+    }
 }
@@ -0,0 +1,163 @@
+use crate::thok::Thok;
+use std::process::ExitCode;
+
+/// Final numbers of a completed test, used to decide the process exit code
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SessionResult {
+    pub wpm: f64,
+    pub accuracy: f64,
+    /// Mistakes counted against `--max-errors`, see `Session::errors`
+    pub errors: usize,
+}
+
+impl SessionResult {
+    /// Capture the result of a finished test (after `calc_results`)
+    pub fn from_thok(thok: &Thok) -> Self {
+        Self {
+            wpm: thok.wpm(),
+            accuracy: thok.accuracy(),
+            errors: thok.session.errors(),
+        }
+    }
+}
+
+/// Pass/fail thresholds checked against the final result
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ResultGate {
+    pub min_accuracy: Option<f64>,
+    pub max_errors: Option<usize>,
+}
+
+impl ResultGate {
+    pub fn is_configured(&self) -> bool {
+        self.min_accuracy.is_some() || self.max_errors.is_some()
+    }
+
+    pub fn passes(&self, result: &SessionResult) -> bool {
+        let accuracy_ok = self.min_accuracy.is_none_or(|min| result.accuracy >= min);
+        let errors_ok = self.max_errors.is_none_or(|max| result.errors <= max);
+        accuracy_ok && errors_ok
+    }
+}
+
+/// Process exit status for scripted runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+    /// Test completed and every configured gate passed (or no gate was set)
+    Ok,
+    /// Test completed but a `--min-accuracy`/`--max-errors` gate failed
+    FailedGate,
+    /// A gate was configured but the user quit before finishing a test
+    Incomplete,
+}
+
+impl ExitStatus {
+    pub fn evaluate(result: Option<&SessionResult>, gate: &ResultGate) -> Self {
+        match result {
+            Some(r) if gate.passes(r) => ExitStatus::Ok,
+            Some(_) => ExitStatus::FailedGate,
+            None if gate.is_configured() => ExitStatus::Incomplete,
+            None => ExitStatus::Ok,
+        }
+    }
+
+    /// Numeric code; 1 is left for runtime errors returned from `main`
+    pub fn code(self) -> u8 {
+        match self {
+            ExitStatus::Ok => 0,
+            ExitStatus::FailedGate => 2,
+            ExitStatus::Incomplete => 3,
+        }
+    }
+}
+
+impl From<ExitStatus> for ExitCode {
+    fn from(status: ExitStatus) -> Self {
+        ExitCode::from(status.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(accuracy: f64, errors: usize) -> SessionResult {
+        SessionResult {
+            wpm: 60.0,
+            accuracy,
+            errors,
+        }
+    }
+
+    #[test]
+    fn test_no_gate_is_always_ok() {
+        let gate = ResultGate::default();
+        assert_eq!(
+            ExitStatus::evaluate(Some(&result(10.0, 50)), &gate).code(),
+            0
+        );
+        assert_eq!(ExitStatus::evaluate(None, &gate).code(), 0);
+    }
+
+    #[test]
+    fn test_min_accuracy_gate() {
+        let gate = ResultGate {
+            min_accuracy: Some(95.0),
+            max_errors: None,
+        };
+        assert_eq!(
+            ExitStatus::evaluate(Some(&result(95.0, 3)), &gate),
+            ExitStatus::Ok
+        );
+        assert_eq!(
+            ExitStatus::evaluate(Some(&result(94.0, 3)), &gate),
+            ExitStatus::FailedGate
+        );
+        assert_eq!(ExitStatus::FailedGate.code(), 2);
+    }
+
+    #[test]
+    fn test_max_errors_gate() {
+        let gate = ResultGate {
+            min_accuracy: None,
+            max_errors: Some(2),
+        };
+        assert_eq!(
+            ExitStatus::evaluate(Some(&result(80.0, 2)), &gate),
+            ExitStatus::Ok
+        );
+        assert_eq!(
+            ExitStatus::evaluate(Some(&result(99.0, 3)), &gate),
+            ExitStatus::FailedGate
+        );
+    }
+
+    #[test]
+    fn test_gate_without_result_is_incomplete() {
+        let gate = ResultGate {
+            min_accuracy: Some(90.0),
+            max_errors: None,
+        };
+        assert_eq!(ExitStatus::evaluate(None, &gate), ExitStatus::Incomplete);
+        assert_eq!(ExitStatus::Incomplete.code(), 3);
+    }
+
+    #[test]
+    fn test_session_result_counts_corrected_errors() {
+        let mut thok = Thok::with_stats_store(
+            "ab".to_string(),
+            1,
+            None,
+            true,
+            Box::new(crate::stats::NoopStatsStore),
+        );
+        thok.write('x');
+        thok.write('a');
+        thok.write('b');
+        thok.session.calc_results();
+
+        let result = SessionResult::from_thok(&thok);
+        assert_eq!(result.errors, 1);
+        assert_eq!(result.accuracy, 100.0);
+    }
+}
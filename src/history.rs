@@ -0,0 +1,184 @@
+use std::io::Read;
+use std::path::Path;
+
+/// Most sessions plotted on the History screen; older ones are left off the charts
+pub const MAX_PLOTTED_SESSIONS: usize = 200;
+
+/// A single session summary row read back from the CSV log
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    pub date: String,
+    pub wpm: Option<f64>,
+    pub accuracy: Option<f64>,
+    pub std_dev: Option<f64>,
+}
+
+/// Parse session summaries from CSV data.
+///
+/// Columns are looked up by header name so older logs with fewer or reordered
+/// columns still load. Rows that are too short or hold unparsable values keep
+/// their remaining fields and report the bad ones as `None`. Runs flagged as
+/// suspicious (e.g. pasted) are left out.
+pub fn parse_history<R: Read>(reader: R) -> Vec<HistoryEntry> {
+    let mut csv_reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+
+    let headers = match csv_reader.headers() {
+        Ok(h) => h.clone(),
+        Err(_) => return Vec::new(),
+    };
+    let column = |name: &str| headers.iter().position(|h| h == name);
+    let date_idx = column("date");
+    let wpm_idx = column("wpm");
+    let accuracy_idx = column("accuracy");
+    let std_dev_idx = column("std_dev");
+    let suspicious_idx = column("suspicious");
+
+    csv_reader
+        .records()
+        .filter_map(|record| record.ok())
+        .filter(|record| suspicious_idx.and_then(|i| record.get(i)) != Some("true"))
+        .map(|record| {
+            let number = |idx: Option<usize>| {
+                idx.and_then(|i| record.get(i))
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .filter(|v| v.is_finite())
+            };
+            HistoryEntry {
+                date: date_idx
+                    .and_then(|i| record.get(i))
+                    .unwrap_or_default()
+                    .to_string(),
+                wpm: number(wpm_idx),
+                accuracy: number(accuracy_idx),
+                std_dev: number(std_dev_idx),
+            }
+        })
+        .collect()
+}
+
+/// Load session summaries from a CSV log file, returning nothing if it can't be read
+pub fn load_history_from<P: AsRef<Path>>(path: P) -> Vec<HistoryEntry> {
+    match std::fs::File::open(path) {
+        Ok(file) => parse_history(file),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Load session summaries from the CSV log of `profile` (`None` for the default one)
+pub fn load_history(profile: Option<&str>) -> Vec<HistoryEntry> {
+    crate::app_dirs::AppDirs::log_path(profile)
+        .map(load_history_from)
+        .unwrap_or_default()
+}
+
+/// The most recent `MAX_PLOTTED_SESSIONS` entries, paired with their 1-based session number
+fn plotted(entries: &[HistoryEntry]) -> impl Iterator<Item = (f64, &HistoryEntry)> {
+    let skip = entries.len().saturating_sub(MAX_PLOTTED_SESSIONS);
+    entries
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .map(|(i, e)| ((i + 1) as f64, e))
+}
+
+/// WPM per session as chart points: (session number, wpm).
+/// Sessions without a wpm value are skipped but keep their position on the x axis.
+pub fn wpm_series(entries: &[HistoryEntry]) -> Vec<(f64, f64)> {
+    plotted(entries)
+        .filter_map(|(x, e)| e.wpm.map(|wpm| (x, wpm)))
+        .collect()
+}
+
+/// Consistency (std dev) per session as chart points: (session number, std_dev).
+/// Sessions without a std_dev value are skipped but keep their position on the x axis.
+pub fn consistency_series(entries: &[HistoryEntry]) -> Vec<(f64, f64)> {
+    plotted(entries)
+        .filter_map(|(x, e)| e.std_dev.map(|sd| (x, sd)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const SEEDED_CSV: &str = "\
+date,num_words,num_secs,elapsed_secs,wpm,accuracy,std_dev
+Mon Jan  1 10:00:00 2024,15,,12.00,55,96,3.50
+Tue Jan  2 10:00:00 2024,15,,11.00,60,98,
+Wed Jan  3 10:00:00 2024,15,,10.50,62,97,oops
+Thu Jan  4 10:00:00 2024,15,30.00,30.00,64,99,2.25
+Fri Jan  5 10:00:00 2024,15
+";
+
+    #[test]
+    fn test_consistency_series_from_seeded_csv() {
+        let entries = parse_history(SEEDED_CSV.as_bytes());
+        assert_eq!(entries.len(), 5);
+
+        let series = consistency_series(&entries);
+        assert_eq!(series, vec![(1.0, 3.5), (4.0, 2.25)]);
+    }
+
+    #[test]
+    fn test_wpm_series_from_seeded_csv() {
+        let entries = parse_history(SEEDED_CSV.as_bytes());
+        assert_eq!(
+            wpm_series(&entries),
+            vec![(1.0, 55.0), (2.0, 60.0), (3.0, 62.0), (4.0, 64.0)]
+        );
+    }
+
+    #[test]
+    fn test_series_capped_to_most_recent_sessions() {
+        let entries: Vec<HistoryEntry> = (0..MAX_PLOTTED_SESSIONS + 50)
+            .map(|i| HistoryEntry {
+                date: i.to_string(),
+                wpm: Some(i as f64),
+                accuracy: None,
+                std_dev: Some(1.0),
+            })
+            .collect();
+
+        let wpm = wpm_series(&entries);
+        assert_eq!(wpm.len(), MAX_PLOTTED_SESSIONS);
+        assert_eq!(wpm.first(), Some(&(51.0, 50.0)));
+        assert_eq!(wpm.last(), Some(&(250.0, 249.0)));
+        assert_eq!(consistency_series(&entries).len(), MAX_PLOTTED_SESSIONS);
+    }
+
+    #[test]
+    fn test_parse_history_handles_short_rows() {
+        let entries = parse_history(SEEDED_CSV.as_bytes());
+        let last = entries.last().unwrap();
+        assert_eq!(last.date, "Fri Jan  5 10:00:00 2024");
+        assert_eq!(last.wpm, None);
+        assert_eq!(last.std_dev, None);
+        assert_eq!(entries[0].wpm, Some(55.0));
+        assert_eq!(entries[0].accuracy, Some(96.0));
+    }
+
+    #[test]
+    fn test_parse_history_old_log_without_std_dev_column() {
+        let csv = "date,num_words,num_secs,elapsed_secs,wpm,accuracy\nx,15,,10.0,40,90\n";
+        let entries = parse_history(csv.as_bytes());
+        assert_eq!(entries.len(), 1);
+        assert!(consistency_series(&entries).is_empty());
+    }
+
+    #[test]
+    fn test_parse_history_skips_suspicious_runs() {
+        let csv = "date,wpm,accuracy,std_dev,suspicious\na,40,90,1.0,false\nb,900,100,0.0,true\nc,45,92,1.5,false\n";
+        let entries = parse_history(csv.as_bytes());
+        assert_eq!(wpm_series(&entries), vec![(1.0, 40.0), (2.0, 45.0)]);
+    }
+
+    #[test]
+    fn test_load_history_missing_file() {
+        let dir = tempdir().unwrap();
+        assert!(load_history_from(dir.path().join("missing.csv")).is_empty());
+    }
+}
@@ -1,14 +1,24 @@
 use include_dir::{include_dir, Dir};
 use serde::Deserialize;
 use serde_json::from_str;
+use std::io;
+use std::path::Path;
 
 static LANG_DIR: Dir = include_dir!("src/lang");
 
+/// Contents of a bundled file under `src/lang`, if present and valid UTF-8
+pub(crate) fn read_lang_file(file_name: &str) -> Option<&'static str> {
+    LANG_DIR.get_file(file_name)?.contents_utf8()
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct Language {
     pub name: String,
     pub size: u32,
     pub words: Vec<String>,
+    /// Relative frequency of each word, parallel to `words`; sampling is uniform without it
+    #[serde(default)]
+    pub weights: Option<Vec<f64>>,
 }
 
 impl Language {
@@ -24,6 +34,41 @@ impl Language {
 
         from_str(file_as_str).unwrap_or_else(|e| panic!("Unable to deserialize {file_name}: {e}"))
     }
+
+    /// Build an ad-hoc language from newline-separated words, skipping blank lines
+    pub fn from_word_lines(contents: &str) -> Self {
+        let words: Vec<String> = contents
+            .lines()
+            .map(str::trim)
+            .filter(|w| !w.is_empty())
+            .map(String::from)
+            .collect();
+        Self {
+            name: "custom".to_string(),
+            size: words.len() as u32,
+            words,
+            weights: None,
+        }
+    }
+
+    /// The word weights, if present and one per word
+    pub fn word_weights(&self) -> Option<&[f64]> {
+        self.weights
+            .as_deref()
+            .filter(|weights| weights.len() == self.words.len())
+    }
+
+    /// Read a user word list from disk at runtime
+    pub fn from_wordlist_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let language = Self::from_word_lines(&std::fs::read_to_string(path)?);
+        if language.words.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "word list contains no words",
+            ));
+        }
+        Ok(language)
+    }
 }
 
 #[cfg(test)]
@@ -75,6 +120,49 @@ mod tests {
         assert!(lang.words.contains(&"hello".to_string()));
         assert!(lang.words.contains(&"world".to_string()));
         assert!(lang.words.contains(&"test".to_string()));
+        assert!(lang.word_weights().is_none());
+    }
+
+    #[test]
+    fn test_weighted_language_deserialization() {
+        let json_data = r#"
+        {
+            "name": "test",
+            "size": 3,
+            "words": ["hello", "world", "test"],
+            "weights": [3.0, 1.5, 1.0]
+        }
+        "#;
+
+        let lang: Language = from_str(json_data).expect("Failed to deserialize test language");
+        assert_eq!(lang.words.len(), 3);
+        assert_eq!(lang.word_weights(), Some([3.0, 1.5, 1.0].as_slice()));
+
+        // A weights array that doesn't line up with the words is ignored
+        let lang: Language =
+            from_str(r#"{"name": "t", "size": 2, "words": ["a", "b"], "weights": [1.0]}"#).unwrap();
+        assert!(lang.word_weights().is_none());
+    }
+
+    #[test]
+    fn test_from_word_lines_trims_and_skips_blanks() {
+        let lang = Language::from_word_lines("  alpha \n\n\tbeta\r\n   \ngamma");
+
+        assert_eq!(lang.name, "custom");
+        assert_eq!(lang.size, 3);
+        assert_eq!(lang.words, vec!["alpha", "beta", "gamma"]);
+    }
+
+    #[test]
+    fn test_from_wordlist_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("words.txt");
+        std::fs::write(&path, "one\ntwo\n").unwrap();
+        assert_eq!(Language::from_wordlist_file(&path).unwrap().size, 2);
+
+        std::fs::write(&path, "\n  \n").unwrap();
+        assert!(Language::from_wordlist_file(&path).is_err());
+        assert!(Language::from_wordlist_file(dir.path().join("missing.txt")).is_err());
     }
 
     #[test]
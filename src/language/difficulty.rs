@@ -10,3 +10,62 @@ pub struct CharacterDifficulty {
     pub uppercase_attempts: i64,  // Total uppercase attempts for weighting
     pub uppercase_penalty: f64,   // Additional difficulty penalty for uppercase (0-1)
 }
+
+impl CharacterDifficulty {
+    /// Combined practice score (higher = more practice needed), see [`practice_score`]
+    pub fn practice_score(&self) -> f64 {
+        practice_score(self.miss_rate, self.avg_time_ms)
+    }
+}
+
+/// Miss rate weighted double plus a penalty for presses slower than 200ms;
+/// the score the intelligent selector ranks characters by
+pub fn practice_score(miss_rate: f64, avg_time_ms: f64) -> f64 {
+    let miss_penalty = miss_rate * 2.0;
+    let timing_penalty = if avg_time_ms > 200.0 {
+        (avg_time_ms - 200.0) / 100.0
+    } else {
+        0.0
+    };
+    miss_penalty + timing_penalty
+}
+
+/// Difficulty of the transition between two adjacent characters, keyed by `(previous, next)`
+#[derive(Debug, Clone)]
+pub struct BigramDifficulty {
+    pub miss_rate: f64, // Percentage of incorrect attempts at the second character (0-100)
+    pub avg_time_ms: f64, // Average time from the first key to the second
+    pub total_attempts: i64, // Total number of attempts for weighting
+}
+
+impl BigramDifficulty {
+    /// Penalty for this transition, on the same scale as `CharacterDifficulty::practice_score`
+    pub fn transition_penalty(&self) -> f64 {
+        let miss_penalty = self.miss_rate * 2.0;
+        let timing_penalty = if self.avg_time_ms > 200.0 {
+            (self.avg_time_ms - 200.0) / 100.0
+        } else {
+            0.0
+        };
+        miss_penalty + timing_penalty
+    }
+}
+
+/// The `count` characters with the highest practice score, worst first
+pub fn rank_weakest_characters(
+    char_stats: &std::collections::HashMap<char, CharacterDifficulty>,
+    count: usize,
+) -> Vec<(char, f64)> {
+    let mut ranked: Vec<(char, f64)> = char_stats
+        .iter()
+        .map(|(ch, difficulty)| (*ch, difficulty.practice_score()))
+        .collect();
+    // Highest score first; ties broken by character for a stable order
+    ranked.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.0.cmp(&b.0))
+    });
+    ranked.truncate(count);
+    ranked
+}
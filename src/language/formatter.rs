@@ -1,17 +1,19 @@
+use super::CharacterDifficulty;
 use rand::seq::SliceRandom;
-use rand::Rng;
+use rand::{Rng, RngCore};
+use std::collections::HashMap;
 
 /// Trait for text formatting strategies
 pub trait TextFormatter {
-    /// Format a list of words into a final string
-    fn format(&self, words: Vec<String>) -> String;
+    /// Format a list of words into a final string, drawing randomness from `rng`
+    fn format(&self, words: Vec<String>, rng: &mut dyn RngCore) -> String;
 }
 
 /// Basic formatter that just joins words with spaces
 pub struct BasicFormatter;
 
 impl TextFormatter for BasicFormatter {
-    fn format(&self, words: Vec<String>) -> String {
+    fn format(&self, words: Vec<String>, _rng: &mut dyn RngCore) -> String {
         words.join(" ")
     }
 }
@@ -20,12 +22,11 @@ impl TextFormatter for BasicFormatter {
 pub struct CapitalizationFormatter;
 
 impl TextFormatter for CapitalizationFormatter {
-    fn format(&self, words: Vec<String>) -> String {
+    fn format(&self, words: Vec<String>, rng: &mut dyn RngCore) -> String {
         if words.is_empty() {
             return String::new();
         }
 
-        let rng = &mut rand::thread_rng();
         let mut result = Vec::new();
 
         for (i, word) in words.iter().enumerate() {
@@ -144,6 +145,10 @@ fn format_with_symbols(words: &[String], capitalize: bool, rng: &mut impl Rng) -
 
     result.push(pick_extended_final_punct(rng).to_string());
 
+    finish_symbol_text(result, capitalize)
+}
+
+fn finish_symbol_text(result: Vec<String>, capitalize: bool) -> String {
     let mut text = clean_punctuation_spacing(result.join(" "));
 
     // Safety net: ensure the first alphabetic character is capitalized
@@ -160,15 +165,92 @@ fn format_with_symbols(words: &[String], capitalize: bool, rng: &mut impl Rng) -
     text
 }
 
+/// Openers the balanced formatter may insert, each with the closer it pushes
+const BALANCED_PAIRS: [(char, char); 6] = [
+    ('(', ')'),
+    ('[', ']'),
+    ('{', '}'),
+    ('"', '"'),
+    ('\'', '\''),
+    ('`', '`'),
+];
+
+/// Like `add_symbol_to_word`, but brackets and quotes only ever open: the closer goes on
+/// `open` for a later word to emit. A quote that is already open is not opened again, so
+/// the result can always be read back unambiguously.
+fn add_balanced_symbol_to_word(word: &str, open: &mut Vec<char>, rng: &mut impl Rng) -> String {
+    match rng.gen_range(0..4) {
+        0 => {
+            let (opener, closer) = *BALANCED_PAIRS.choose(rng).unwrap();
+            if opener == closer && open.contains(&closer) {
+                return word.to_string();
+            }
+            open.push(closer);
+            format!("{opener}{word}")
+        }
+        1 => {
+            let symbol = MATHEMATICAL.choose(rng).unwrap();
+            if rng.gen_bool(0.5) {
+                format!("{symbol}{word}")
+            } else {
+                format!("{word}{symbol}")
+            }
+        }
+        // The backtick and the quotes are left to the pairs above
+        2 => {
+            let symbol = PROGRAMMING[..PROGRAMMING.len() - 1].choose(rng).unwrap();
+            format!("{symbol}{word}")
+        }
+        _ => {
+            let symbol = PUNCTUATION_SYMBOLS[..2].choose(rng).unwrap();
+            format!("{word}{symbol}")
+        }
+    }
+}
+
+/// Format words with symbol decorations whose brackets and quotes always close, innermost
+/// first, by the end of the prompt
+fn format_with_balanced_symbols(words: &[String], capitalize: bool, rng: &mut impl Rng) -> String {
+    let mut result = Vec::new();
+    let mut open = Vec::new();
+
+    for (i, word) in words.iter().enumerate() {
+        let mut formatted_word = word.clone();
+
+        if capitalize && (i == 0 || rng.gen_bool(0.2)) {
+            formatted_word = capitalize_first_letter(&formatted_word);
+        }
+
+        if rng.gen_bool(0.25) {
+            formatted_word = add_balanced_symbol_to_word(&formatted_word, &mut open, rng);
+        }
+
+        let is_last = i == words.len() - 1;
+        while !open.is_empty() && (is_last || rng.gen_bool(0.4)) {
+            formatted_word.extend(open.pop());
+        }
+
+        result.push(formatted_word);
+
+        if !is_last {
+            maybe_add_separator(&mut result, rng);
+        }
+    }
+
+    result.push(pick_extended_final_punct(rng).to_string());
+
+    finish_symbol_text(result, capitalize)
+}
+
 /// Formatter that adds symbols and special characters
 pub struct SymbolFormatter;
 
 impl TextFormatter for SymbolFormatter {
-    fn format(&self, words: Vec<String>) -> String {
+    fn format(&self, words: Vec<String>, mut rng: &mut dyn RngCore) -> String {
         if words.is_empty() {
             return String::new();
         }
-        format_with_symbols(&words, false, &mut rand::thread_rng())
+        format_with_symbols(&words, false, &mut rng)
     }
 }
 
@@ -176,11 +258,133 @@ impl TextFormatter for SymbolFormatter {
 pub struct CombinedFormatter;
 
 impl TextFormatter for CombinedFormatter {
-    fn format(&self, words: Vec<String>) -> String {
+    fn format(&self, words: Vec<String>, mut rng: &mut dyn RngCore) -> String {
+        if words.is_empty() {
+            return String::new();
+        }
+        format_with_symbols(&words, true, &mut rng)
+    }
+}
+
+/// Symbol formatter (`--balanced-symbols`) whose brackets and quotes span one or more
+/// words and are always closed again, so the prompt reads like nested code or prose
+pub struct BalancedSymbolFormatter {
+    pub capitalize: bool,
+}
+
+impl TextFormatter for BalancedSymbolFormatter {
+    fn format(&self, words: Vec<String>, mut rng: &mut dyn RngCore) -> String {
         if words.is_empty() {
             return String::new();
         }
-        format_with_symbols(&words, true, &mut rand::thread_rng())
+        format_with_balanced_symbols(&words, self.capitalize, &mut rng)
+    }
+}
+
+/// Formatter for code tokens: groups them into `;`-terminated statements of 3-6 tokens and
+/// decorates some identifiers the way code uses them, e.g. `len()`, `.iter` or `&self`
+pub struct CodeFormatter;
+
+impl TextFormatter for CodeFormatter {
+    fn format(&self, words: Vec<String>, mut rng: &mut dyn RngCore) -> String {
+        let mut statements = Vec::new();
+        let mut current = Vec::new();
+        let mut target = rng.gen_range(3..=6);
+
+        for word in words {
+            let is_identifier = word.chars().all(|c| c.is_alphanumeric() || c == '_');
+            current.push(if is_identifier && rng.gen_bool(0.3) {
+                add_code_symbol_to_word(&word, &mut rng)
+            } else {
+                word
+            });
+            if current.len() == target {
+                statements.push(format!("{};", current.join(" ")));
+                current.clear();
+                target = rng.gen_range(3..=6);
+            }
+        }
+        if !current.is_empty() {
+            statements.push(format!("{};", current.join(" ")));
+        }
+        statements.join(" ")
+    }
+}
+
+fn add_code_symbol_to_word(word: &str, rng: &mut impl Rng) -> String {
+    match rng.gen_range(0..5) {
+        0 => format!("{word}()"),
+        1 => format!(".{word}"),
+        2 => format!("&{word}"),
+        3 => format!("{word}?"),
+        _ => format!("[{word}]"),
+    }
+}
+
+/// Chance a letter is capitalized by `UppercaseDrillFormatter` when its capital is typed as
+/// well as the lowercase; the `uppercase_penalty` of weaker capitals raises it towards
+/// `UPPERCASE_DRILL_BASE + UPPERCASE_DRILL_SPAN`
+const UPPERCASE_DRILL_BASE: f64 = 0.35;
+const UPPERCASE_DRILL_SPAN: f64 = 0.6;
+/// Penalty assumed for letters without stats, matching `get_character_difficulties`
+const UPPERCASE_DRILL_UNKNOWN_PENALTY: f64 = 0.5;
+
+/// Formatter for `--uppercase-drill`: capitalizes letters anywhere in a word, not just the
+/// first, and the letters whose capitals lag furthest behind most often
+pub struct UppercaseDrillFormatter {
+    pub difficulties: HashMap<char, CharacterDifficulty>,
+}
+
+impl UppercaseDrillFormatter {
+    /// Chance of capitalizing `c`, from its `uppercase_penalty`
+    fn rate(&self, c: char) -> f64 {
+        let penalty = self
+            .difficulties
+            .get(&c)
+            .map_or(UPPERCASE_DRILL_UNKNOWN_PENALTY, |d| d.uppercase_penalty);
+        UPPERCASE_DRILL_BASE + UPPERCASE_DRILL_SPAN * penalty.clamp(0.0, 1.0)
+    }
+}
+
+impl TextFormatter for UppercaseDrillFormatter {
+    fn format(&self, words: Vec<String>, rng: &mut dyn RngCore) -> String {
+        words
+            .join(" ")
+            .chars()
+            .map(|c| {
+                if c.is_lowercase() && rng.gen_bool(self.rate(c)) {
+                    c.to_uppercase().next().unwrap_or(c)
+                } else {
+                    c
+                }
+            })
+            .collect()
+    }
+}
+
+/// Formatter for `--title-case`: the first letter of every whitespace-separated word is
+/// capitalized. It runs after the other formatters, so a word opened by a symbol like `(word`
+/// gets its first letter capitalized and an already capitalized word is left as it is.
+pub struct TitleCaseFormatter;
+
+impl TextFormatter for TitleCaseFormatter {
+    fn format(&self, words: Vec<String>, _rng: &mut dyn RngCore) -> String {
+        let mut at_word_start = true;
+        words
+            .join(" ")
+            .chars()
+            .map(|c| {
+                if c.is_whitespace() {
+                    at_word_start = true;
+                    c
+                } else if at_word_start && c.is_alphabetic() {
+                    at_word_start = false;
+                    c.to_uppercase().next().unwrap_or(c)
+                } else {
+                    c
+                }
+            })
+            .collect()
     }
 }
 
@@ -221,11 +425,11 @@ impl CompositeFormatter {
 }
 
 impl TextFormatter for CompositeFormatter {
-    fn format(&self, words: Vec<String>) -> String {
+    fn format(&self, words: Vec<String>, rng: &mut dyn RngCore) -> String {
         self.formatters
             .iter()
             .fold(words, |current_words, formatter| {
-                let formatted = formatter.format(current_words);
+                let formatted = formatter.format(current_words, rng);
                 vec![formatted]
             })
             .into_iter()
@@ -252,16 +456,51 @@ mod tests {
         let formatter = BasicFormatter;
         let words = vec!["hello".to_string(), "world".to_string()];
 
-        let result = formatter.format(words);
+        let result = formatter.format(words, &mut rand::thread_rng());
         assert_eq!(result, "hello world");
     }
 
+    #[test]
+    fn test_title_case_formatter_capitalizes_every_word() {
+        let words: Vec<String> = ["hello", "wide", "world", "élan", "again"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let mut rng = rand::thread_rng();
+
+        let titled = TitleCaseFormatter.format(words.clone(), &mut rng);
+        assert_eq!(titled, "Hello Wide World Élan Again");
+        for word in titled.split_whitespace() {
+            assert!(word.chars().next().unwrap().is_uppercase(), "{word}");
+        }
+
+        // After symbols (and the capitalized first word), each word's first letter is capital
+        for _ in 0..20 {
+            let formatted = CompositeFormatter::new()
+                .add_formatter(CompositeFormatter::build_from_flags(true, true))
+                .add_formatter(Box::new(TitleCaseFormatter))
+                .format(words.clone(), &mut rng);
+            for word in formatted.split_whitespace() {
+                if let Some(first) = word.chars().find(|c| c.is_alphabetic()) {
+                    assert!(first.is_uppercase(), "{word} in {formatted}");
+                }
+                // Only the first letter changes case
+                let rest: String = word
+                    .chars()
+                    .skip_while(|c| !c.is_alphabetic())
+                    .skip(1)
+                    .collect();
+                assert_eq!(rest, rest.to_lowercase(), "{word} in {formatted}");
+            }
+        }
+    }
+
     #[test]
     fn test_capitalization_formatter() {
         let formatter = CapitalizationFormatter;
         let words = vec!["hello".to_string(), "world".to_string()];
 
-        let result = formatter.format(words);
+        let result = formatter.format(words, &mut rand::thread_rng());
 
         assert!(result.chars().next().unwrap().is_uppercase());
         assert!(result.ends_with('.') || result.ends_with('!') || result.ends_with('?'));
@@ -272,7 +511,7 @@ mod tests {
         let formatter = SymbolFormatter;
         let words = vec!["hello".to_string(), "world".to_string()];
 
-        let result = formatter.format(words);
+        let result = formatter.format(words, &mut rand::thread_rng());
 
         assert!(
             result.ends_with('.')
@@ -293,28 +532,136 @@ mod tests {
 
         // Test basic (no flags)
         let basic_formatter = CompositeFormatter::build_from_flags(false, false);
-        let basic_result = basic_formatter.format(words.clone());
+        let basic_result = basic_formatter.format(words.clone(), &mut rand::thread_rng());
         assert_eq!(basic_result, "hello world");
 
         // Test capitalization only
         let cap_formatter = CompositeFormatter::build_from_flags(true, false);
-        let cap_result = cap_formatter.format(words.clone());
+        let cap_result = cap_formatter.format(words.clone(), &mut rand::thread_rng());
         assert!(cap_result.chars().next().unwrap().is_uppercase());
 
         // Test symbols only
         let sym_formatter = CompositeFormatter::build_from_flags(false, true);
-        let sym_result = sym_formatter.format(words.clone());
+        let sym_result = sym_formatter.format(words.clone(), &mut rand::thread_rng());
         assert!(!sym_result.is_empty());
 
         // Test combined
         let combined_formatter = CompositeFormatter::build_from_flags(true, true);
-        let combined_result = combined_formatter.format(words);
+        let combined_result = combined_formatter.format(words, &mut rand::thread_rng());
         let first_alpha_char = combined_result.chars().find(|c| c.is_alphabetic());
         if let Some(first_char) = first_alpha_char {
             assert!(first_char.is_uppercase());
         }
     }
 
+    /// Whether every bracket and quote in `text` closes, innermost first
+    fn is_balanced(text: &str) -> bool {
+        let mut open = Vec::new();
+        for c in text.chars() {
+            match c {
+                '(' => open.push(')'),
+                '[' => open.push(']'),
+                '{' => open.push('}'),
+                ')' | ']' | '}' if open.pop() != Some(c) => return false,
+                '"' | '\'' | '`' if open.last() == Some(&c) => {
+                    open.pop();
+                }
+                '"' | '\'' | '`' => open.push(c),
+                _ => {}
+            }
+        }
+        open.is_empty()
+    }
+
+    #[test]
+    fn test_balanced_symbol_formatter_always_closes() {
+        use rand::SeedableRng;
+
+        let words: Vec<String> = "the quick brown fox jumps over the lazy dog again and again"
+            .split(' ')
+            .map(String::from)
+            .collect();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let mut delimited = 0;
+
+        for capitalize in [false, true] {
+            let formatter = BalancedSymbolFormatter { capitalize };
+            for _ in 0..500 {
+                let result = formatter.format(words.clone(), &mut rng);
+                assert!(is_balanced(&result), "unbalanced: {result}");
+                if result.contains(['(', '[', '{', '"', '\'', '`']) {
+                    delimited += 1;
+                }
+            }
+        }
+        // The test is only meaningful if delimiters actually get inserted
+        assert!(delimited > 100);
+        assert!(!is_balanced("(a [b) c]"));
+        assert!(!is_balanced("\"a"));
+        assert_eq!(
+            BalancedSymbolFormatter { capitalize: false }.format(vec![], &mut rng),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_uppercase_drill_capitalizes_throughout_favoring_weak_capitals() {
+        use rand::SeedableRng;
+
+        let difficulty = |uppercase_penalty| CharacterDifficulty {
+            miss_rate: 5.0,
+            avg_time_ms: 200.0,
+            total_attempts: 50,
+            uppercase_miss_rate: 5.0,
+            uppercase_avg_time: 250.0,
+            uppercase_attempts: 20,
+            uppercase_penalty,
+        };
+        let formatter = UppercaseDrillFormatter {
+            difficulties: HashMap::from([('a', difficulty(1.0)), ('b', difficulty(0.0))]),
+        };
+        let words = vec!["ab".repeat(50), "cd".repeat(50)];
+        let result = formatter.format(words, &mut rand::rngs::StdRng::seed_from_u64(3));
+
+        // Letters are only recased, never added or dropped
+        assert_eq!(
+            result.to_lowercase(),
+            format!("{} {}", "ab".repeat(50), "cd".repeat(50))
+        );
+        let share = |c: char| {
+            let upper = c.to_ascii_uppercase();
+            result.chars().filter(|&r| r == upper).count() as f64 / 50.0
+        };
+        assert!(share('a') > 0.8, "weakest capital: {}", share('a'));
+        assert!(share('b') < 0.55, "strongest capital: {}", share('b'));
+        // Letters without stats land in between, well above the odd first letter
+        assert!(share('c') > 0.45 && share('c') < 0.85);
+
+        let capitals = result.chars().filter(|c| c.is_uppercase()).count();
+        let letters = result.chars().filter(|c| c.is_alphabetic()).count();
+        assert!(capitals as f64 / letters as f64 > 0.5);
+        // Capitals show up inside words, not just at their start
+        assert!(result
+            .split(' ')
+            .all(|w| w.chars().skip(1).any(char::is_uppercase)));
+    }
+
+    #[test]
+    fn test_code_formatter_builds_statements() {
+        let words: Vec<String> = ["let", "x", "=", "value", "fn", "main", "()", "{}"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+
+        let result = CodeFormatter.format(words.clone(), &mut rand::thread_rng());
+
+        assert!(result.ends_with(';'));
+        // Decorations stay attached, so the token count is unchanged
+        assert_eq!(result.split_whitespace().count(), words.len());
+        assert!(result.contains("=") && result.contains("{}"));
+        assert_eq!(CodeFormatter.format(vec![], &mut rand::thread_rng()), "");
+    }
+
     #[test]
     fn test_capitalize_first_letter() {
         assert_eq!(capitalize_first_letter("hello"), "Hello");
@@ -328,25 +675,35 @@ mod tests {
     fn test_formatters_with_empty_input() {
         let empty_words = vec![];
 
-        assert_eq!(BasicFormatter.format(empty_words.clone()), "");
-        assert_eq!(CapitalizationFormatter.format(empty_words.clone()), "");
-        assert_eq!(SymbolFormatter.format(empty_words.clone()), "");
+        assert_eq!(
+            BasicFormatter.format(empty_words.clone(), &mut rand::thread_rng()),
+            ""
+        );
+        assert_eq!(
+            CapitalizationFormatter.format(empty_words.clone(), &mut rand::thread_rng()),
+            ""
+        );
+        assert_eq!(
+            SymbolFormatter.format(empty_words.clone(), &mut rand::thread_rng()),
+            ""
+        );
     }
 
     #[test]
     fn test_formatters_with_single_word() {
         let single_word = vec!["test".to_string()];
 
-        let basic_result = BasicFormatter.format(single_word.clone());
+        let basic_result = BasicFormatter.format(single_word.clone(), &mut rand::thread_rng());
         assert_eq!(basic_result, "test");
 
-        let cap_result = CapitalizationFormatter.format(single_word.clone());
+        let cap_result =
+            CapitalizationFormatter.format(single_word.clone(), &mut rand::thread_rng());
         assert!(cap_result.starts_with("Test"));
         assert!(
             cap_result.ends_with('.') || cap_result.ends_with('!') || cap_result.ends_with('?')
         );
 
-        let sym_result = SymbolFormatter.format(single_word);
+        let sym_result = SymbolFormatter.format(single_word, &mut rand::thread_rng());
         assert!(!sym_result.is_empty());
     }
 
@@ -355,7 +712,7 @@ mod tests {
         let composite = CompositeFormatter::new().add_formatter(Box::new(BasicFormatter));
 
         let words = vec!["hello".to_string(), "world".to_string()];
-        let result = composite.format(words);
+        let result = composite.format(words, &mut rand::thread_rng());
         assert_eq!(result, "hello world");
     }
 
@@ -364,7 +721,7 @@ mod tests {
         let combined = CombinedFormatter;
         let empty_words = vec![];
 
-        let result = combined.format(empty_words);
+        let result = combined.format(empty_words, &mut rand::thread_rng());
         assert_eq!(result, "");
     }
 
@@ -373,7 +730,7 @@ mod tests {
         let combined = CombinedFormatter;
         let words = vec!["hello".to_string(), "world".to_string()];
 
-        let result = combined.format(words);
+        let result = combined.format(words, &mut rand::thread_rng());
 
         let first_alpha_char = result.chars().find(|c| c.is_alphabetic());
         if let Some(first_char) = first_alpha_char {
@@ -395,7 +752,7 @@ mod tests {
         let words = vec!["test".to_string(), "word".to_string()];
 
         for attempt in 0..100 {
-            let result = combined.format(words.clone());
+            let result = combined.format(words.clone(), &mut rand::thread_rng());
 
             let first_alpha_char = result.chars().find(|c| c.is_alphabetic());
             if let Some(first_char) = first_alpha_char {
@@ -419,7 +776,7 @@ mod tests {
         ];
 
         for attempt in 0..100 {
-            let result = combined.format(words.clone());
+            let result = combined.format(words.clone(), &mut rand::thread_rng());
 
             if result.starts_with(',') {
                 panic!("Found comma at beginning on attempt {attempt}: '{result}'");
@@ -1,5 +1,6 @@
 use super::core::Language;
 use crate::language::formatter::TextFormatter;
+use rand::{Rng, RngCore};
 // Deprecated: logic moved to formatter strategies; keep tests redirected
 
 impl Language {
@@ -10,6 +11,7 @@ impl Language {
         words: Vec<String>,
         include_capitalize: bool,
         include_symbols: bool,
+        rng: &mut dyn RngCore,
     ) -> String {
         use super::formatter::CompositeFormatter;
         if !include_capitalize && !include_symbols {
@@ -17,9 +19,8 @@ impl Language {
             if words.is_empty() {
                 return String::new();
             }
-            let mut text = super::formatter::BasicFormatter.format(words);
-            let rng = &mut rand::thread_rng();
-            let final_punct = match rand::Rng::gen_range(rng, 0..100) {
+            let mut text = super::formatter::BasicFormatter.format(words, rng);
+            let final_punct = match rng.gen_range(0..100) {
                 0..=79 => '.',
                 80..=94 => '!',
                 _ => '?',
@@ -28,7 +29,7 @@ impl Language {
             return text;
         }
         let formatter = CompositeFormatter::build_from_flags(include_capitalize, include_symbols);
-        formatter.format(words)
+        formatter.format(words, rng)
     }
 
     // Legacy helper removed
@@ -43,7 +44,7 @@ mod tests {
         let lang = Language::new("english".to_string());
         let words = vec!["hello".to_string(), "world".to_string(), "test".to_string()];
 
-        let result = lang.apply_advanced_formatting(words, true, false);
+        let result = lang.apply_advanced_formatting(words, true, false, &mut rand::thread_rng());
 
         // Should have capitalized first word and end with punctuation
         assert!(result.starts_with("Hello"));
@@ -61,7 +62,7 @@ mod tests {
         let lang = Language::new("english".to_string());
         let words = vec!["hello".to_string(), "world".to_string()];
 
-        let result = lang.apply_advanced_formatting(words, true, false);
+        let result = lang.apply_advanced_formatting(words, true, false, &mut rand::thread_rng());
 
         // Should have capitalization
         assert!(result.chars().next().unwrap().is_uppercase());
@@ -82,7 +83,8 @@ mod tests {
         let lang = Language::new("english".to_string());
         let words = vec!["hello".to_string(), "world".to_string()];
 
-        let result = lang.apply_advanced_formatting(words.clone(), false, true);
+        let result =
+            lang.apply_advanced_formatting(words.clone(), false, true, &mut rand::thread_rng());
 
         // Should NOT have capitalization (except potentially from symbols)
         // But basic words should remain lowercase
@@ -95,7 +97,8 @@ mod tests {
         let symbol_chars = "@#$%^&*()[]{}|\\~`+-=<>:;\"'";
         let mut found_symbols = false;
         for _ in 0..100 {
-            let test_result = lang.apply_advanced_formatting(words.clone(), false, true);
+            let test_result =
+                lang.apply_advanced_formatting(words.clone(), false, true, &mut rand::thread_rng());
             if test_result.chars().any(|c| symbol_chars.contains(c)) {
                 found_symbols = true;
                 break;
@@ -109,7 +112,7 @@ mod tests {
         let lang = Language::new("english".to_string());
         let words = vec!["hello".to_string(), "world".to_string()];
 
-        let result = lang.apply_advanced_formatting(words, false, false);
+        let result = lang.apply_advanced_formatting(words, false, false, &mut rand::thread_rng());
 
         // Should have basic punctuation but no capitalization or symbols
         assert!(result.ends_with('.') || result.ends_with('!') || result.ends_with('?'));
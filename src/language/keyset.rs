@@ -0,0 +1,156 @@
+use super::core::Language;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+use std::fmt;
+use std::str::FromStr;
+
+/// Fewer matching words than this and the prompt is padded with synthesized sequences
+pub const MIN_KEYSET_WORDS: usize = 20;
+
+/// Restricts practice to a set of keys, e.g. the home row or an explicit list like `asdf`
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeySet {
+    spec: String,
+    keys: Vec<char>,
+}
+
+impl KeySet {
+    pub fn keys(&self) -> &[char] {
+        &self.keys
+    }
+
+    /// Whether `word` can be typed using only keys from this set
+    pub fn allows(&self, word: &str) -> bool {
+        !word.is_empty()
+            && word
+                .chars()
+                .all(|c| self.keys.contains(&c.to_lowercase().next().unwrap_or(c)))
+    }
+
+    /// A copy of `language` holding only typeable words, padded with random key sequences when
+    /// too few real words survive so the prompt is never empty
+    pub fn restrict(&self, language: &Language, rng: &mut dyn RngCore) -> Language {
+        let kept: Vec<usize> = (0..language.words.len())
+            .filter(|&i| self.allows(&language.words[i]))
+            .collect();
+        let mut words: Vec<String> = kept.iter().map(|&i| language.words[i].clone()).collect();
+        // Synthesized padding is as rare as the rarest real word
+        let mut weights: Option<Vec<f64>> = language
+            .word_weights()
+            .map(|all| kept.iter().map(|&i| all[i]).collect());
+        while words.len() < MIN_KEYSET_WORDS {
+            words.push(self.synthesize_word(rng));
+            if let Some(weights) = weights.as_mut() {
+                let rarest = weights.iter().copied().fold(f64::INFINITY, f64::min);
+                weights.push(if rarest.is_finite() { rarest } else { 1.0 });
+            }
+        }
+        Language {
+            name: format!("{}:{}", language.name, self.spec),
+            size: words.len() as u32,
+            words,
+            weights,
+        }
+    }
+
+    /// A random 2-6 key sequence from the set
+    fn synthesize_word(&self, mut rng: &mut dyn RngCore) -> String {
+        let len = rng.gen_range(2..=6);
+        (0..len)
+            .filter_map(|_| self.keys.choose(&mut rng))
+            .collect()
+    }
+}
+
+impl FromStr for KeySet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let spec = s.trim().to_lowercase();
+        let chars = match spec.as_str() {
+            "home" => "asdfghjkl",
+            "top" => "qwertyuiop",
+            "bottom" => "zxcvbnm",
+            other => other,
+        };
+        let mut keys: Vec<char> = Vec::new();
+        for c in chars.chars() {
+            if c.is_whitespace() {
+                return Err(format!("key set '{s}' must not contain whitespace"));
+            }
+            if !keys.contains(&c) {
+                keys.push(c);
+            }
+        }
+        if keys.is_empty() {
+            return Err("key set must be 'home', 'top', 'bottom' or a list of keys".to_string());
+        }
+        Ok(Self { spec, keys })
+    }
+}
+
+impl fmt::Display for KeySet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.spec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_named_and_explicit_sets() {
+        assert_eq!("home".parse::<KeySet>().unwrap().keys().len(), 9);
+        assert_eq!("TOP".parse::<KeySet>().unwrap().to_string(), "top");
+        assert_eq!(
+            "asdfa".parse::<KeySet>().unwrap().keys(),
+            &['a', 's', 'd', 'f']
+        );
+        assert!("".parse::<KeySet>().is_err());
+        assert!("as df".parse::<KeySet>().is_err());
+    }
+
+    #[test]
+    fn test_restrict_filters_words() {
+        let keys: KeySet = "home".parse().unwrap();
+        let restricted = keys.restrict(
+            &Language::new("english10k".to_string()),
+            &mut rand::thread_rng(),
+        );
+
+        assert!(restricted.words.len() >= MIN_KEYSET_WORDS);
+        assert!(restricted.words.iter().all(|w| keys.allows(w)));
+        assert!(restricted.words.iter().any(|w| w == "flash"));
+    }
+
+    #[test]
+    fn test_restrict_synthesizes_when_nothing_matches() {
+        let keys: KeySet = "qz".parse().unwrap();
+        let restricted = keys.restrict(
+            &Language::from_word_lines("hello\nworld"),
+            &mut rand::thread_rng(),
+        );
+
+        assert_eq!(restricted.words.len(), MIN_KEYSET_WORDS);
+        for word in &restricted.words {
+            assert!((2..=6).contains(&word.len()), "{word}");
+            assert!(keys.allows(word), "{word}");
+        }
+        assert!(restricted.weights.is_none());
+    }
+
+    #[test]
+    fn test_restrict_keeps_weights_of_surviving_words() {
+        let keys: KeySet = "home".parse().unwrap();
+        let mut language = Language::from_word_lines("sad\nhello\nflask");
+        language.weights = Some(vec![3.0, 2.0, 1.0]);
+        let restricted = keys.restrict(&language, &mut rand::thread_rng());
+
+        assert_eq!(restricted.words[..2], ["sad", "flask"]);
+        let weights = restricted.word_weights().unwrap();
+        assert_eq!(weights[..2], [3.0, 1.0]);
+        // Padding takes the weight of the rarest real word
+        assert!(weights[2..].iter().all(|w| *w == 1.0));
+    }
+}
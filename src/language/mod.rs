@@ -2,17 +2,28 @@ pub mod core;
 pub mod difficulty;
 pub mod formatter;
 pub mod formatting;
+pub mod keyset;
+pub mod quotes;
 pub mod selection;
 pub mod selector;
 pub mod sentences;
 
 // Re-export the main types for convenience
 pub use core::Language;
-pub use difficulty::CharacterDifficulty;
+pub use difficulty::{
+    practice_score, rank_weakest_characters, BigramDifficulty, CharacterDifficulty,
+};
 pub use formatter::{
-    BasicFormatter, CapitalizationFormatter, CompositeFormatter, SymbolFormatter, TextFormatter,
+    BalancedSymbolFormatter, BasicFormatter, CapitalizationFormatter, CodeFormatter,
+    CompositeFormatter, SymbolFormatter, TextFormatter, TitleCaseFormatter,
+    UppercaseDrillFormatter,
+};
+pub use keyset::KeySet;
+pub use quotes::{Quote, QuoteSelector};
+pub use selector::{
+    IntelligentBigramSelector, IntelligentSelector, RandomSelector, SelectionConfig,
+    SelectionTarget, SubstitutionSelector, WeightedSelector, WordSelector,
 };
-pub use selector::{IntelligentSelector, RandomSelector, SubstitutionSelector, WordSelector};
 
 #[cfg(test)]
 mod tests {
@@ -24,10 +35,10 @@ mod tests {
         let lang = Language::new("english".to_string());
 
         // Test that all functionality works together
-        let words = lang.get_random(5);
+        let words = lang.get_random(5, &mut rand::thread_rng());
         assert_eq!(words.len(), 5);
 
-        let formatted = lang.apply_advanced_formatting(words, true, false);
+        let formatted = lang.apply_advanced_formatting(words, true, false, &mut rand::thread_rng());
         assert!(!formatted.is_empty());
         assert!(formatted.chars().next().unwrap().is_uppercase());
     }
@@ -50,8 +61,9 @@ mod tests {
             },
         );
 
-        let substituted_words = lang.get_substituted(3, &char_stats);
-        let formatted = lang.apply_advanced_formatting(substituted_words, true, true);
+        let substituted_words = lang.get_substituted(3, &char_stats, &mut rand::thread_rng());
+        let formatted =
+            lang.apply_advanced_formatting(substituted_words, true, true, &mut rand::thread_rng());
 
         assert!(!formatted.is_empty());
         // Should have capitalization (first alphabetic character should be uppercase)
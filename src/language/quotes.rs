@@ -0,0 +1,82 @@
+use super::core::read_lang_file;
+use rand::{seq::SliceRandom, RngCore};
+use serde::Deserialize;
+
+/// A passage from the bundled quotes corpus
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct Quote {
+    pub text: String,
+    pub author: String,
+    pub source: String,
+}
+
+impl Quote {
+    pub fn word_count(&self) -> usize {
+        self.text.split_whitespace().count()
+    }
+}
+
+/// Picks whole quotes for `--quote` mode
+#[derive(Debug, Clone, Default)]
+pub struct QuoteSelector {
+    quotes: Vec<Quote>,
+}
+
+impl QuoteSelector {
+    /// Load the bundled `quotes.json`; a missing or malformed file yields no quotes
+    pub fn new() -> Self {
+        read_lang_file("quotes.json")
+            .map(Self::from_json)
+            .unwrap_or_default()
+    }
+
+    pub fn from_json(json: &str) -> Self {
+        let quotes: Vec<Quote> = serde_json::from_str(json).unwrap_or_default();
+        Self {
+            quotes: quotes
+                .into_iter()
+                .filter(|q| !q.text.trim().is_empty())
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.quotes.is_empty()
+    }
+
+    /// A random quote, or None if the corpus is empty
+    pub fn pick(&self, mut rng: &mut dyn RngCore) -> Option<&Quote> {
+        self.quotes.choose(&mut rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_quotes_load() {
+        let selector = QuoteSelector::new();
+        assert!(!selector.is_empty());
+
+        let quote = selector.pick(&mut rand::thread_rng()).unwrap();
+        assert!(!quote.text.is_empty());
+        assert!(!quote.author.is_empty());
+        assert_eq!(quote.word_count(), quote.text.split_whitespace().count());
+    }
+
+    #[test]
+    fn test_empty_corpus_picks_nothing() {
+        assert!(QuoteSelector::from_json("[]")
+            .pick(&mut rand::thread_rng())
+            .is_none());
+        assert!(QuoteSelector::from_json("not json")
+            .pick(&mut rand::thread_rng())
+            .is_none());
+        assert!(
+            QuoteSelector::from_json(r#"[{"text": " ", "author": "a", "source": "b"}]"#)
+                .pick(&mut rand::thread_rng())
+                .is_none()
+        );
+    }
+}
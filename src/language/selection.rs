@@ -1,17 +1,27 @@
 use super::{
     core::Language,
-    difficulty::CharacterDifficulty,
-    selector::{IntelligentSelector, RandomSelector, SubstitutionSelector, WordSelector},
+    difficulty::{BigramDifficulty, CharacterDifficulty},
+    selector::{
+        IntelligentBigramSelector, IntelligentSelector, RandomSelector, SelectionConfig,
+        SubstitutionSelector, WeightedSelector, WordSelector,
+    },
 };
-// Delegates selection to selector module; no direct RNG use here
+// Delegates selection to selector module; callers supply the RNG
+use rand::RngCore;
 use std::collections::HashMap;
 
 impl Language {
     /// Get random words from the language
-    pub fn get_random(&self, num: usize) -> Vec<String> {
+    pub fn get_random(&self, num: usize, rng: &mut dyn RngCore) -> Vec<String> {
         // Delegate to the unified selector implementation
         let empty: std::collections::HashMap<char, CharacterDifficulty> = Default::default();
-        RandomSelector.select_words(self, num, &empty)
+        RandomSelector.select_words(self, num, &empty, rng)
+    }
+
+    /// Get random words, more frequent words more often when the language carries weights
+    pub fn get_random_weighted(&self, num: usize, rng: &mut dyn RngCore) -> Vec<String> {
+        let empty: HashMap<char, CharacterDifficulty> = Default::default();
+        WeightedSelector.select_words(self, num, &empty, rng)
     }
 
     /// Get words with character substitution: replace some characters with ones that need most practice
@@ -19,8 +29,9 @@ impl Language {
         &self,
         num: usize,
         char_stats: &HashMap<char, CharacterDifficulty>,
+        rng: &mut dyn RngCore,
     ) -> Vec<String> {
-        SubstitutionSelector.select_words(self, num, char_stats)
+        SubstitutionSelector.select_words(self, num, char_stats, rng)
     }
 
     /// Get words intelligently selected based on character statistics
@@ -29,8 +40,26 @@ impl Language {
         &self,
         num: usize,
         char_stats: &HashMap<char, CharacterDifficulty>,
+        config: SelectionConfig,
+        rng: &mut dyn RngCore,
+    ) -> Vec<String> {
+        IntelligentSelector { config }.select_words(self, num, char_stats, rng)
+    }
+
+    /// Like `get_intelligent`, also adding the difficulty of each adjacent character pair
+    pub fn get_intelligent_bigram(
+        &self,
+        num: usize,
+        char_stats: &HashMap<char, CharacterDifficulty>,
+        bigram_stats: &HashMap<(char, char), BigramDifficulty>,
+        config: SelectionConfig,
+        rng: &mut dyn RngCore,
     ) -> Vec<String> {
-        IntelligentSelector.select_words(self, num, char_stats)
+        IntelligentBigramSelector {
+            bigram_stats,
+            config,
+        }
+        .select_words(self, num, char_stats, rng)
     }
 
     // Deprecated helpers removed in favor of selector.rs single source of truth
@@ -39,6 +68,7 @@ impl Language {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::SeedableRng;
 
     fn create_test_language() -> Language {
         Language {
@@ -50,6 +80,7 @@ mod tests {
                 "test".to_string(), // Contains 't' (medium)
                 "zap".to_string(),  // Contains 'z' (very hard)
             ],
+            weights: None,
         }
     }
 
@@ -110,7 +141,7 @@ mod tests {
     fn test_get_random_words() {
         let lang = Language::new("english".to_string());
 
-        let words = lang.get_random(5);
+        let words = lang.get_random(5, &mut rand::thread_rng());
         assert_eq!(words.len(), 5);
 
         for word in &words {
@@ -122,16 +153,41 @@ mod tests {
     fn test_get_random_single_word() {
         let lang = Language::new("english".to_string());
 
-        let words = lang.get_random(1);
+        let words = lang.get_random(1, &mut rand::thread_rng());
         assert_eq!(words.len(), 1);
         assert!(lang.words.contains(&words[0]));
     }
 
+    #[test]
+    fn test_get_random_weighted_follows_weights() {
+        let mut lang = create_test_language();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+
+        // Without weights every word turns up
+        let mut seen: Vec<String> = (0..200)
+            .flat_map(|_| lang.get_random_weighted(1, &mut rng))
+            .collect();
+        seen.sort();
+        seen.dedup();
+        assert_eq!(seen.len(), 4);
+
+        lang.weights = Some(vec![1000.0, 1.0, 1.0, 1.0]);
+        let easy = (0..200)
+            .filter(|_| lang.get_random_weighted(1, &mut rng) == ["easy"])
+            .count();
+        assert!(easy > 180, "easy should dominate (got {easy} of 200)");
+
+        // Words are still drawn without repetition
+        let mut words = lang.get_random_weighted(4, &mut rng);
+        words.sort();
+        assert_eq!(words, vec!["easy", "hard", "test", "zap"]);
+    }
+
     #[test]
     fn test_get_random_zero_words() {
         let lang = Language::new("english".to_string());
 
-        let words = lang.get_random(0);
+        let words = lang.get_random(0, &mut rand::thread_rng());
         assert_eq!(words.len(), 0);
     }
 
@@ -146,7 +202,12 @@ mod tests {
         let trials = 100;
 
         for _ in 0..trials {
-            let words = lang.get_intelligent(2, &char_stats);
+            let words = lang.get_intelligent(
+                2,
+                &char_stats,
+                SelectionConfig::default(),
+                &mut rand::thread_rng(),
+            );
             if words.contains(&"hard".to_string()) {
                 hard_count += 1;
             }
@@ -186,7 +247,7 @@ mod tests {
         );
 
         for count in [1, 5, 10] {
-            let words = lang.get_substituted(count, &char_stats);
+            let words = lang.get_substituted(count, &char_stats, &mut rand::thread_rng());
             assert_eq!(words.len(), count, "Should return exactly {count} words");
         }
     }
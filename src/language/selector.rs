@@ -1,16 +1,21 @@
-use super::{core::Language, difficulty::CharacterDifficulty};
+use super::{
+    core::Language,
+    difficulty::{BigramDifficulty, CharacterDifficulty},
+};
+use clap::ValueEnum;
 use rand::seq::SliceRandom;
-use rand::Rng;
+use rand::{Rng, RngCore};
 use std::collections::HashMap;
 
 /// Trait for different word selection strategies
 pub trait WordSelector {
-    /// Select words from the language based on the strategy
+    /// Select words from the language based on the strategy, drawing randomness from `rng`
     fn select_words(
         &self,
         language: &Language,
         count: usize,
         char_stats: &HashMap<char, CharacterDifficulty>,
+        rng: &mut dyn RngCore,
     ) -> Vec<String>;
 }
 
@@ -23,8 +28,8 @@ impl WordSelector for RandomSelector {
         language: &Language,
         count: usize,
         _char_stats: &HashMap<char, CharacterDifficulty>,
+        mut rng: &mut dyn RngCore,
     ) -> Vec<String> {
-        let mut rng = &mut rand::thread_rng();
         language
             .words
             .choose_multiple(&mut rng, count)
@@ -33,8 +38,110 @@ impl WordSelector for RandomSelector {
     }
 }
 
+/// Random selection in proportion to each word's frequency weight; uniform for languages
+/// without weights
+pub struct WeightedSelector;
+
+impl WordSelector for WeightedSelector {
+    fn select_words(
+        &self,
+        language: &Language,
+        count: usize,
+        char_stats: &HashMap<char, CharacterDifficulty>,
+        mut rng: &mut dyn RngCore,
+    ) -> Vec<String> {
+        let Some(weights) = language.word_weights() else {
+            return RandomSelector.select_words(language, count, char_stats, rng);
+        };
+        let weighted: Vec<(&String, f64)> =
+            language.words.iter().zip(weights.iter().copied()).collect();
+        match weighted.choose_multiple_weighted(&mut rng, count, |(_, weight)| *weight) {
+            Ok(chosen) => {
+                let mut words: Vec<String> = chosen.map(|(word, _)| (*word).clone()).collect();
+                // The weighted draw doesn't come back in random order
+                words.shuffle(&mut rng);
+                words
+            }
+            Err(_) => RandomSelector.select_words(language, count, char_stats, rng),
+        }
+    }
+}
+
+/// Which character statistic difficulty-targeted selection chases, chosen with `--target`
+#[derive(Debug, Copy, Clone, Default, PartialEq, ValueEnum, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum SelectionTarget {
+    /// Slow keys: only the average keypress time counts
+    Speed,
+    /// Missed keys: only the miss rate counts
+    Accuracy,
+    /// Both, with misses weighted more heavily
+    #[default]
+    Combined,
+}
+
+/// Tuning knobs for difficulty-targeted selection
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SelectionConfig {
+    /// Fraction of the hardest-scoring words to pick from; lower focuses harder on weak words
+    pub pool_fraction: f64,
+    /// Weight of a character's miss rate in its difficulty score
+    pub miss_weight: f64,
+    /// Weight of a character's slowness (each 100ms over 200ms) in its difficulty score
+    pub time_weight: f64,
+    /// Weight of the uppercase-specific miss rate for capitalized characters
+    pub uppercase_miss_weight: f64,
+}
+
+impl Default for SelectionConfig {
+    fn default() -> Self {
+        Self {
+            pool_fraction: 0.3,
+            miss_weight: 2.0,
+            time_weight: 1.0,
+            uppercase_miss_weight: 1.5,
+        }
+    }
+}
+
+impl SelectionConfig {
+    /// Defaults with the pool fraction set from `--target-aggression`, clamped to 0.0-1.0
+    pub fn with_aggression(aggression: f64) -> Self {
+        Self {
+            pool_fraction: aggression.clamp(0.0, 1.0),
+            ..Self::default()
+        }
+    }
+
+    /// These settings with the miss and timing weights for `target`: `speed` drops the miss
+    /// rates from the score and `accuracy` drops the timings
+    pub fn for_target(self, target: SelectionTarget) -> Self {
+        let defaults = Self::default();
+        let (miss_weight, uppercase_miss_weight, time_weight) = match target {
+            SelectionTarget::Speed => (0.0, 0.0, defaults.time_weight),
+            SelectionTarget::Accuracy => {
+                (defaults.miss_weight, defaults.uppercase_miss_weight, 0.0)
+            }
+            SelectionTarget::Combined => (
+                defaults.miss_weight,
+                defaults.uppercase_miss_weight,
+                defaults.time_weight,
+            ),
+        };
+        Self {
+            miss_weight,
+            uppercase_miss_weight,
+            time_weight,
+            ..self
+        }
+    }
+}
+
 /// Intelligent word selection based on character difficulty
-pub struct IntelligentSelector;
+#[derive(Debug, Default)]
+pub struct IntelligentSelector {
+    pub config: SelectionConfig,
+}
 
 impl WordSelector for IntelligentSelector {
     fn select_words(
@@ -42,40 +149,100 @@ impl WordSelector for IntelligentSelector {
         language: &Language,
         count: usize,
         char_stats: &HashMap<char, CharacterDifficulty>,
+        rng: &mut dyn RngCore,
     ) -> Vec<String> {
         if char_stats.is_empty() {
             // Fall back to random selection if no statistics available
-            return RandomSelector.select_words(language, count, char_stats);
+            return WeightedSelector.select_words(language, count, char_stats, rng);
         }
 
         // Score each word based on the difficulty of characters it contains
-        let mut word_scores: Vec<(String, f64)> = language
+        let word_scores: Vec<(String, f64)> = language
             .words
             .iter()
             .map(|word| {
-                let score = calculate_word_difficulty_score(word, char_stats);
+                let score = calculate_word_difficulty_score(word, char_stats, &self.config);
                 (word.clone(), score)
             })
             .collect();
 
-        // Sort by score (highest difficulty first for more practice)
-        word_scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        select_from_hardest(word_scores, count, self.config.pool_fraction, rng)
+    }
+}
 
-        // Select from top 30% of difficult words to avoid repetition while still targeting weak areas
-        let selection_pool_size = (word_scores.len() as f64 * 0.3)
-            .max(count as f64)
-            .min(word_scores.len() as f64) as usize;
-        let selection_pool = &word_scores[0..selection_pool_size];
+/// Intelligent selection that also weighs the character transitions inside each word
+pub struct IntelligentBigramSelector<'a> {
+    pub bigram_stats: &'a HashMap<(char, char), BigramDifficulty>,
+    pub config: SelectionConfig,
+}
 
-        // Randomly select from the high-difficulty pool
-        let mut rng = &mut rand::thread_rng();
-        selection_pool
-            .choose_multiple(&mut rng, count)
-            .map(|(word, _score)| word.clone())
-            .collect()
+impl WordSelector for IntelligentBigramSelector<'_> {
+    fn select_words(
+        &self,
+        language: &Language,
+        count: usize,
+        char_stats: &HashMap<char, CharacterDifficulty>,
+        rng: &mut dyn RngCore,
+    ) -> Vec<String> {
+        if char_stats.is_empty() && self.bigram_stats.is_empty() {
+            // Fall back to random selection if no statistics available
+            return WeightedSelector.select_words(language, count, char_stats, rng);
+        }
+
+        let word_scores: Vec<(String, f64)> = language
+            .words
+            .iter()
+            .map(|word| {
+                let score = calculate_word_difficulty_score(word, char_stats, &self.config)
+                    + calculate_bigram_difficulty_score(word, self.bigram_stats);
+                (word.clone(), score)
+            })
+            .collect();
+
+        select_from_hardest(word_scores, count, self.config.pool_fraction, rng)
     }
 }
 
+/// Pick `count` words at random from the hardest `pool_fraction` so weak areas are targeted
+/// without repetition
+fn select_from_hardest(
+    mut word_scores: Vec<(String, f64)>,
+    count: usize,
+    pool_fraction: f64,
+    mut rng: &mut dyn RngCore,
+) -> Vec<String> {
+    // Sort by score (highest difficulty first for more practice)
+    word_scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    // Select from the most difficult words to avoid repetition while still targeting weak areas
+    let selection_pool_size = (word_scores.len() as f64 * pool_fraction)
+        .max(count as f64)
+        .min(word_scores.len() as f64) as usize;
+    let selection_pool = &word_scores[0..selection_pool_size];
+
+    // Randomly select from the high-difficulty pool
+    selection_pool
+        .choose_multiple(&mut rng, count)
+        .map(|(word, _score)| word.clone())
+        .collect()
+}
+
+/// Sum of transition penalties for each adjacent character pair in a word (case-insensitive)
+fn calculate_bigram_difficulty_score(
+    word: &str,
+    bigram_stats: &HashMap<(char, char), BigramDifficulty>,
+) -> f64 {
+    let chars: Vec<char> = word
+        .chars()
+        .map(|ch| ch.to_lowercase().next().unwrap_or(ch))
+        .collect();
+    chars
+        .windows(2)
+        .filter_map(|pair| bigram_stats.get(&(pair[0], pair[1])))
+        .map(BigramDifficulty::transition_penalty)
+        .sum()
+}
+
 /// Character substitution selector
 pub struct SubstitutionSelector;
 
@@ -85,14 +252,15 @@ impl WordSelector for SubstitutionSelector {
         language: &Language,
         count: usize,
         char_stats: &HashMap<char, CharacterDifficulty>,
+        rng: &mut dyn RngCore,
     ) -> Vec<String> {
         if char_stats.is_empty() {
             // Fall back to random selection if no statistics available
-            return RandomSelector.select_words(language, count, char_stats);
+            return WeightedSelector.select_words(language, count, char_stats, rng);
         }
 
         // Get regular words first
-        let base_words = RandomSelector.select_words(language, count, char_stats);
+        let base_words = WeightedSelector.select_words(language, count, char_stats, rng);
 
         // Find the most difficult characters to practice
         let weak_chars = get_weakest_characters(char_stats, 10);
@@ -100,15 +268,16 @@ impl WordSelector for SubstitutionSelector {
         // For each word, substitute some characters with weak ones
         base_words
             .into_iter()
-            .map(|word| substitute_characters_in_word(&word, &weak_chars))
+            .map(|word| substitute_characters_in_word(&word, &weak_chars, rng))
             .collect()
     }
 }
 
 /// Calculate difficulty score for a word based on character statistics
-fn calculate_word_difficulty_score(
+pub(super) fn calculate_word_difficulty_score(
     word: &str,
     char_stats: &HashMap<char, CharacterDifficulty>,
+    config: &SelectionConfig,
 ) -> f64 {
     let chars: Vec<char> = word.chars().collect();
     if chars.is_empty() {
@@ -124,9 +293,9 @@ fn calculate_word_difficulty_score(
 
         if let Some(difficulty) = char_stats.get(&base_char) {
             // Base difficulty calculation
-            let miss_penalty = difficulty.miss_rate * 2.0; // Miss rate has higher weight
+            let miss_penalty = difficulty.miss_rate * config.miss_weight; // Miss rate has higher weight
             let timing_penalty = if difficulty.avg_time_ms > 200.0 {
-                (difficulty.avg_time_ms - 200.0) / 100.0 // Normalize timing penalty
+                (difficulty.avg_time_ms - 200.0) / 100.0 * config.time_weight // Normalize timing penalty
             } else {
                 0.0
             };
@@ -140,9 +309,10 @@ fn calculate_word_difficulty_score(
 
                 // Additional penalty based on uppercase-specific performance
                 if difficulty.uppercase_attempts > 0 {
-                    let uppercase_miss_penalty = difficulty.uppercase_miss_rate * 1.5;
+                    let uppercase_miss_penalty =
+                        difficulty.uppercase_miss_rate * config.uppercase_miss_weight;
                     let uppercase_timing_penalty = if difficulty.uppercase_avg_time > 200.0 {
-                        (difficulty.uppercase_avg_time - 200.0) / 100.0
+                        (difficulty.uppercase_avg_time - 200.0) / 100.0 * config.time_weight
                     } else {
                         0.0
                     };
@@ -180,39 +350,22 @@ fn get_weakest_characters(
     char_stats: &HashMap<char, CharacterDifficulty>,
     count: usize,
 ) -> Vec<char> {
-    let mut char_difficulties: Vec<(char, f64)> = char_stats
-        .iter()
-        .map(|(ch, difficulty)| {
-            // Calculate combined difficulty score (higher = more practice needed)
-            let miss_penalty = difficulty.miss_rate * 2.0;
-            let timing_penalty = if difficulty.avg_time_ms > 200.0 {
-                (difficulty.avg_time_ms - 200.0) / 100.0
-            } else {
-                0.0
-            };
-            let combined_difficulty = miss_penalty + timing_penalty;
-            (*ch, combined_difficulty)
-        })
-        .collect();
-
-    // Sort by difficulty (highest first)
-    char_difficulties.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-
-    // Return the weakest characters, limited by count
-    char_difficulties
+    super::difficulty::rank_weakest_characters(char_stats, count)
         .into_iter()
-        .take(count)
         .map(|(ch, _)| ch)
         .collect()
 }
 
 /// Substitute some characters in a word with weaker characters for practice
-fn substitute_characters_in_word(word: &str, weak_chars: &[char]) -> String {
+fn substitute_characters_in_word(
+    word: &str,
+    weak_chars: &[char],
+    mut rng: &mut dyn RngCore,
+) -> String {
     if weak_chars.is_empty() || word.is_empty() {
         return word.to_string();
     }
 
-    let rng = &mut rand::thread_rng();
     let chars: Vec<char> = word.chars().collect();
     let mut result: Vec<char> = Vec::with_capacity(chars.len());
 
@@ -220,7 +373,7 @@ fn substitute_characters_in_word(word: &str, weak_chars: &[char]) -> String {
         // Only substitute alphabetic characters, preserve punctuation/spaces
         if ch.is_alphabetic() && rng.gen_bool(0.3) {
             // 30% chance to substitute each character
-            if let Some(&weak_char) = weak_chars.choose(rng) {
+            if let Some(&weak_char) = weak_chars.choose(&mut rng) {
                 // Preserve case: if original was uppercase, make weak char uppercase too
                 if ch.is_uppercase() {
                     result.push(weak_char.to_uppercase().next().unwrap_or(weak_char));
@@ -252,6 +405,7 @@ mod tests {
                 "test".to_string(),
                 "zap".to_string(),
             ],
+            weights: None,
         }
     }
 
@@ -278,7 +432,7 @@ mod tests {
         let language = create_test_language();
         let char_stats = HashMap::new();
 
-        let words = selector.select_words(&language, 2, &char_stats);
+        let words = selector.select_words(&language, 2, &char_stats, &mut rand::thread_rng());
         assert_eq!(words.len(), 2);
 
         for word in &words {
@@ -288,11 +442,11 @@ mod tests {
 
     #[test]
     fn test_intelligent_selector() {
-        let selector = IntelligentSelector;
+        let selector = IntelligentSelector::default();
         let language = create_test_language();
         let char_stats = create_test_char_stats();
 
-        let words = selector.select_words(&language, 2, &char_stats);
+        let words = selector.select_words(&language, 2, &char_stats, &mut rand::thread_rng());
         assert_eq!(words.len(), 2);
 
         for word in &words {
@@ -306,7 +460,7 @@ mod tests {
         let language = create_test_language();
         let char_stats = create_test_char_stats();
 
-        let words = selector.select_words(&language, 2, &char_stats);
+        let words = selector.select_words(&language, 2, &char_stats, &mut rand::thread_rng());
         assert_eq!(words.len(), 2);
 
         // Words should be same length as originals but potentially modified
@@ -318,7 +472,7 @@ mod tests {
     #[test]
     fn test_selector_fallback_to_random() {
         let selectors: Vec<Box<dyn WordSelector>> = vec![
-            Box::new(IntelligentSelector),
+            Box::new(IntelligentSelector::default()),
             Box::new(SubstitutionSelector),
         ];
 
@@ -326,7 +480,7 @@ mod tests {
         let empty_stats = HashMap::new();
 
         for selector in selectors {
-            let words = selector.select_words(&language, 2, &empty_stats);
+            let words = selector.select_words(&language, 2, &empty_stats, &mut rand::thread_rng());
             assert_eq!(words.len(), 2);
 
             for word in &words {
@@ -339,30 +493,129 @@ mod tests {
     fn test_calculate_word_difficulty_score() {
         let char_stats = create_test_char_stats();
 
-        let score1 = calculate_word_difficulty_score("zap", &char_stats);
-        let score2 = calculate_word_difficulty_score("easy", &char_stats);
+        let score1 =
+            calculate_word_difficulty_score("zap", &char_stats, &SelectionConfig::default());
+        let score2 =
+            calculate_word_difficulty_score("easy", &char_stats, &SelectionConfig::default());
 
         // Word with difficult character should score higher
         assert!(score1 > score2);
     }
 
+    #[test]
+    fn test_target_picks_the_statistic_that_counts() {
+        let stat = |miss_rate, avg_time_ms| CharacterDifficulty {
+            miss_rate,
+            avg_time_ms,
+            total_attempts: 10,
+            uppercase_miss_rate: 0.0,
+            uppercase_avg_time: 0.0,
+            uppercase_attempts: 0,
+            uppercase_penalty: 0.0,
+        };
+        // 's' is slow but never missed, 'm' is quick but often missed
+        let char_stats = HashMap::from([('s', stat(0.0, 600.0)), ('m', stat(30.0, 150.0))]);
+        let score = |word, target| {
+            let config = SelectionConfig::default().for_target(target);
+            calculate_word_difficulty_score(word, &char_stats, &config)
+        };
+
+        assert!(score("sss", SelectionTarget::Speed) > score("mmm", SelectionTarget::Speed));
+        assert_eq!(score("mmm", SelectionTarget::Speed), 0.0);
+        assert!(score("mmm", SelectionTarget::Accuracy) > score("sss", SelectionTarget::Accuracy));
+        assert_eq!(score("sss", SelectionTarget::Accuracy), 0.0);
+        assert_eq!(
+            SelectionConfig::default().for_target(SelectionTarget::Combined),
+            SelectionConfig::default()
+        );
+        assert_eq!(
+            SelectionConfig::with_aggression(0.1)
+                .for_target(SelectionTarget::Speed)
+                .pool_fraction,
+            0.1
+        );
+    }
+
     #[test]
     fn test_calculate_word_difficulty_score_edge_cases() {
         let char_stats = create_test_char_stats();
 
         // Empty word
-        let empty_score = calculate_word_difficulty_score("", &char_stats);
+        let empty_score =
+            calculate_word_difficulty_score("", &char_stats, &SelectionConfig::default());
         assert_eq!(empty_score, 0.0);
 
         // Word with unknown characters
-        let unknown_score = calculate_word_difficulty_score("xyz", &HashMap::new());
+        let unknown_score =
+            calculate_word_difficulty_score("xyz", &HashMap::new(), &SelectionConfig::default());
         assert!(unknown_score > 0.0);
 
         // Word with mixed known and unknown characters
-        let mixed_score = calculate_word_difficulty_score("zxy", &char_stats);
+        let mixed_score =
+            calculate_word_difficulty_score("zxy", &char_stats, &SelectionConfig::default());
         assert!(mixed_score > 0.0);
     }
 
+    fn create_test_bigram_stats() -> HashMap<(char, char), BigramDifficulty> {
+        let mut bigram_stats = HashMap::new();
+        bigram_stats.insert(
+            ('e', 's'),
+            BigramDifficulty {
+                miss_rate: 30.0,
+                avg_time_ms: 450.0,
+                total_attempts: 12,
+            },
+        );
+        bigram_stats
+    }
+
+    #[test]
+    fn test_calculate_bigram_difficulty_score() {
+        let bigram_stats = create_test_bigram_stats();
+
+        // "test" contains "es" once, "easy" contains no "es" transition
+        assert!(calculate_bigram_difficulty_score("test", &bigram_stats) > 0.0);
+        assert_eq!(
+            calculate_bigram_difficulty_score("easy", &bigram_stats),
+            0.0
+        );
+        assert_eq!(
+            calculate_bigram_difficulty_score("TESTES", &bigram_stats),
+            2.0 * calculate_bigram_difficulty_score("test", &bigram_stats)
+        );
+        assert_eq!(calculate_bigram_difficulty_score("e", &bigram_stats), 0.0);
+    }
+
+    #[test]
+    fn test_intelligent_bigram_selector_targets_hard_transitions() {
+        let language = create_test_language();
+        let bigram_stats = create_test_bigram_stats();
+        let selector = IntelligentBigramSelector {
+            bigram_stats: &bigram_stats,
+            config: SelectionConfig::default(),
+        };
+
+        // Only transitions are known, so the pool of one is the "es" word
+        let words = selector.select_words(&language, 1, &HashMap::new(), &mut rand::thread_rng());
+        assert_eq!(words, vec!["test".to_string()]);
+    }
+
+    #[test]
+    fn test_intelligent_bigram_selector_fallback_to_random() {
+        let language = create_test_language();
+        let empty_bigrams = HashMap::new();
+        let selector = IntelligentBigramSelector {
+            bigram_stats: &empty_bigrams,
+            config: SelectionConfig::default(),
+        };
+
+        let words = selector.select_words(&language, 2, &HashMap::new(), &mut rand::thread_rng());
+        assert_eq!(words.len(), 2);
+        for word in &words {
+            assert!(language.words.contains(word));
+        }
+    }
+
     #[test]
     fn test_get_weakest_characters() {
         let char_stats = create_test_char_stats();
@@ -381,19 +634,21 @@ mod tests {
         let weak_chars = vec!['x', 'y', 'z'];
 
         // Test with empty word
-        let empty_result = substitute_characters_in_word("", &weak_chars);
+        let empty_result = substitute_characters_in_word("", &weak_chars, &mut rand::thread_rng());
         assert_eq!(empty_result, "");
 
         // Test with empty weak chars
-        let no_substitution = substitute_characters_in_word("hello", &[]);
+        let no_substitution = substitute_characters_in_word("hello", &[], &mut rand::thread_rng());
         assert_eq!(no_substitution, "hello");
 
         // Test with actual substitution - should preserve word length
-        let substituted = substitute_characters_in_word("hello", &weak_chars);
+        let substituted =
+            substitute_characters_in_word("hello", &weak_chars, &mut rand::thread_rng());
         assert_eq!(substituted.len(), 5);
 
         // Test case preservation
-        let uppercase_result = substitute_characters_in_word("HELLO", &weak_chars);
+        let uppercase_result =
+            substitute_characters_in_word("HELLO", &weak_chars, &mut rand::thread_rng());
         assert_eq!(uppercase_result.len(), 5);
         // At least the first character should remain uppercase if substituted
         if !uppercase_result.starts_with('H') {
@@ -408,21 +663,24 @@ mod tests {
 
         let selectors: Vec<Box<dyn WordSelector>> = vec![
             Box::new(RandomSelector),
-            Box::new(IntelligentSelector),
+            Box::new(IntelligentSelector::default()),
             Box::new(SubstitutionSelector),
         ];
 
         for selector in selectors {
             // Test with count 0
-            let zero_words = selector.select_words(&language, 0, &char_stats);
+            let zero_words =
+                selector.select_words(&language, 0, &char_stats, &mut rand::thread_rng());
             assert_eq!(zero_words.len(), 0);
 
             // Test with count 1
-            let one_word = selector.select_words(&language, 1, &char_stats);
+            let one_word =
+                selector.select_words(&language, 1, &char_stats, &mut rand::thread_rng());
             assert_eq!(one_word.len(), 1);
 
             // Test with count larger than available words
-            let many_words = selector.select_words(&language, 100, &char_stats);
+            let many_words =
+                selector.select_words(&language, 100, &char_stats, &mut rand::thread_rng());
             assert!(many_words.len() <= language.words.len());
         }
     }
@@ -443,25 +701,78 @@ mod tests {
             },
         );
 
-        let lowercase_score = calculate_word_difficulty_score("aaa", &char_stats);
-        let uppercase_score = calculate_word_difficulty_score("AAA", &char_stats);
+        let lowercase_score =
+            calculate_word_difficulty_score("aaa", &char_stats, &SelectionConfig::default());
+        let uppercase_score =
+            calculate_word_difficulty_score("AAA", &char_stats, &SelectionConfig::default());
 
         // Uppercase should score higher due to penalty
         assert!(uppercase_score > lowercase_score);
     }
 
+    /// 100 words where only `z0`..`z9` contain the weak character 'z'
+    fn create_graded_language() -> Language {
+        let mut words: Vec<String> = (0..10).map(|i| format!("z{i}")).collect();
+        words.extend((0..90).map(|i| format!("a{i}")));
+        Language {
+            name: "graded".to_string(),
+            size: words.len() as u32,
+            words,
+            weights: None,
+        }
+    }
+
+    fn hard_share(aggression: f64) -> f64 {
+        let language = create_graded_language();
+        let char_stats = create_test_char_stats();
+        let selector = IntelligentSelector {
+            config: SelectionConfig::with_aggression(aggression),
+        };
+        let mut hard = 0;
+        let mut total = 0;
+        for _ in 0..200 {
+            for word in selector.select_words(&language, 5, &char_stats, &mut rand::thread_rng()) {
+                hard += usize::from(word.starts_with('z'));
+                total += 1;
+            }
+        }
+        hard as f64 / total as f64
+    }
+
+    #[test]
+    fn test_low_aggression_selects_hardest_words() {
+        // The pool shrinks to the requested count, all of it from the 'z' words
+        assert_eq!(hard_share(0.0), 1.0);
+        assert_eq!(hard_share(0.05), 1.0);
+    }
+
+    #[test]
+    fn test_full_aggression_approaches_uniform() {
+        // 10% of the words are hard, so uniform picks land there about 10% of the time
+        let share = hard_share(1.0);
+        assert!(share > 0.03 && share < 0.2, "{share}");
+    }
+
+    #[test]
+    fn test_selection_config_clamps_aggression() {
+        assert_eq!(SelectionConfig::with_aggression(-1.0).pool_fraction, 0.0);
+        assert_eq!(SelectionConfig::with_aggression(3.0).pool_fraction, 1.0);
+        assert_eq!(SelectionConfig::default().pool_fraction, 0.3);
+    }
+
     #[test]
     fn test_intelligent_selector_with_limited_words() {
         let small_language = Language {
             name: "small".to_string(),
             size: 2,
             words: vec!["a".to_string(), "z".to_string()],
+            weights: None,
         };
 
         let char_stats = create_test_char_stats();
-        let selector = IntelligentSelector;
+        let selector = IntelligentSelector::default();
 
-        let words = selector.select_words(&small_language, 2, &char_stats);
+        let words = selector.select_words(&small_language, 2, &char_stats, &mut rand::thread_rng());
         assert_eq!(words.len(), 2);
     }
 }
@@ -1,21 +1,75 @@
-use super::core::Language;
+use super::{
+    core::Language, difficulty::CharacterDifficulty, selector::calculate_word_difficulty_score,
+    SelectionConfig,
+};
 use cgisf_lib::cgisf;
-use rand::Rng;
+use rand::{Rng, RngCore};
+use std::collections::HashMap;
+
+/// Drafts generated per sentence by `get_targeted_sentence`; the hardest one is kept
+const TARGETED_SENTENCE_CANDIDATES: usize = 8;
+
+/// One cgisf sentence, ending in a space; `rng` picks its shape
+fn draft_sentence(rng: &mut dyn RngCore) -> String {
+    cgisf(
+        rng.gen_range(1..3),
+        rng.gen_range(1..3),
+        rng.gen_range(1..5),
+        rng.gen_bool(0.5),
+        rng.gen_range(1..3),
+        rng.gen_bool(0.5),
+    )
+}
+
+/// Mean difficulty of the words in `sentence`, scored like intelligent word selection does
+pub fn sentence_difficulty_score(
+    sentence: &str,
+    char_stats: &HashMap<char, CharacterDifficulty>,
+    config: &SelectionConfig,
+) -> f64 {
+    let scores: Vec<f64> = sentence
+        .split_whitespace()
+        .map(|word| calculate_word_difficulty_score(word, char_stats, config))
+        .collect();
+    crate::util::mean(&scores).unwrap_or(0.0)
+}
 
 impl Language {
-    pub fn get_random_sentence(&self, num: usize) -> (Vec<String>, usize) {
-        let rng = &mut rand::thread_rng();
+    /// `rng` picks the sentence shapes; cgisf draws the words with its own RNG
+    pub fn get_random_sentence(&self, num: usize, rng: &mut dyn RngCore) -> (Vec<String>, usize) {
+        self.sentences_from(num, || draft_sentence(rng))
+    }
+
+    /// Like `get_random_sentence`, but each sentence is the hardest of a few drafts by
+    /// `sentence_difficulty_score`, so weak characters come up more often. Without stats
+    /// there is nothing to aim for and the first draft is taken.
+    pub fn get_targeted_sentence(
+        &self,
+        num: usize,
+        char_stats: &HashMap<char, CharacterDifficulty>,
+        config: &SelectionConfig,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<String>, usize) {
+        if char_stats.is_empty() {
+            return self.get_random_sentence(num, rng);
+        }
+        self.sentences_from(num, || {
+            (0..TARGETED_SENTENCE_CANDIDATES)
+                .map(|_| draft_sentence(rng))
+                .max_by(|a, b| {
+                    sentence_difficulty_score(a, char_stats, config)
+                        .total_cmp(&sentence_difficulty_score(b, char_stats, config))
+                })
+                .unwrap_or_default()
+        })
+    }
+
+    /// `num` sentences from `next`, the last one without its trailing space, and their word count
+    fn sentences_from(&self, num: usize, mut next: impl FnMut() -> String) -> (Vec<String>, usize) {
         let mut vec = Vec::new();
         let mut word_count = 0;
         for i in 0..num {
-            let mut s = cgisf(
-                rng.gen_range(1..3),
-                rng.gen_range(1..3),
-                rng.gen_range(1..5),
-                rng.gen_bool(0.5),
-                rng.gen_range(1..3),
-                rng.gen_bool(0.5),
-            );
+            let mut s = next();
             word_count += &s.matches(' ').count();
             // gets the word count of the sentence.
             if i == num - 1 {
@@ -25,6 +79,35 @@ impl Language {
         }
         (vec, word_count)
     }
+
+    /// Whole sentences until there are at least `target_words` words, trimmed back to exactly
+    /// that many and closed with a full stop if the cut fell mid-sentence
+    pub fn get_sentences_for_words(
+        &self,
+        target_words: usize,
+        rng: &mut dyn RngCore,
+    ) -> (String, usize) {
+        let mut words: Vec<String> = Vec::with_capacity(target_words);
+        while words.len() < target_words {
+            let (sentences, _) = self.get_random_sentence(1, rng);
+            let before = words.len();
+            words.extend(sentences.concat().split_whitespace().map(String::from));
+            if words.len() == before {
+                break;
+            }
+        }
+        words.truncate(target_words);
+        if let Some(last) = words.last_mut() {
+            let trimmed = last.trim_end_matches([',', ';', ':']);
+            *last = if trimmed.ends_with(['.', '!', '?']) {
+                trimmed.to_string()
+            } else {
+                format!("{trimmed}.")
+            };
+        }
+        let word_count = words.len();
+        (words.join(" "), word_count)
+    }
 }
 
 #[cfg(test)]
@@ -35,7 +118,7 @@ mod tests {
     fn test_get_random_sentence() {
         let lang = Language::new("english".to_string());
 
-        let (sentences, word_count) = lang.get_random_sentence(2);
+        let (sentences, word_count) = lang.get_random_sentence(2, &mut rand::thread_rng());
 
         assert_eq!(sentences.len(), 2);
         assert!(word_count > 0);
@@ -50,7 +133,7 @@ mod tests {
     fn test_get_random_sentence_single() {
         let lang = Language::new("english".to_string());
 
-        let (sentences, word_count) = lang.get_random_sentence(1);
+        let (sentences, word_count) = lang.get_random_sentence(1, &mut rand::thread_rng());
 
         assert_eq!(sentences.len(), 1);
         assert!(word_count > 0);
@@ -61,17 +144,77 @@ mod tests {
     fn test_get_random_sentence_zero() {
         let lang = Language::new("english".to_string());
 
-        let (sentences, word_count) = lang.get_random_sentence(0);
+        let (sentences, word_count) = lang.get_random_sentence(0, &mut rand::thread_rng());
 
         assert_eq!(sentences.len(), 0);
         assert_eq!(word_count, 0);
     }
 
+    #[test]
+    fn test_get_sentences_for_words_hits_target() {
+        let lang = Language::new("english".to_string());
+
+        for target in [1, 7, 50] {
+            let (text, word_count) = lang.get_sentences_for_words(target, &mut rand::thread_rng());
+            assert_eq!(word_count, target);
+            assert_eq!(text.split_whitespace().count(), target);
+            assert!(text.ends_with(['.', '!', '?']), "{text}");
+        }
+
+        let (text, word_count) = lang.get_sentences_for_words(0, &mut rand::thread_rng());
+        assert_eq!((text.as_str(), word_count), ("", 0));
+    }
+
+    #[test]
+    fn test_targeted_sentences_score_no_lower_than_random() {
+        let lang = Language::new("english".to_string());
+        let config = SelectionConfig::default();
+        let easy = CharacterDifficulty {
+            miss_rate: 1.0,
+            avg_time_ms: 150.0,
+            total_attempts: 100,
+            uppercase_miss_rate: 1.0,
+            uppercase_avg_time: 200.0,
+            uppercase_attempts: 10,
+            uppercase_penalty: 0.0,
+        };
+        let hard = CharacterDifficulty {
+            miss_rate: 30.0,
+            avg_time_ms: 400.0,
+            ..easy.clone()
+        };
+        let char_stats: HashMap<char, CharacterDifficulty> = ('a'..='z')
+            .map(|c| {
+                let difficulty = if "wyvkj".contains(c) { &hard } else { &easy };
+                (c, difficulty.clone())
+            })
+            .collect();
+
+        let mean_score = |sentences: Vec<String>| {
+            let scores: Vec<f64> = sentences
+                .iter()
+                .map(|s| sentence_difficulty_score(s, &char_stats, &config))
+                .collect();
+            crate::util::mean(&scores).unwrap()
+        };
+        let rng = &mut rand::thread_rng();
+        let (targeted, word_count) = lang.get_targeted_sentence(40, &char_stats, &config, rng);
+        assert_eq!(targeted.len(), 40);
+        assert_eq!(word_count, targeted.concat().matches(' ').count() + 1);
+        let (random, _) = lang.get_random_sentence(40, rng);
+
+        assert!(mean_score(targeted) >= mean_score(random));
+
+        // No stats: plain random sentences
+        let (sentences, _) = lang.get_targeted_sentence(2, &HashMap::new(), &config, rng);
+        assert_eq!(sentences.len(), 2);
+    }
+
     #[test]
     fn test_get_random_sentence_word_count_accuracy() {
         let lang = Language::new("english".to_string());
 
-        let (sentences, word_count) = lang.get_random_sentence(1);
+        let (sentences, word_count) = lang.get_random_sentence(1, &mut rand::thread_rng());
 
         if !sentences.is_empty() {
             let actual_word_count = sentences[0].matches(' ').count();
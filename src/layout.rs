@@ -0,0 +1,191 @@
+use clap::ValueEnum;
+
+/// Physical keyboard layouts selectable with `--layout`
+#[derive(Debug, Copy, Clone, Default, PartialEq, ValueEnum, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum KeyboardLayout {
+    #[default]
+    Qwerty,
+    Dvorak,
+    Colemak,
+}
+
+/// The finger that presses a key under standard touch typing
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Finger {
+    LeftPinky,
+    LeftRing,
+    LeftMiddle,
+    LeftIndex,
+    Thumb,
+    RightIndex,
+    RightMiddle,
+    RightRing,
+    RightPinky,
+}
+
+impl Finger {
+    /// Every finger, left pinky to right pinky
+    pub const ALL: [Finger; 9] = [
+        Finger::LeftPinky,
+        Finger::LeftRing,
+        Finger::LeftMiddle,
+        Finger::LeftIndex,
+        Finger::Thumb,
+        Finger::RightIndex,
+        Finger::RightMiddle,
+        Finger::RightRing,
+        Finger::RightPinky,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Finger::LeftPinky => "left pinky",
+            Finger::LeftRing => "left ring",
+            Finger::LeftMiddle => "left middle",
+            Finger::LeftIndex => "left index",
+            Finger::Thumb => "thumb",
+            Finger::RightIndex => "right index",
+            Finger::RightMiddle => "right middle",
+            Finger::RightRing => "right ring",
+            Finger::RightPinky => "right pinky",
+        }
+    }
+
+    /// Finger for a column of the number, top, home or bottom row: four columns per hand
+    /// from the outside in, with the index fingers covering two each
+    fn for_column(column: usize) -> Self {
+        match column {
+            0 => Finger::LeftPinky,
+            1 => Finger::LeftRing,
+            2 => Finger::LeftMiddle,
+            3 | 4 => Finger::LeftIndex,
+            5 | 6 => Finger::RightIndex,
+            7 => Finger::RightMiddle,
+            8 => Finger::RightRing,
+            _ => Finger::RightPinky,
+        }
+    }
+}
+
+/// Where a key sits: `row` 0 is the number row, 2 the home row and 4 the space bar
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct KeyPosition {
+    pub row: usize,
+    pub finger: Finger,
+}
+
+const SHIFTED: &str = "~!@#$%^&*()_+{}|:\"<>?";
+const UNSHIFTED: &str = "`1234567890-=[]\\;',./";
+
+impl KeyboardLayout {
+    /// Unshifted keys of the number, top, home and bottom rows, left to right
+    pub fn rows(&self) -> [&'static str; 4] {
+        match self {
+            KeyboardLayout::Qwerty => [
+                "1234567890-=",
+                "qwertyuiop[]\\",
+                "asdfghjkl;'",
+                "zxcvbnm,./",
+            ],
+            KeyboardLayout::Dvorak => [
+                "1234567890[]",
+                "',.pyfgcrl/=\\",
+                "aoeuidhtns-",
+                ";qjkxbmwvz",
+            ],
+            KeyboardLayout::Colemak => [
+                "1234567890-=",
+                "qwfpgjluy;[]\\",
+                "arstdhneio'",
+                "zxcvbkm,./",
+            ],
+        }
+    }
+
+    /// Row and finger for `c`, looking through Shift; `None` for keys off the main block
+    pub fn position(&self, c: char) -> Option<KeyPosition> {
+        if c == ' ' {
+            return Some(KeyPosition {
+                row: 4,
+                finger: Finger::Thumb,
+            });
+        }
+        let key = match SHIFTED.chars().position(|s| s == c) {
+            Some(i) => UNSHIFTED.chars().nth(i)?,
+            None => c.to_lowercase().next()?,
+        };
+        self.rows().iter().enumerate().find_map(|(row, keys)| {
+            keys.chars()
+                .position(|k| k == key)
+                .map(|column| KeyPosition {
+                    row,
+                    finger: Finger::for_column(column),
+                })
+        })
+    }
+
+    /// `chars` grouped by the finger that types them, in order of first appearance;
+    /// characters off the main block share a `None` group at the end
+    pub fn group_by_finger(&self, chars: &[char]) -> Vec<(Option<Finger>, Vec<char>)> {
+        let mut groups: Vec<(Option<Finger>, Vec<char>)> = Vec::new();
+        let mut unplaced = Vec::new();
+        for &c in chars {
+            match self.position(c).map(|p| p.finger) {
+                Some(finger) => match groups.iter_mut().find(|(f, _)| *f == Some(finger)) {
+                    Some((_, group)) => group.push(c),
+                    None => groups.push((Some(finger), vec![c])),
+                },
+                None => unplaced.push(c),
+            }
+        }
+        if !unplaced.is_empty() {
+            groups.push((None, unplaced));
+        }
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_positions_differ_by_layout() {
+        let qwerty = KeyboardLayout::Qwerty.position('e').unwrap();
+        assert_eq!((qwerty.row, qwerty.finger), (1, Finger::LeftMiddle));
+
+        // Dvorak puts 'e' on the home row under the left middle finger
+        let dvorak = KeyboardLayout::Dvorak.position('e').unwrap();
+        assert_eq!((dvorak.row, dvorak.finger), (2, Finger::LeftMiddle));
+
+        let colemak = KeyboardLayout::Colemak.position('n').unwrap();
+        assert_eq!((colemak.row, colemak.finger), (2, Finger::RightIndex));
+
+        assert_eq!(KeyboardLayout::Dvorak.to_string(), "dvorak");
+    }
+
+    #[test]
+    fn test_position_looks_through_shift() {
+        let layout = KeyboardLayout::Qwerty;
+        assert_eq!(layout.position('A'), layout.position('a'));
+        assert_eq!(layout.position(':'), layout.position(';'));
+        assert_eq!(layout.position('?').unwrap().finger, Finger::RightPinky);
+        assert_eq!(layout.position(' ').unwrap().finger, Finger::Thumb);
+        assert!(layout.position('é').is_none());
+    }
+
+    #[test]
+    fn test_group_by_finger() {
+        let groups = KeyboardLayout::Qwerty.group_by_finger(&['s', 'j', 'é', 'x', 'h']);
+        assert_eq!(
+            groups,
+            vec![
+                (Some(Finger::LeftRing), vec!['s', 'x']),
+                (Some(Finger::RightIndex), vec!['j', 'h']),
+                (None, vec!['é']),
+            ]
+        );
+        assert!(KeyboardLayout::Qwerty.group_by_finger(&[]).is_empty());
+    }
+}
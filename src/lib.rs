@@ -2,10 +2,20 @@
 // Keep this lean to avoid coupling to bin-only types in main.rs.
 pub mod app_dirs;
 pub mod celebration;
+pub mod clock;
+pub mod exit_status;
+pub mod history;
 pub mod language;
+pub mod layout;
+pub mod pomodoro;
+pub mod replay;
+pub mod report;
+pub mod rounds;
 pub mod runtime;
 pub mod session;
+pub mod share;
 pub mod stats;
+pub mod theme;
 pub mod thok;
 pub mod time_series;
 pub mod typing_policy;
@@ -1,10 +1,21 @@
 pub mod app_dirs;
 pub mod celebration;
+pub mod clock;
 pub mod config;
+pub mod exit_status;
+pub mod history;
 pub mod language;
+pub mod layout;
+pub mod pomodoro;
+pub mod progression;
+pub mod replay;
+pub mod report;
+pub mod rounds;
 pub mod runtime;
 pub mod session;
+pub mod share;
 pub mod stats;
+pub mod theme;
 pub mod thok;
 pub mod time_series;
 pub mod typing_policy;
@@ -12,16 +23,26 @@ pub mod ui;
 pub mod util;
 pub mod word_generator;
 
-use crate::runtime::{CrosstermEventSource, FixedTicker, Runner, ThokEvent as RtEvent};
+use crate::exit_status::{ExitStatus, ResultGate, SessionResult};
+use crate::runtime::{
+    CrosstermEventSource, FixedTicker, Runner, ThokEvent as RtEvent, ThokEventSource,
+};
+use crate::stats::SessionContext;
 #[cfg(test)]
 use crate::ui::character_stats::render_character_stats;
 use crate::ui::screen::current_screen;
 use crate::{
-    language::Language,
-    thok::Thok,
-    word_generator::{WordGenConfig, WordGenerator},
+    celebration::CelebrationStyle,
+    language::{KeySet, Language, SelectionConfig, SelectionTarget},
+    layout::KeyboardLayout,
+    session::DEFAULT_IDLE_TIMEOUT_SECS,
+    theme::{StatsThresholds, Theme, ThemeName},
+    thok::{Bell, PromptExtender, Thok},
+    typing_policy::TypingPolicy,
+    word_generator::{NumberFormat, NumberGroups, NumberSeparator, WordGenConfig, WordGenerator},
 };
-use clap::{error::ErrorKind, CommandFactory, Parser, ValueEnum};
+use chrono::NaiveDate;
+use clap::{error::ErrorKind, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 use crossterm::{
     event::{KeyCode, KeyModifiers},
     execute,
@@ -30,12 +51,14 @@ use crossterm::{
 };
 use ratatui::{
     backend::{Backend, CrosstermBackend},
-    Frame, Terminal,
+    Frame, Terminal, TerminalOptions, Viewport,
 };
 use std::{
     error::Error,
-    io::{self, stdin},
-    time::Duration,
+    io::{self, stdin, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, SystemTime},
 };
 
 // Use TICK_RATE_MS from thok module to avoid duplication
@@ -57,44 +80,361 @@ pub struct Cli {
     #[clap(short = 'f', long = "full-sentences")]
     number_of_sentences: Option<usize>,
 
+    /// generate sentences until the prompt reaches --number-of-words words
+    #[clap(long)]
+    sentence_words: bool,
+
+    /// with --full-sentences, pick the sentences that exercise your weakest characters most
+    #[clap(long)]
+    targeted_sentences: bool,
+
     /// number of seconds to run test
     #[clap(short = 's', long)]
     number_of_secs: Option<usize>,
 
+    /// in timed tests, keep appending words when the prompt runs out so only the timer ends the test
+    #[clap(long)]
+    fill_time: bool,
+
+    /// endless test: words keep coming until you press Esc, which shows results for what you typed
+    #[clap(long)]
+    zen: bool,
+
+    /// practice random groups of 2-6 digits instead of words (overrides --random-words/--substitute)
+    #[clap(long)]
+    numbers: bool,
+
+    /// with --numbers, the smallest number to practice (needs --number-max)
+    #[clap(long, value_name = "N", requires = "number_max")]
+    number_min: Option<u64>,
+
+    /// with --numbers, the largest number to practice; numbers are zero-padded to its digit count
+    #[clap(long, value_name = "N")]
+    number_max: Option<u64>,
+
+    /// with --numbers, split each number into digit groups of these sizes, e.g. 3,3,4
+    #[clap(long, value_name = "SIZES")]
+    number_groups: Option<NumberGroups>,
+
+    /// what goes between --number-groups
+    #[clap(long, value_enum, default_value_t = NumberSeparator::Dash)]
+    number_separator: NumberSeparator,
+
+    /// interleave words from this second language roughly 50/50 with the main one
+    #[clap(long, value_enum, value_name = "LANG")]
+    mix: Option<SupportedLanguage>,
+
+    /// only practice keys from a set: home, top, bottom, or an explicit list like asdf
+    #[clap(long, value_name = "SET")]
+    keys: Option<KeySet>,
+
+    /// practice on a random quote from the built-in corpus instead of generated words
+    #[clap(long)]
+    quote: bool,
+
+    /// show a countdown of this many seconds before input is accepted
+    #[clap(long, value_name = "SECS")]
+    countdown: Option<u64>,
+
+    /// seconds without input before a running test is considered idle (0 disables idle detection)
+    #[clap(long, value_name = "SECS", default_value_t = DEFAULT_IDLE_TIMEOUT_SECS)]
+    idle_timeout: f64,
+
+    /// milliseconds between screen updates; lower is smoother (10-1000)
+    #[clap(long, value_name = "MS", default_value_t = TICK_RATE_MS, value_parser = clap::value_parser!(u64).range(10..=1000))]
+    tick_ms: u64,
+
+    /// newline-separated file of words to practice instead of the built-in language
+    #[clap(long, value_name = "PATH")]
+    wordlist: Option<PathBuf>,
+
+    /// newline-separated file of prompts; each test (and `New`) types a random line
+    #[clap(long, value_name = "PATH", conflicts_with_all = ["prompt", "code_file"])]
+    prompt_file: Option<PathBuf>,
+
     /// custom prompt to use
     #[clap(short = 'p', long)]
     prompt: Option<String>,
 
+    /// use text piped to stdin as the prompt and read keys from the terminal instead (Unix only)
+    #[clap(long, conflicts_with = "prompt")]
+    stdin: bool,
+
+    /// type a source file: blank lines are dropped, indentation kept and newlines typed with enter
+    #[clap(long, value_name = "PATH", conflicts_with_all = ["prompt", "stdin"])]
+    code_file: Option<PathBuf>,
+
+    /// with --code-file, also drop lines that are only a comment
+    #[clap(long, requires = "code_file")]
+    strip_comments: bool,
+
     /// language to pull words from
     #[clap(short = 'l', long, value_enum, default_value_t = SupportedLanguage::English)]
     supported_language: SupportedLanguage,
 
+    /// move english up to english1k, then english10k, once your last 10 tests average enough wpm and accuracy
+    #[clap(long)]
+    progression: bool,
+
     /// use random word selection instead of intelligent character-based selection (default: intelligent selection that targets your weakest characters)
     #[clap(long)]
     random_words: bool,
 
+    /// share of the hardest words intelligent selection picks from, 0.0-1.0 (lower = more focused on weak words)
+    #[clap(long, value_name = "0.0-1.0", default_value_t = 0.3, value_parser = parse_aggression)]
+    target_aggression: f64,
+
+    /// let each session count half as much every DAYS days old when finding weak characters, so practice follows current skill
+    #[clap(long, value_name = "DAYS", value_parser = parse_halflife)]
+    recency_halflife: Option<f64>,
+
+    /// what intelligent selection targets: slow keys (speed), missed keys (accuracy) or both
+    #[clap(long, value_enum, default_value_t = SelectionTarget::Combined)]
+    target: SelectionTarget,
+
+    /// seed for word selection and formatting, so the same options give the same prompt
+    #[clap(long, value_name = "N")]
+    seed: Option<u64>,
+
     /// enable capitalization, punctuation, and commas for realistic typing practice
     #[clap(long)]
     capitalize: bool,
 
-    /// enable strict mode: stop on errors and require correction before proceeding
+    /// enable strict mode: stop on errors and require correction before proceeding (same as --policy strict)
     #[clap(long)]
     strict: bool,
 
+    /// how wrong keys are handled: normal moves on, strict stops until corrected, lenient moves on but the test only finishes once every error is fixed
+    #[clap(long, value_enum, default_value_t = TypingPolicy::Normal)]
+    policy: TypingPolicy,
+
+    /// in strict mode, ring the terminal bell on every wrong key
+    #[clap(long)]
+    bell: bool,
+
     /// include symbols and special characters for comprehensive typing practice
     #[clap(long)]
     symbols: bool,
 
+    /// like --symbols, but every inserted bracket or quote is closed again later in the prompt
+    #[clap(long)]
+    balanced_symbols: bool,
+
+    /// capitalize many letters in every word, most often the ones whose capitals you type worst
+    #[clap(long)]
+    uppercase_drill: bool,
+
+    /// capitalize the first letter of every word, to practice the shift key
+    #[clap(long)]
+    title_case: bool,
+
     /// enable character substitution mode: create "almost English" words by replacing characters with ones that need most practice
     #[clap(long)]
     substitute: bool,
+
+    /// glyph to display in place of incorrectly typed characters (e.g. '▢')
+    #[clap(long, value_name = "CHAR")]
+    incorrect_glyph: Option<char>,
+
+    /// on errors, display the expected character instead of the one that was typed
+    #[clap(long)]
+    show_expected: bool,
+
+    /// color theme for all screens
+    #[clap(long, value_enum, default_value_t = ThemeName::Default)]
+    theme: ThemeName,
+
+    /// how to draw the cursor while typing
+    #[clap(long, value_enum, default_value_t = CursorStyle::Underline)]
+    cursor: CursorStyle,
+
+    /// your physical keyboard layout, used to group the weakest keys by finger
+    #[clap(long, value_enum, default_value_t = KeyboardLayout::Qwerty)]
+    layout: KeyboardLayout,
+
+    /// character stats: average keypress times below this many ms are shown as good
+    #[clap(long, value_name = "MS", default_value_t = 150.0)]
+    good_ms: f64,
+
+    /// character stats: average keypress times at or above this many ms are shown as slow
+    #[clap(long, value_name = "MS", default_value_t = 250.0)]
+    slow_ms: f64,
+
+    /// character stats: miss rates (%) at or below this are shown as good
+    #[clap(long, value_name = "PCT", default_value_t = 0.0)]
+    good_miss: f64,
+
+    /// character stats: miss rates (%) at or above this are shown as bad
+    #[clap(long, value_name = "PCT", default_value_t = 10.0)]
+    bad_miss: f64,
+
+    /// plot the results chart as a rolling average over this many seconds (3 if no value is given)
+    #[clap(long, value_name = "SECS", num_args = 0..=1, default_missing_value = "3")]
+    smooth: Option<usize>,
+
+    /// results show only WPM, accuracy and the controls, without the chart and other panels
+    #[clap(long)]
+    minimal: bool,
+
+    /// link opened by `t` on the results screen, with {wpm}, {acc} and {sd} filled in; empty turns sharing off
+    #[clap(long, value_name = "TEMPLATE", default_value = share::DEFAULT_SHARE_TEMPLATE)]
+    share_template: String,
+
+    /// how long the caret stays on, then off, while typing; 0 keeps it solid
+    #[clap(long, value_name = "MS", default_value_t = DEFAULT_BLINK_MS)]
+    blink_ms: u64,
+
+    /// draw a dashed reference line at this WPM on the results chart
+    #[clap(long, value_name = "WPM")]
+    goal_wpm: Option<f64>,
+
+    /// show the current word spelled out letter by letter above the prompt
+    #[clap(long)]
+    spell_out: bool,
+
+    /// when idle, dim the prompt and hide the cursor under a small badge instead of blanking the screen
+    #[clap(long)]
+    hide_cursor_on_idle: bool,
+
+    /// keep drilling one prompt: new tests reuse it instead of generating another
+    #[clap(long)]
+    repeat: bool,
+
+    /// type a few pangrams first to seed the character stats for intelligent selection; the warmup's result isn't kept
+    #[clap(long)]
+    warmup: bool,
+
+    /// keep spaces and punctuation out of the character statistics
+    #[clap(long)]
+    stats_letters_only: bool,
+
+    /// save an unfinished test every few keystrokes and offer to resume it on the next start
+    #[clap(long)]
+    checkpoint: bool,
+
+    /// retry the same prompt until accuracy (%) reaches this threshold; exit with code 2 if the final test is still below it
+    #[clap(long, value_name = "PCT")]
+    min_accuracy: Option<f64>,
+
+    /// start a new prompt this many ms after finishing instead of waiting on the results (800 if no value is given)
+    #[clap(long, value_name = "MS", num_args = 0..=1, default_missing_value = "800")]
+    auto_advance: Option<u64>,
+
+    /// end the test as failed as soon as it has more than this many errors, and exit with code 2
+    #[clap(long, value_name = "N")]
+    max_errors: Option<usize>,
+
+    /// with --max-errors, count only errors still standing, not ones you fixed
+    #[clap(long)]
+    forgive_corrected: bool,
+
+    /// race a ghost cursor moving at your best recorded WPM
+    #[clap(long)]
+    ghost: bool,
+
+    /// effect played over a perfect or personal-best result
+    #[clap(long, value_enum, default_value_t = CelebrationStyle::Confetti)]
+    celebration: CelebrationStyle,
+
+    /// focus timer: run back-to-back tests for the given number of minutes, then show a session-wide summary
+    #[clap(long, value_name = "MINUTES")]
+    pomodoro: Option<u64>,
+
+    /// run this many tests back to back, then show mean/median WPM, accuracy and the best round (ignored with --pomodoro)
+    #[clap(long, value_name = "N")]
+    rounds: Option<usize>,
+
+    /// with --rounds, wait on each round's results until Enter instead of starting the next one right away
+    #[clap(long)]
+    rounds_wait: bool,
+
+    /// print the final result as JSON to stdout; with piped stdin, type the piped text headlessly against --prompt, without saving stats
+    #[clap(long)]
+    json: bool,
+
+    /// draw inline instead of in the alternate screen, so panics, logs and the last frame stay in the scrollback
+    #[clap(long)]
+    no_altscreen: bool,
+
+    /// keep stats and the session log under this name, apart from other people on the machine
+    #[clap(long, value_name = "NAME", value_parser = parse_profile)]
+    profile: Option<String>,
+
+    /// write every row of the character statistics database to a JSON file and exit
+    #[clap(long, value_name = "PATH")]
+    export_stats: Option<PathBuf>,
+
+    /// after the test, write every keystroke of the last finished test (typed, expected, outcome, ms from start) to a JSON file
+    #[clap(long, value_name = "PATH")]
+    export_keylog: Option<PathBuf>,
+
+    /// delete all character statistics after a y/N confirmation, then exit
+    #[clap(long)]
+    reset_stats: bool,
+
+    /// save the effective settings as defaults in the config file and exit
+    #[clap(long)]
+    write_config: bool,
+
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// print average WPM and accuracy per day, then exit
+    Stats {
+        /// number of most recent days with finished tests to show
+        #[clap(long, default_value_t = 7)]
+        days: usize,
+
+        /// only count tests on or after this date (YYYY-MM-DD)
+        #[clap(long, value_name = "DATE")]
+        stats_since: Option<NaiveDate>,
+    },
+}
+
+/// A profile name becomes a directory, so it has to be a single plain path component
+fn parse_profile(s: &str) -> Result<String, String> {
+    let valid = !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '.')
+        && !s.starts_with('.');
+    if valid {
+        Ok(s.to_string())
+    } else {
+        Err(format!(
+            "profile '{s}' must be letters, digits, '-', '_' or '.', not starting with '.'"
+        ))
+    }
+}
+
+fn parse_halflife(s: &str) -> Result<f64, String> {
+    let value: f64 = s.parse().map_err(|e| format!("{e}"))?;
+    if value > 0.0 && value.is_finite() {
+        Ok(value)
+    } else {
+        Err(format!("{value} is not a positive number of days"))
+    }
+}
+
+fn parse_aggression(s: &str) -> Result<f64, String> {
+    let value: f64 = s.parse().map_err(|e| format!("{e}"))?;
+    if (0.0..=1.0).contains(&value) {
+        Ok(value)
+    } else {
+        Err(format!("{value} is not between 0.0 and 1.0"))
+    }
 }
 
-#[derive(Debug, Copy, Clone, ValueEnum, strum_macros::Display)]
+#[derive(Debug, Copy, Clone, PartialEq, ValueEnum, strum_macros::Display)]
 pub enum SupportedLanguage {
     English,
     English1k,
     English10k,
+    Spanish,
+    /// Programming keywords, identifiers and symbols, arranged into code-like statements
+    Code,
 }
 
 impl SupportedLanguage {
@@ -104,6 +444,8 @@ impl SupportedLanguage {
             SupportedLanguage::English => "english",
             SupportedLanguage::English1k => "english1k",
             SupportedLanguage::English10k => "english10k",
+            SupportedLanguage::Spanish => "spanish",
+            SupportedLanguage::Code => "code",
         };
         Language::new(file_name.to_string())
     }
@@ -116,6 +458,14 @@ pub enum AppState {
     Typing,
     Results,
     CharacterStats,
+    History,
+    PomodoroSummary,
+    /// Aggregate results after the last of the `--rounds`
+    Aggregate,
+    Heatmap,
+    Replay,
+    /// Press time and miss rate per finger, opened from the character stats
+    FingerStats,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -124,11 +474,44 @@ pub enum SortBy {
     AvgTime,
     MissRate,
     Attempts,
+    Difficulty,
+}
+
+/// How the character under the cursor is drawn while typing
+#[derive(Debug, Copy, Clone, Default, PartialEq, ValueEnum, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum CursorStyle {
+    /// Inverted colors over the whole cell
+    Block,
+    /// Underlined, dimmed like the rest of the untyped prompt
+    #[default]
+    Underline,
+    /// A thin marker just left of the character
+    Bar,
+}
+
+/// Metric used to color keys on the heatmap screen
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum HeatmapMetric {
+    #[default]
+    AvgTime,
+    MissRate,
+}
+
+impl HeatmapMetric {
+    pub fn toggle(self) -> Self {
+        match self {
+            HeatmapMetric::AvgTime => HeatmapMetric::MissRate,
+            HeatmapMetric::MissRate => HeatmapMetric::AvgTime,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct CharStatsState {
     pub scroll_offset: usize,
+    /// Row of the sorted table whose history is shown; clamped to the table when rendering
+    pub selected: usize,
     pub sort_by: SortBy,
     pub sort_ascending: bool,
 }
@@ -137,6 +520,7 @@ impl Default for CharStatsState {
     fn default() -> Self {
         Self {
             scroll_offset: 0,
+            selected: 0,
             sort_by: SortBy::Character,
             sort_ascending: true,
         }
@@ -149,11 +533,41 @@ pub struct RuntimeSettings {
     pub number_of_sentences: Option<usize>,
     pub number_of_secs: Option<usize>,
     pub supported_language: SupportedLanguage,
+    pub progression: bool,
     pub random_words: bool,
     pub capitalize: bool,
-    pub strict: bool,
+    pub policy: TypingPolicy,
     pub symbols: bool,
+    pub balanced_symbols: bool,
+    pub uppercase_drill: bool,
+    pub title_case: bool,
+    pub targeted_sentences: bool,
     pub substitute: bool,
+    pub fill_time: bool,
+    pub zen: bool,
+    pub quote: bool,
+    pub wordlist: Option<PathBuf>,
+    pub prompt_file: Option<PathBuf>,
+    pub numbers: bool,
+    pub number_format: NumberFormat,
+    pub sentence_words: bool,
+    pub keys: Option<KeySet>,
+    pub mix: Option<SupportedLanguage>,
+    pub target_aggression: f64,
+    pub recency_halflife: Option<f64>,
+    pub target: SelectionTarget,
+    pub seed: Option<u64>,
+    pub profile: Option<String>,
+    pub idle_timeout: f64,
+    pub tick_ms: u64,
+    pub countdown: Option<u64>,
+    pub bell: bool,
+    pub repeat: bool,
+    pub stats_letters_only: bool,
+    pub max_errors: Option<usize>,
+    pub forgive_corrected: bool,
+    pub ghost: bool,
+    pub celebration: CelebrationStyle,
 }
 
 impl From<&Cli> for RuntimeSettings {
@@ -163,15 +577,62 @@ impl From<&Cli> for RuntimeSettings {
             number_of_sentences: cli.number_of_sentences,
             number_of_secs: cli.number_of_secs,
             supported_language: cli.supported_language,
+            progression: cli.progression,
             random_words: cli.random_words,
             capitalize: cli.capitalize,
-            strict: cli.strict,
-            symbols: cli.symbols,
+            policy: if cli.strict {
+                TypingPolicy::Strict
+            } else {
+                cli.policy
+            },
+            symbols: cli.symbols || cli.balanced_symbols,
+            balanced_symbols: cli.balanced_symbols,
+            uppercase_drill: cli.uppercase_drill,
+            title_case: cli.title_case,
+            targeted_sentences: cli.targeted_sentences,
             substitute: cli.substitute,
+            fill_time: cli.fill_time,
+            zen: cli.zen,
+            quote: cli.quote,
+            wordlist: cli.wordlist.clone(),
+            prompt_file: cli.prompt_file.clone(),
+            numbers: cli.numbers,
+            number_format: NumberFormat {
+                range: cli.number_max.map(|max| cli.number_min.unwrap_or(0)..=max),
+                groups: cli.number_groups.clone(),
+                separator: cli.number_separator,
+            },
+            sentence_words: cli.sentence_words,
+            keys: cli.keys.clone(),
+            mix: cli.mix,
+            target_aggression: cli.target_aggression,
+            recency_halflife: cli.recency_halflife,
+            target: cli.target,
+            seed: cli.seed,
+            profile: cli.profile.clone(),
+            idle_timeout: cli.idle_timeout,
+            tick_ms: cli.tick_ms,
+            countdown: cli.countdown,
+            bell: cli.bell,
+            repeat: cli.repeat,
+            stats_letters_only: cli.stats_letters_only,
+            max_errors: cli.max_errors,
+            forgive_corrected: cli.forgive_corrected,
+            ghost: cli.ghost,
+            celebration: cli.celebration,
         }
     }
 }
 
+/// Write BEL straight to stdout and flush it, so it lands between frames rather than inside a
+/// partially written draw. BEL moves no cursor, so the alternate screen is left untouched.
+fn terminal_bell() -> Bell {
+    Bell(Box::new(|| {
+        let mut out = io::stdout();
+        let _ = out.write_all(b"\x07").and_then(|_| out.flush());
+    }))
+}
+
 impl RuntimeSettings {
     // Keep for backward compatibility, but prefer From trait
     pub fn from_cli(cli: &Cli) -> Self {
@@ -188,6 +649,160 @@ impl RuntimeSettings {
             substitute: self.substitute,
             capitalize: self.capitalize,
             symbols: self.symbols,
+            balanced_symbols: self.balanced_symbols,
+            uppercase_drill: self.uppercase_drill,
+            title_case: self.title_case,
+            targeted_sentences: self.targeted_sentences,
+            quote: self.quote,
+            wordlist: self.wordlist.clone(),
+            prompt_file: self.prompt_file.clone(),
+            numbers: self.numbers,
+            number_format: self.number_format.clone(),
+            sentence_words: self.sentence_words,
+            keys: self.keys.clone(),
+            mix: self.mix,
+            selection: SelectionConfig::with_aggression(self.target_aggression)
+                .for_target(self.target),
+            seed: self.seed,
+            profile: self.profile.clone(),
+            recency_halflife: self.recency_halflife,
+        }
+    }
+
+    /// A fresh test for `prompt` configured from these settings
+    pub fn new_thok(&self, prompt: String, word_count: usize) -> Thok {
+        let mut thok = Thok::with_profile(
+            prompt,
+            word_count,
+            self.number_of_secs.map(|ns| ns as f64),
+            false,
+            self.profile.as_deref(),
+        );
+        self.configure(&mut thok);
+        thok
+    }
+
+    /// The untimed `--warmup` test over `WARMUP_PROMPT`
+    pub fn warmup_thok(&self) -> Thok {
+        let mut thok = Thok::with_profile(
+            WARMUP_PROMPT.to_string(),
+            WARMUP_PROMPT.split_whitespace().count(),
+            None,
+            false,
+            self.profile.as_deref(),
+        );
+        self.configure_warmup(&mut thok);
+        thok
+    }
+
+    /// Like `configure`, but the warmup always ends with its prompt
+    fn configure_warmup(&self, thok: &mut Thok) {
+        self.configure(thok);
+        thok.session.config.zen = false;
+        thok.session.config.max_errors = None;
+        thok.prompt_extender = None;
+        thok.ghost_wpm = None;
+    }
+
+    /// Apply the session options to `thok` and start its countdown, if any
+    fn configure(&self, thok: &mut Thok) {
+        thok.session.config.policy = self.policy;
+        thok.session.config.idle_timeout_secs = self.idle_timeout;
+        thok.session.config.tick_ms = self.tick_ms;
+        thok.session.config.zen = self.zen;
+        thok.session.config.max_errors = self.max_errors;
+        thok.session.config.forgive_corrected = self.forgive_corrected;
+        thok.prompt_extender = self.prompt_extender();
+        thok.stats_letters_only = self.stats_letters_only;
+        thok.celebration.style = self.celebration;
+        if let Some(stats_db) = thok.stats_db.as_mut() {
+            stats_db.set_session_context(self.session_context());
+            if self.ghost {
+                thok.ghost_wpm = stats_db.get_best_wpm().ok().flatten();
+            }
+        }
+        if self.bell && self.policy == TypingPolicy::Strict {
+            thok.bell = Some(terminal_bell());
+        }
+        if let Some(secs) = self.countdown {
+            thok.session.start_countdown(secs as f64);
+        }
+    }
+
+    /// The language and options this test's character stats are stored under
+    pub fn session_context(&self) -> SessionContext {
+        let flags = [
+            (self.symbols, SessionContext::SYMBOLS),
+            (
+                self.capitalize || self.title_case,
+                SessionContext::CAPITALIZE,
+            ),
+            (self.numbers, SessionContext::NUMBERS),
+            (self.policy == TypingPolicy::Strict, SessionContext::STRICT),
+        ]
+        .into_iter()
+        .filter(|(on, _)| *on)
+        .fold(0, |flags, (_, flag)| flags | flag);
+        SessionContext {
+            language: self.supported_language.to_string().to_lowercase(),
+            flags,
+        }
+    }
+
+    /// Word source for `--zen` and `--fill-time`; the latter only applies to timed tests
+    pub fn prompt_extender(&self) -> Option<PromptExtender> {
+        if !self.zen && (!self.fill_time || self.number_of_secs.is_none()) {
+            return None;
+        }
+        let generator = WordGenerator::new(self.to_word_gen_config(None));
+        Some(PromptExtender(Box::new(move || {
+            generator.generate_prompt().0
+        })))
+    }
+}
+
+/// Display-only options consumed by the typing view
+#[derive(Debug, Clone, Default)]
+pub struct RenderSettings {
+    pub incorrect_glyph: Option<char>,
+    pub show_expected: bool,
+    pub spell_out: bool,
+    /// Keep the dimmed prompt on screen while idle rather than a full-screen message
+    pub hide_cursor_on_idle: bool,
+    pub theme: Theme,
+    pub cursor: CursorStyle,
+    pub layout: KeyboardLayout,
+    pub goal_wpm: Option<f64>,
+    pub smooth: Option<usize>,
+    pub thresholds: StatsThresholds,
+    pub minimal: bool,
+    /// Share link template; empty when sharing is off
+    pub share_template: String,
+    /// Caret blink half-period; 0 keeps the caret solid
+    pub blink_ms: u64,
+}
+
+impl From<&Cli> for RenderSettings {
+    fn from(cli: &Cli) -> Self {
+        Self {
+            incorrect_glyph: cli.incorrect_glyph,
+            show_expected: cli.show_expected,
+            spell_out: cli.spell_out,
+            hide_cursor_on_idle: cli.hide_cursor_on_idle,
+            theme: Theme::from(cli.theme),
+            cursor: cli.cursor,
+            layout: cli.layout,
+            goal_wpm: cli.goal_wpm,
+            smooth: cli.smooth,
+            thresholds: StatsThresholds {
+                good_ms: cli.good_ms,
+                slow_ms: cli.slow_ms,
+                good_miss: cli.good_miss,
+                bad_miss: cli.bad_miss,
+            },
+            minimal: cli.minimal,
+            share_template: cli.share_template.clone(),
+            blink_ms: cli.blink_ms,
         }
     }
 }
@@ -197,35 +812,104 @@ pub struct App {
     pub thok: Thok,
     pub state: AppState,
     pub char_stats_state: CharStatsState,
+    pub heatmap_metric: HeatmapMetric,
     pub runtime_settings: RuntimeSettings,
+    pub render_settings: RenderSettings,
     pub config_store: Box<dyn crate::config::ConfigStore>,
+    pub history: Vec<crate::history::HistoryEntry>,
+    pub pomodoro: Option<crate::pomodoro::PomodoroSession>,
+    pub rounds: Option<crate::rounds::Rounds>,
+    pub last_result: Option<crate::exit_status::SessionResult>,
+    pub last_report: Option<crate::report::SessionReport>,
+    pub last_keylog: Option<crate::report::KeyLog>,
+    /// Attribution for the current prompt in `--quote` mode
+    pub quote: Option<crate::language::Quote>,
+    /// Playback of the last test, while on the replay screen
+    pub replay: Option<crate::replay::Replay>,
+    /// Tries at the current prompt, counting the first
+    pub attempt: u32,
+    /// Set when a test missed `--min-accuracy`; the same prompt restarts at this time
+    pub auto_retry_at: Option<SystemTime>,
+    /// WPM of each finished run of the current prompt, kept for the `--repeat` average
+    pub repeat_wpms: Vec<f64>,
+    /// Set when a test finishes under `--auto-advance`; a new prompt starts at this time
+    pub auto_advance_at: Option<SystemTime>,
+    /// Every test finished under `--auto-advance`, summarized on the results and on quit
+    pub auto_advanced: Vec<crate::rounds::SessionSummary>,
+    /// Where `--checkpoint` saves the test in progress
+    pub checkpoint_path: Option<PathBuf>,
+    pub keystrokes_since_checkpoint: usize,
+    /// The current test is the `--warmup`; finishing it primes the stats and starts the real one
+    pub warming_up: bool,
+    /// Ticks since the last keystroke, for the caret blink
+    pub caret_ticks: u64,
+    /// Set on the results when recent tests unlock a harder language and `--progression`
+    /// isn't there to switch to it
+    pub progression_hint: Option<String>,
+    /// Kept across tests so each new prompt continues the `--seed` sequence
+    pub generator: WordGenerator,
 }
 
 impl App {
     pub fn new(cli: Cli) -> Self {
         let runtime_settings = RuntimeSettings::from(&cli);
-        let config = runtime_settings.to_word_gen_config(cli.prompt.clone());
-        let generator = WordGenerator::new(config);
-        let (prompt, word_count) = generator.generate_prompt();
+        // A custom prompt doesn't use the stats, so there is nothing to warm up for
+        let warming_up = cli.warmup && cli.prompt.is_none();
+        let generator = WordGenerator::new(runtime_settings.to_word_gen_config(cli.prompt.clone()));
+        let (thok, quote) = if warming_up {
+            (runtime_settings.warmup_thok(), None)
+        } else {
+            let (prompt, word_count, quote) = generator.generate_prompt_with_quote();
+            (runtime_settings.new_thok(prompt, word_count), quote)
+        };
 
         Self {
-            thok: Thok::new(
-                prompt,
-                word_count,
-                runtime_settings.number_of_secs.map(|ns| ns as f64),
-                runtime_settings.strict,
-            ),
-            cli: Some(cli),
+            thok,
             state: AppState::Typing,
             char_stats_state: CharStatsState::default(),
+            heatmap_metric: HeatmapMetric::default(),
             runtime_settings,
+            render_settings: RenderSettings::from(&cli),
             config_store: Box::new(crate::config::FileConfigStore::default()),
+            history: Vec::new(),
+            pomodoro: cli
+                .pomodoro
+                .map(crate::pomodoro::PomodoroSession::from_minutes),
+            rounds: cli.rounds.map(crate::rounds::Rounds::new),
+            checkpoint_path: cli
+                .checkpoint
+                .then(|| crate::app_dirs::AppDirs::checkpoint_path(cli.profile.as_deref()))
+                .flatten(),
+            keystrokes_since_checkpoint: 0,
+            cli: Some(cli),
+            last_result: None,
+            last_report: None,
+            last_keylog: None,
+            quote,
+            replay: None,
+            attempt: 1,
+            auto_retry_at: None,
+            repeat_wpms: Vec::new(),
+            auto_advance_at: None,
+            auto_advanced: Vec::new(),
+            warming_up,
+            caret_ticks: 0,
+            progression_hint: None,
+            generator,
         }
     }
 
-    /// Persist current runtime settings to config file
+    /// Whether the caret is drawn in the current blink phase; always with `--blink-ms 0`
+    pub fn caret_visible(&self) -> bool {
+        let blink_ms = self.render_settings.blink_ms;
+        let elapsed_ms = self.caret_ticks * self.runtime_settings.tick_ms;
+        blink_ms == 0 || (elapsed_ms / blink_ms).is_multiple_of(2)
+    }
+
+    /// Persist current runtime settings to config file, keeping any other saved defaults
     pub fn save_config(&self) {
-        let config = crate::config::Config::from(&self.runtime_settings);
+        let mut config = self.config_store.load();
+        config.apply_runtime_settings(&self.runtime_settings);
         if let Err(_e) = self.config_store.save(&config) {
             #[cfg(any(debug_assertions, test))]
             eprintln!("Failed to save config: {}", _e);
@@ -233,60 +917,590 @@ impl App {
     }
 
     pub fn reset(&mut self, new_prompt: Option<String>) {
-        let config = self.runtime_settings.to_word_gen_config(new_prompt);
-        let generator = WordGenerator::new(config);
-        let (prompt, word_count) = generator.generate_prompt();
+        // Retrying the same quote keeps its attribution
+        let is_retry = new_prompt.as_deref() == Some(self.thok.session.prompt.as_str());
+        if is_retry {
+            self.attempt += 1;
+            // Nothing to generate, and the open stats store can be kept
+            self.thok.restart_same_prompt();
+            if self.warming_up {
+                self.runtime_settings.configure_warmup(&mut self.thok);
+            } else {
+                self.runtime_settings.configure(&mut self.thok);
+            }
+        } else {
+            self.warming_up = false;
+            if self.runtime_settings.progression {
+                if let Some(language) = self.unlocked_language() {
+                    self.runtime_settings.supported_language = language;
+                }
+            }
+            let config = self.runtime_settings.to_word_gen_config(new_prompt);
+            self.generator.reconfigure(config);
+            let (prompt, word_count, quote) = self.generator.generate_prompt_with_quote();
+            self.quote = quote;
+            self.attempt = 1;
+            self.repeat_wpms.clear();
+            self.thok = self.runtime_settings.new_thok(prompt, word_count);
+        }
+        self.auto_retry_at = None;
+        self.auto_advance_at = None;
+        self.progression_hint = None;
+        self.clear_checkpoint();
 
-        self.thok = Thok::new(
-            prompt,
-            word_count,
-            self.runtime_settings.number_of_secs.map(|ns| ns as f64),
-            self.runtime_settings.strict,
-        );
         self.state = AppState::Typing;
         self.char_stats_state = CharStatsState::default();
+        self.replay = None;
+    }
+
+    /// The unfinished test left behind by an earlier `--checkpoint` run, if any
+    pub fn pending_checkpoint(&self) -> Option<crate::session::Checkpoint> {
+        self.checkpoint_path
+            .as_deref()
+            .and_then(crate::session::load_checkpoint)
+    }
+
+    /// Pick up an unfinished test where its checkpoint left off
+    pub fn resume(&mut self, checkpoint: &crate::session::Checkpoint) {
+        self.thok = self
+            .runtime_settings
+            .new_thok(checkpoint.prompt.clone(), checkpoint.number_of_words);
+        self.thok.session.restore(checkpoint);
+        self.quote = None;
+        self.warming_up = false;
+        self.state = AppState::Typing;
+    }
+
+    /// Count a keystroke and save the test in progress every `CHECKPOINT_INTERVAL` of them
+    pub fn checkpoint_if_due(&mut self) {
+        let Some(path) = self.checkpoint_path.as_deref() else {
+            return;
+        };
+        if self.state != AppState::Typing || self.thok.has_finished() || self.warming_up {
+            return;
+        }
+        self.keystrokes_since_checkpoint += 1;
+        if self.keystrokes_since_checkpoint >= crate::session::CHECKPOINT_INTERVAL {
+            self.keystrokes_since_checkpoint = 0;
+            if let Err(_e) = crate::session::save_checkpoint(&self.thok.session, path) {
+                #[cfg(any(debug_assertions, test))]
+                eprintln!("Failed to save checkpoint: {}", _e);
+            }
+        }
+    }
+
+    pub fn clear_checkpoint(&mut self) {
+        if let Some(path) = self.checkpoint_path.as_deref() {
+            crate::session::clear_checkpoint(path);
+        }
+        self.keystrokes_since_checkpoint = 0;
+    }
+
+    /// Move on to the next test: a fresh prompt, or the same one again with `--repeat`
+    pub fn new_test(&mut self) {
+        if self.runtime_settings.repeat {
+            self.reset(Some(self.thok.session.prompt.clone()));
+        } else {
+            self.reset(None);
+        }
+    }
+
+    /// Play back the finished test's keystrokes at their recorded pace
+    pub fn start_replay(&mut self) {
+        self.replay = Some(crate::replay::Replay::new(
+            self.thok.input(),
+            self.thok.session.now(),
+            1.0,
+        ));
+        self.state = AppState::Replay;
+    }
+
+    /// Compute and persist results for the current test; shared by the TUI and headless runs
+    pub fn finish_test(&mut self) {
+        self.thok.calc_results();
+        self.keep_last_result();
+    }
+
+    /// Hold on to the finished test's result, report and key log for printing and exporting
+    fn keep_last_result(&mut self) {
+        self.last_result = Some(crate::exit_status::SessionResult::from_thok(&self.thok));
+        self.last_report = Some(crate::report::SessionReport::from_thok(&self.thok));
+        self.last_keylog = Some(crate::report::KeyLog::from_thok(&self.thok));
+    }
+
+    /// Finalize a just-finished test and move to the results screen.
+    /// During a pomodoro run or between rounds, returns true when the next test should start
+    /// right away.
+    pub fn complete_test(&mut self, width: u16, height: u16) -> bool {
+        if self.warming_up {
+            self.finish_warmup();
+            return false;
+        }
+        self.finish_test();
+        self.clear_checkpoint();
+        self.thok.start_celebration_if_worthy(width, height);
+        self.state = AppState::Results;
+        if !self.runtime_settings.progression {
+            self.progression_hint = self.unlocked_language().map(|language| {
+                format!(
+                    "Your last {} tests unlock {}: try -l {} or --progression",
+                    crate::progression::ROLLING_SESSIONS,
+                    language.to_string().to_lowercase(),
+                    language.to_string().to_lowercase(),
+                )
+            });
+        }
+        if self.runtime_settings.repeat {
+            self.repeat_wpms.push(self.thok.wpm());
+        }
+
+        if let Some(pomodoro) = self.pomodoro.as_mut() {
+            pomodoro.record(&self.thok);
+            if pomodoro.is_over() {
+                self.state = AppState::PomodoroSummary;
+            } else {
+                return true;
+            }
+        } else if let Some(rounds) = self.rounds.as_mut() {
+            rounds.record(&self.thok);
+            if rounds.is_over() {
+                self.state = AppState::Aggregate;
+            } else {
+                return !self.waits_between_rounds();
+            }
+        } else if self.below_min_accuracy() {
+            self.auto_retry_at = Some(self.thok.session.now() + AUTO_RETRY_DELAY);
+        } else if let Some(delay_ms) = self.cli.as_ref().and_then(|cli| cli.auto_advance) {
+            self.auto_advanced.push(crate::rounds::SessionSummary {
+                wpm: self.thok.wpm(),
+                accuracy: self.thok.accuracy(),
+            });
+            self.auto_advance_at = Some(self.thok.session.now() + Duration::from_millis(delay_ms));
+        }
+        false
+    }
+
+    /// The harder language the recent results unlock over the current one, if any
+    fn unlocked_language(&self) -> Option<SupportedLanguage> {
+        let recent = self
+            .thok
+            .stats_db
+            .as_ref()?
+            .get_recent_session_results(crate::progression::ROLLING_SESSIONS)
+            .ok()?;
+        crate::progression::next_language(self.runtime_settings.supported_language, &recent)
+    }
+
+    /// End the `--warmup`: its keystrokes go into the character stats, with no result, log
+    /// entry or personal best, before the real prompt is generated from them
+    fn finish_warmup(&mut self) {
+        self.thok.flush_char_stats();
+        self.reset(None);
+    }
+
+    /// Whether `--rounds-wait` holds each round's results until Enter
+    pub fn waits_between_rounds(&self) -> bool {
+        self.cli.as_ref().is_some_and(|cli| cli.rounds_wait)
+    }
+
+    /// Whether the last finished test missed the `--min-accuracy` target
+    pub fn below_min_accuracy(&self) -> bool {
+        let min_accuracy = self.cli.as_ref().and_then(|cli| cli.min_accuracy);
+        min_accuracy.is_some_and(|min| self.thok.accuracy() < min)
+    }
+
+    /// Running tally across `--repeat` runs, once there is more than one to average
+    pub fn repeat_summary(&self) -> Option<String> {
+        let runs = self.repeat_wpms.len();
+        if runs < 2 {
+            return None;
+        }
+        let avg = self.repeat_wpms.iter().sum::<f64>() / runs as f64;
+        let best = self.repeat_wpms.iter().copied().fold(0.0, f64::max);
+        Some(format!(
+            "{runs} repeats: avg {avg:.0} wpm, best {best:.0} wpm"
+        ))
+    }
+
+    /// Whether the pause after a missed `--min-accuracy` target is over
+    pub fn auto_retry_due(&self) -> bool {
+        self.auto_retry_at
+            .is_some_and(|at| self.thok.session.now() >= at)
+    }
+
+    /// Whether the `--auto-advance` flash of the results is over; a celebration plays out
+    /// first, and leaving the results screen holds the next prompt back
+    pub fn auto_advance_due(&self) -> bool {
+        self.state == AppState::Results
+            && !self.thok.celebration.is_active
+            && self
+                .auto_advance_at
+                .is_some_and(|at| self.thok.session.now() >= at)
+    }
+
+    /// Running tally of the tests finished under `--auto-advance`
+    pub fn auto_advance_summary(&self) -> Option<String> {
+        let runs = self.auto_advanced.len();
+        if runs == 0 {
+            return None;
+        }
+        let wpms: Vec<f64> = self.auto_advanced.iter().map(|s| s.wpm).collect();
+        let accuracies: Vec<f64> = self.auto_advanced.iter().map(|s| s.accuracy).collect();
+        Some(format!(
+            "{runs} tests: avg {:.0} wpm, best {:.0} wpm, avg {:.0}% acc",
+            crate::util::mean(&wpms).unwrap_or(0.0),
+            wpms.iter().copied().fold(0.0, f64::max),
+            crate::util::mean(&accuracies).unwrap_or(0.0)
+        ))
     }
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let cli = Cli::parse();
+fn main() -> Result<ExitCode, Box<dyn Error>> {
+    use crate::config::ConfigStore;
+    let config_store = crate::config::FileConfigStore::default();
+    let matches = config_store
+        .load()
+        .apply_defaults(Cli::command())
+        .get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
+    if cli.write_config {
+        config_store.save(&crate::config::Config::from(&cli))?;
+        println!("Wrote {}", config_store.path().display());
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if let Some(Command::Stats { days, stats_since }) = &cli.command {
+        let rows = crate::stats::StatsDb::new(cli.profile.as_deref())?
+            .get_daily_summary(*days, *stats_since)?;
+        print!("{}", daily_summary_table(&rows));
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if let Some(path) = &cli.export_stats {
+        let json = crate::stats::StatsDb::new(cli.profile.as_deref())?.export_json()?;
+        std::fs::write(path, json)?;
+        println!("Exported statistics to {}", path.display());
+        return Ok(ExitCode::SUCCESS);
+    }
 
-    if !stdin().is_tty() {
+    if cli.reset_stats {
+        let db = crate::stats::StatsDb::new(cli.profile.as_deref())?;
+        let count = db.get_session_count()?;
+        let question = format!("Delete all character statistics ({count} session rows)?");
+        if confirm(&question, &mut stdin().lock(), &mut io::stdout())? {
+            db.clear_all_stats()?;
+            println!("Deleted {count} session rows");
+        } else {
+            println!("Aborted; statistics left untouched");
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+    let gate = ResultGate {
+        min_accuracy: cli.min_accuracy,
+        max_errors: cli.max_errors,
+    };
+
+    if cli.stdin {
+        match read_piped_prompt() {
+            Ok(prompt) => cli.prompt = Some(prompt),
+            Err(e) => {
+                let mut cmd = Cli::command();
+                cmd.error(ErrorKind::Io, e).exit();
+            }
+        }
+    }
+
+    if let Some(path) = &cli.code_file {
+        match read_code_prompt(path, cli.strip_comments) {
+            Ok(prompt) => cli.prompt = Some(prompt),
+            Err(e) => {
+                let mut cmd = Cli::command();
+                cmd.error(ErrorKind::Io, e).exit();
+            }
+        }
+    }
+
+    let headless = cli.json && !stdin().is_tty() && !cli.stdin;
+    if !stdin().is_tty() && !headless && !cli.stdin {
         let mut cmd = Cli::command();
         cmd.error(ErrorKind::Io, "stdin must be a tty").exit();
     }
 
-    enable_raw_mode()?;
+    if let Err(e) = RenderSettings::from(&cli).thresholds.validate() {
+        let mut cmd = Cli::command();
+        cmd.error(ErrorKind::ArgumentConflict, e).exit();
+    }
 
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    if let (Some(min), Some(max)) = (cli.number_min, cli.number_max) {
+        if min > max {
+            let mut cmd = Cli::command();
+            cmd.error(
+                ErrorKind::ArgumentConflict,
+                format!("--number-min ({min}) must not be above --number-max ({max})"),
+            )
+            .exit();
+        }
+    }
 
+    if let Some(path) = &cli.wordlist {
+        if let Err(e) = Language::from_wordlist_file(path) {
+            let mut cmd = Cli::command();
+            cmd.error(
+                ErrorKind::ValueValidation,
+                format!("unable to load word list {}: {e}", path.display()),
+            )
+            .exit();
+        }
+    }
+
+    if let Some(path) = &cli.prompt_file {
+        if let Err(e) = word_generator::read_prompt_lines(path) {
+            let mut cmd = Cli::command();
+            cmd.error(
+                ErrorKind::ValueValidation,
+                format!("unable to load prompt file {}: {e}", path.display()),
+            )
+            .exit();
+        }
+    }
+
+    if headless {
+        // The piped text is typed against the real prompt
+        cli.warmup = false;
+    }
+
+    let print_json = cli.json;
+    let alt_screen = !cli.no_altscreen;
+    let export_keylog = cli.export_keylog.clone();
+    let piped_prompt = cli.stdin;
     let mut app = App::new(cli);
-    start_tui(&mut terminal, &mut app)?;
+    // The piped text is the prompt to type, so don't offer to swap it for a checkpoint
+    if let Some(checkpoint) = app
+        .pending_checkpoint()
+        .filter(|_| !headless && !piped_prompt)
+    {
+        let question = format!(
+            "Resume the unfinished test ({} of {} characters typed)?",
+            checkpoint.cursor_pos,
+            checkpoint.prompt.chars().count()
+        );
+        if confirm(&question, &mut stdin().lock(), &mut io::stdout())? {
+            app.resume(&checkpoint);
+        } else {
+            app.clear_checkpoint();
+        }
+    }
+    let result = if headless {
+        let mut typed = String::new();
+        io::Read::read_to_string(&mut stdin(), &mut typed)?;
+        run_headless(&mut app, &typed)
+    } else {
+        let _guard = TerminalGuard::enter(alt_screen)?;
+        let backend = CrosstermBackend::new(io::stdout());
+        let mut terminal = if alt_screen {
+            Terminal::new(backend)?
+        } else {
+            // Draw below the shell prompt so the last frame stays in the scrollback
+            let (_, rows) = crossterm::terminal::size()?;
+            Terminal::with_options(
+                backend,
+                TerminalOptions {
+                    viewport: Viewport::Inline(rows),
+                },
+            )?
+        };
+        start_tui(&mut terminal, &mut app, CrosstermEventSource::new())?
+    };
+
+    if !headless {
+        if let Some(summary) = app.auto_advance_summary() {
+            println!("{summary}");
+        }
+    }
+
+    if print_json {
+        if let Some(report) = &app.last_report {
+            println!("{}", report.to_json());
+        }
+    }
+
+    if let (Some(path), Some(keylog)) = (&export_keylog, &app.last_keylog) {
+        std::fs::write(path, keylog.to_json())?;
+    }
+
+    Ok(ExitStatus::evaluate(result.as_ref(), &gate).into())
+}
+
+/// Per-day averages as a plain text table for `klik stats`
+fn daily_summary_table(rows: &[crate::stats::DailySummary]) -> String {
+    if rows.is_empty() {
+        return "No finished tests recorded yet.\n".to_string();
+    }
+    let mut table = format!("{:<12}{:>6}{:>7}\n", "date", "wpm", "acc");
+    for (date, wpm, accuracy) in rows {
+        table.push_str(&format!(
+            "{date:<12}{wpm:>6.0}{:>7}\n",
+            format!("{accuracy:.0}%")
+        ));
+    }
+    table
+}
+
+/// Read all of the piped stdin as a `--stdin` prompt. Keys are then read from the controlling
+/// terminal, which crossterm opens at `/dev/tty` when stdin is not a tty.
+#[cfg(unix)]
+fn read_piped_prompt() -> Result<String, String> {
+    if stdin().is_tty() {
+        return Err("--stdin needs text piped to it, e.g. echo \"text\" | klik --stdin".into());
+    }
+    let mut text = String::new();
+    io::Read::read_to_string(&mut stdin(), &mut text)
+        .map_err(|e| format!("unable to read the prompt from stdin: {e}"))?;
+    let prompt = piped_prompt(&text).ok_or("the text piped to --stdin is empty")?;
+    std::fs::File::open("/dev/tty")
+        .map_err(|e| format!("--stdin needs a terminal to read keys from (/dev/tty: {e})"))?;
+    Ok(prompt)
+}
 
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen,)?;
-    terminal.show_cursor()?;
+#[cfg(not(unix))]
+fn read_piped_prompt() -> Result<String, String> {
+    Err("--stdin is only supported on Unix".into())
+}
+
+/// Piped text as a single-line prompt: line breaks and runs of whitespace become one space
+fn piped_prompt(text: &str) -> Option<String> {
+    let prompt = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!prompt.is_empty()).then_some(prompt)
+}
 
-    Ok(())
+/// Read a `--code-file` prompt, choosing the comment markers from the file extension
+fn read_code_prompt(path: &Path, strip_comments: bool) -> Result<String, String> {
+    let source = std::fs::read_to_string(path)
+        .map_err(|e| format!("unable to read {}: {e}", path.display()))?;
+    let markers: &[&str] = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("rs" | "c" | "h" | "cc" | "cpp" | "hpp" | "cs" | "go" | "java" | "js" | "kt")
+        | Some("scala" | "swift" | "ts") => &["//"],
+        Some("py" | "rb" | "sh" | "bash" | "zsh" | "pl" | "r" | "toml" | "yaml" | "yml") => &["#"],
+        Some("hs" | "lua" | "sql") => &["--"],
+        _ => &["//", "#"],
+    };
+    code_prompt(&source, markers, strip_comments)
+        .ok_or_else(|| format!("{} has no code to type", path.display()))
 }
 
+/// Source code as a multi-line prompt: blank lines (and, with `strip_comments`, lines starting
+/// with one of `markers`) are dropped, trailing whitespace trimmed and tabs expanded to four
+/// spaces so indentation can be typed with the space bar
+fn code_prompt(source: &str, markers: &[&str], strip_comments: bool) -> Option<String> {
+    let lines: Vec<String> = source
+        .lines()
+        .map(|line| line.trim_end().replace('\t', "    "))
+        .filter(|line| !line.is_empty())
+        .filter(|line| !strip_comments || !markers.iter().any(|m| line.trim_start().starts_with(m)))
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// Ask a yes/no question on the terminal; only an explicit "y"/"yes" confirms
+fn confirm<R: io::BufRead, W: io::Write>(
+    question: &str,
+    input: &mut R,
+    output: &mut W,
+) -> io::Result<bool> {
+    write!(output, "{question} [y/N] ")?;
+    output.flush()?;
+    let mut answer = String::new();
+    input.read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Type `typed` into the current test without a terminal; line breaks count as spaces.
+/// Returns a result only if the text covered the whole prompt.
+fn run_headless(app: &mut App, typed: &str) -> Option<SessionResult> {
+    // Piped text carries no key timing, so it is typed on a simulated clock at a steady pace
+    // and, like `runtime::run_session`, never reaches the stats database or the session log
+    let clock = crate::clock::MockClock::default();
+    app.thok.session.clock = std::sync::Arc::new(clock.clone());
+    let chars = typed
+        .trim_end_matches(['\r', '\n'])
+        .chars()
+        .filter(|&c| c != '\r')
+        .map(|c| if c == '\n' { ' ' } else { c });
+    for c in chars {
+        if app.thok.has_finished() {
+            break;
+        }
+        clock.advance(HEADLESS_KEY_INTERVAL);
+        app.thok.write(c);
+    }
+    if !app.thok.has_finished() {
+        return None;
+    }
+    app.thok.session.calc_results();
+    app.keep_last_result();
+    app.last_result
+}
+
+/// The pace piped text is typed at with `--json`: a key every 200 ms, about 60 wpm
+const HEADLESS_KEY_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Typed by `--warmup`: four pangrams, so every letter gets enough attempts to be ranked
+const WARMUP_PROMPT: &str = "the quick brown fox jumps over the lazy dog pack my box with five dozen liquor jugs how vexingly quick daft zebras jump sphinx of black quartz judge my vow";
+
+/// Default `--blink-ms`: the caret is on for half a second, then off for half a second
+pub const DEFAULT_BLINK_MS: u64 = 500;
+
+/// How long the "retrying" message shows before a missed `--min-accuracy` test restarts
+const AUTO_RETRY_DELAY: Duration = Duration::from_millis(1500);
+
 #[derive(Debug)]
 enum ExitType {
     Restart,
     New,
     Quit,
 }
-fn start_tui<B: Backend>(
+/// Raw mode, and the alternate screen unless running inline, for as long as it lives. Dropping
+/// it restores the terminal, including when the session ends in an error or a panic.
+struct TerminalGuard {
+    alt_screen: bool,
+}
+
+impl TerminalGuard {
+    fn enter(alt_screen: bool) -> io::Result<Self> {
+        enable_raw_mode()?;
+        let guard = Self { alt_screen };
+        if alt_screen {
+            execute!(io::stdout(), EnterAlternateScreen)?;
+        }
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = disable_raw_mode();
+        let mut stdout = io::stdout();
+        if self.alt_screen {
+            let _ = execute!(stdout, LeaveAlternateScreen);
+        } else {
+            // Leave the shell prompt on a fresh line below the last frame
+            let _ = writeln!(stdout);
+        }
+        let _ = execute!(stdout, crossterm::cursor::Show);
+    }
+}
+
+/// Run the key-handling loop until the user quits; returns the result of the last completed
+/// test, if any. The binary reads `events` from the terminal; tests pass a scripted
+/// `TestEventSource` and a `TestBackend`.
+fn start_tui<B: Backend, E: ThokEventSource>(
     terminal: &mut Terminal<B>,
     mut app: &mut App,
-) -> Result<(), Box<dyn Error>> {
-    // Use the new Runner with crossterm event source and fixed ticker
-    let event_source = CrosstermEventSource::new();
-    let ticker = FixedTicker::new(Duration::from_millis(TICK_RATE_MS));
-    let runner = Runner::new(event_source, ticker);
+    events: E,
+) -> Result<Option<SessionResult>, Box<dyn Error>> {
+    let ticker = FixedTicker::new(Duration::from_millis(app.runtime_settings.tick_ms));
+    let runner = Runner::new(events, ticker);
 
     loop {
         let mut exit_type: ExitType = ExitType::Quit;
@@ -297,24 +1511,49 @@ fn start_tui<B: Backend>(
 
             match runner.step() {
                 RtEvent::Tick => {
-                    if app.thok.has_started() && !app.thok.has_finished() {
+                    if app.thok.session.is_counting_down() {
+                        app.thok.on_tick();
+                        terminal.draw(|f| ui(app, f))?;
+                    } else if app.thok.has_started() && !app.thok.has_finished() {
                         app.thok.on_tick();
 
                         if app.thok.has_finished() {
-                            app.thok.calc_results();
                             // Get terminal size for celebration
                             let size = terminal.size().unwrap_or_default();
-                            app.thok
-                                .start_celebration_if_worthy(size.width, size.height);
-                            app.state = AppState::Results;
+                            if app.complete_test(size.width, size.height) {
+                                exit_type = ExitType::New;
+                                break;
+                            }
                         }
                     }
 
+                    if app.auto_retry_due() {
+                        exit_type = ExitType::Restart;
+                        break;
+                    }
+                    if app.auto_advance_due() {
+                        exit_type = ExitType::New;
+                        break;
+                    }
+
                     // Always update celebration animation if active
                     app.thok.update_celebration();
+                    app.caret_ticks += 1;
 
-                    // Draw on every tick if there's active animation or during typing
+                    if app.state == AppState::Replay {
+                        let now = app.thok.session.now();
+                        if let Some(replay) = app.replay.as_mut() {
+                            replay.advance(now);
+                        }
+                    }
+
+                    // Draw on every tick if there's active animation, a replay or during typing;
+                    // a blinking caret needs it before the first key too
+                    let blinking =
+                        app.render_settings.blink_ms > 0 && app.state == AppState::Typing;
                     if app.thok.celebration.is_active
+                        || app.state == AppState::Replay
+                        || (blinking && !app.thok.has_finished())
                         || (app.thok.has_started() && !app.thok.has_finished())
                     {
                         terminal.draw(|f| ui(app, f))?;
@@ -324,17 +1563,34 @@ fn start_tui<B: Backend>(
                     terminal.draw(|f| ui(app, f))?;
                 }
                 RtEvent::Key(key) => {
+                    // The caret stays solid while keys are coming in
+                    app.caret_ticks = 0;
                     // Mark activity for any key press during typing to exit idle state
                     if app.state == AppState::Typing && !app.thok.has_finished() {
                         let was_idle = app.thok.mark_activity();
                         if was_idle {
-                            // Reset session when exiting idle state
-                            app.reset(Some(app.thok.session.prompt.clone()));
-                            // Skip processing this key event since we just reset
+                            // The key only wakes the test up; typing carries on from the next one
+                            terminal.draw(|f| ui(app, f))?;
                             continue;
                         }
                     }
 
+                    // Esc ends an endless test with results instead of quitting
+                    if key.code == KeyCode::Esc
+                        && app.state == AppState::Typing
+                        && app.thok.session.config.zen
+                        && app.thok.has_started()
+                    {
+                        app.thok.session.end_zen();
+                        let size = terminal.size().unwrap_or_default();
+                        if app.complete_test(size.width, size.height) {
+                            exit_type = ExitType::New;
+                            break;
+                        }
+                        terminal.draw(|f| ui(app, f))?;
+                        continue;
+                    }
+
                     // Global keys
                     if key.code == KeyCode::Esc
                         || (key.modifiers.contains(KeyModifiers::CONTROL)
@@ -366,13 +1622,15 @@ fn start_tui<B: Backend>(
                         _ => {}
                     }
 
+                    app.checkpoint_if_due();
+
                     // If we're in Typing state and just finished, finalize results
                     if app.state == AppState::Typing && app.thok.has_finished() {
-                        app.thok.calc_results();
                         let size = terminal.size().unwrap_or_default();
-                        app.thok
-                            .start_celebration_if_worthy(size.width, size.height);
-                        app.state = AppState::Results;
+                        if app.complete_test(size.width, size.height) {
+                            exit_type = ExitType::New;
+                            break;
+                        }
                     }
                     terminal.draw(|f| ui(app, f))?;
                 }
@@ -384,7 +1642,7 @@ fn start_tui<B: Backend>(
                 app.reset(Some(app.thok.session.prompt.clone()));
             }
             ExitType::New => {
-                app.reset(None);
+                app.new_test();
             }
             ExitType::Quit => {
                 break;
@@ -392,7 +1650,7 @@ fn start_tui<B: Backend>(
         }
     }
 
-    Ok(())
+    Ok(app.last_result)
 }
 
 // legacy character stats renderer removed; see ui::character_stats::render_character_stats
@@ -410,6 +1668,11 @@ mod tests {
     use clap::Parser;
     use crossterm::event::KeyEvent;
 
+    /// Defaults for flags a test doesn't set explicitly
+    fn default_cli() -> Cli {
+        Cli::parse_from(["klik"])
+    }
+
     #[test]
     fn test_cli_default_values() {
         let cli = Cli::parse_from(["klik"]);
@@ -419,6 +1682,85 @@ mod tests {
         assert_eq!(cli.number_of_secs, None);
         assert_eq!(cli.prompt, None);
         assert!(matches!(cli.supported_language, SupportedLanguage::English));
+        assert!(!cli.no_altscreen);
+        assert!(Cli::parse_from(["klik", "--no-altscreen"]).no_altscreen);
+    }
+
+    #[test]
+    fn test_stats_subcommand() {
+        assert!(default_cli().command.is_none());
+
+        let cli = Cli::parse_from([
+            "klik",
+            "stats",
+            "--days",
+            "3",
+            "--stats-since",
+            "2026-10-01",
+        ]);
+        let Some(Command::Stats { days, stats_since }) = cli.command else {
+            panic!("expected the stats subcommand");
+        };
+        assert_eq!(days, 3);
+        assert_eq!(stats_since, NaiveDate::from_ymd_opt(2026, 10, 1));
+        assert!(Cli::try_parse_from(["klik", "stats", "--stats-since", "yesterday"]).is_err());
+
+        let table = daily_summary_table(&[
+            ("2026-10-01".to_string(), 61.4, 95.6),
+            ("2026-10-02".to_string(), 70.0, 100.0),
+        ]);
+        assert_eq!(
+            table,
+            "date           wpm    acc\n2026-10-01      61    96%\n2026-10-02      70   100%\n"
+        );
+        assert_eq!(
+            daily_summary_table(&[]),
+            "No finished tests recorded yet.\n"
+        );
+    }
+
+    #[test]
+    fn test_cli_stdin_prompt() {
+        assert!(!default_cli().stdin);
+        assert!(Cli::parse_from(["klik", "--stdin"]).stdin);
+        assert!(Cli::try_parse_from(["klik", "--stdin", "-p", "text"]).is_err());
+
+        assert_eq!(
+            piped_prompt("the quick\nbrown  fox\r\n").as_deref(),
+            Some("the quick brown fox")
+        );
+        assert_eq!(piped_prompt(" \n\t"), None);
+    }
+
+    #[test]
+    fn test_code_file_prompt() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fixture.rs");
+        std::fs::write(
+            &path,
+            "// adds one\nfn inc(x: u32) -> u32 {\n\n\tx + 1 // done\n}  \n\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            read_code_prompt(&path, false).unwrap(),
+            "// adds one\nfn inc(x: u32) -> u32 {\n    x + 1 // done\n}"
+        );
+        assert_eq!(
+            read_code_prompt(&path, true).unwrap(),
+            "fn inc(x: u32) -> u32 {\n    x + 1 // done\n}"
+        );
+
+        let script = dir.path().join("fixture.py");
+        std::fs::write(&script, "# only a comment\n\n").unwrap();
+        assert!(read_code_prompt(&script, true).is_err());
+        assert!(read_code_prompt(&dir.path().join("missing.rs"), false).is_err());
+
+        let cli = Cli::parse_from(["klik", "--code-file", "a.rs", "--strip-comments"]);
+        assert_eq!(cli.code_file, Some(PathBuf::from("a.rs")));
+        assert!(cli.strip_comments);
+        assert!(Cli::try_parse_from(["klik", "--strip-comments"]).is_err());
+        assert!(Cli::try_parse_from(["klik", "--code-file", "a.rs", "--stdin"]).is_err());
     }
 
     #[test]
@@ -430,6 +1772,107 @@ mod tests {
         assert_eq!(cli.number_of_words, 50);
     }
 
+    #[test]
+    fn test_cli_target_aggression() {
+        assert_eq!(Cli::parse_from(["klik"]).target_aggression, 0.3);
+        let cli = Cli::parse_from(["klik", "--target-aggression", "0.1"]);
+        assert_eq!(cli.target_aggression, 0.1);
+        assert!(Cli::try_parse_from(["klik", "--target-aggression", "1.5"]).is_err());
+        assert!(Cli::try_parse_from(["klik", "--target-aggression", "much"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_recency_halflife() {
+        assert_eq!(Cli::parse_from(["klik"]).recency_halflife, None);
+        let cli = Cli::parse_from(["klik", "--recency-halflife", "14"]);
+        let config = RuntimeSettings::from(&cli).to_word_gen_config(None);
+        assert_eq!(config.recency_halflife, Some(14.0));
+        for bad in ["0", "-3", "inf", "soon"] {
+            assert!(
+                Cli::try_parse_from(["klik", "--recency-halflife", bad]).is_err(),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_session_context_from_settings() {
+        let settings = RuntimeSettings::from(&Cli::parse_from([
+            "klik",
+            "-l",
+            "spanish",
+            "--symbols",
+            "--strict",
+        ]));
+        assert_eq!(
+            settings.session_context(),
+            SessionContext {
+                language: "spanish".to_string(),
+                flags: SessionContext::SYMBOLS | SessionContext::STRICT,
+            }
+        );
+        assert_eq!(
+            RuntimeSettings::from(&default_cli())
+                .session_context()
+                .flags,
+            0
+        );
+    }
+
+    #[test]
+    fn test_cli_target() {
+        assert_eq!(default_cli().target, SelectionTarget::Combined);
+        let app = App::new(Cli::parse_from(["klik", "--target", "speed", "-p", "x"]));
+        let selection = app.runtime_settings.to_word_gen_config(None).selection;
+        assert_eq!(selection.miss_weight, 0.0);
+        assert!(Cli::try_parse_from(["klik", "--target", "luck"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_number_format() {
+        let format = RuntimeSettings::from(&default_cli()).number_format;
+        assert_eq!(format, NumberFormat::default());
+
+        let cli = Cli::parse_from([
+            "klik",
+            "--numbers",
+            "--number-max",
+            "9999999999",
+            "--number-groups",
+            "3,3,4",
+            "--number-separator",
+            "space",
+        ]);
+        let format = RuntimeSettings::from(&cli).number_format;
+        assert_eq!(format.range, Some(0..=9_999_999_999));
+        assert_eq!(format.groups, Some("3,3,4".parse().unwrap()));
+        assert_eq!(format.separator, NumberSeparator::Space);
+
+        assert!(Cli::try_parse_from(["klik", "--number-min", "5"]).is_err());
+        assert!(Cli::try_parse_from(["klik", "--number-groups", "3,x"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_smooth() {
+        assert_eq!(Cli::parse_from(["klik"]).smooth, None);
+        assert_eq!(Cli::parse_from(["klik", "--smooth"]).smooth, Some(3));
+        assert_eq!(Cli::parse_from(["klik", "--smooth", "5"]).smooth, Some(5));
+    }
+
+    #[test]
+    fn test_cli_stats_thresholds() {
+        let defaults = RenderSettings::from(&default_cli()).thresholds;
+        assert_eq!(defaults, StatsThresholds::default());
+
+        let cli = Cli::parse_from(["klik", "--good-ms", "300", "--slow-ms", "500"]);
+        let thresholds = RenderSettings::from(&cli).thresholds;
+        assert_eq!((thresholds.good_ms, thresholds.slow_ms), (300.0, 500.0));
+        assert!(thresholds.validate().is_ok());
+
+        let cli = Cli::parse_from(["klik", "--good-ms", "300"]);
+        assert!(RenderSettings::from(&cli).thresholds.validate().is_err());
+    }
+
     #[test]
     fn test_cli_number_of_sentences() {
         let cli = Cli::parse_from(["klik", "-f", "3"]);
@@ -473,6 +1916,9 @@ mod tests {
             cli.supported_language,
             SupportedLanguage::English10k
         ));
+
+        let cli = Cli::parse_from(["klik", "-l", "spanish"]);
+        assert!(matches!(cli.supported_language, SupportedLanguage::Spanish));
     }
 
     #[test]
@@ -485,6 +1931,14 @@ mod tests {
 
         let english10k = SupportedLanguage::English10k.as_lang();
         assert_eq!(english10k.name, "english_10k");
+
+        let spanish = SupportedLanguage::Spanish.as_lang();
+        assert_eq!(spanish.name, "spanish");
+        assert!(spanish.words.iter().any(|w| w == "camión"));
+
+        let code = SupportedLanguage::Code.as_lang();
+        assert_eq!(code.name, "code");
+        assert!(code.words.iter().any(|w| w == "=>"));
     }
 
     #[test]
@@ -492,6 +1946,8 @@ mod tests {
         assert_eq!(SupportedLanguage::English.to_string(), "English");
         assert_eq!(SupportedLanguage::English1k.to_string(), "English1k");
         assert_eq!(SupportedLanguage::English10k.to_string(), "English10k");
+        assert_eq!(SupportedLanguage::Spanish.to_string(), "Spanish");
+        assert_eq!(SupportedLanguage::Code.to_string(), "Code");
     }
 
     #[test]
@@ -507,6 +1963,7 @@ mod tests {
             strict: false,
             symbols: false,
             substitute: false,
+            ..default_cli()
         };
 
         let app = App::new(cli.clone());
@@ -531,6 +1988,7 @@ mod tests {
             strict: false,
             symbols: false,
             substitute: false,
+            ..default_cli()
         };
 
         let app = App::new(cli);
@@ -553,6 +2011,7 @@ mod tests {
             strict: false,
             symbols: false,
             substitute: false,
+            ..default_cli()
         };
 
         let app = App::new(cli);
@@ -575,6 +2034,7 @@ mod tests {
             strict: false,
             symbols: false,
             substitute: false,
+            ..default_cli()
         };
 
         let app = App::new(cli);
@@ -597,6 +2057,7 @@ mod tests {
             strict: false,
             symbols: false,
             substitute: false,
+            ..default_cli()
         };
 
         let mut app = App::new(cli);
@@ -624,6 +2085,7 @@ mod tests {
             strict: false,
             symbols: false,
             substitute: false,
+            ..default_cli()
         };
 
         let mut app = App::new(cli);
@@ -680,6 +2142,7 @@ mod tests {
             strict: false,
             symbols: false,
             substitute: false,
+            ..default_cli()
         };
 
         let mut app = App::new(cli);
@@ -709,6 +2172,432 @@ mod tests {
         assert_eq!(app.state, AppState::Results);
     }
 
+    #[test]
+    fn test_profile_names_stay_inside_the_klik_directory() {
+        let cli = Cli::parse_from(["klik", "--profile", "kid-2"]);
+        assert_eq!(cli.profile.as_deref(), Some("kid-2"));
+        assert_eq!(
+            RuntimeSettings::from(&cli).profile.as_deref(),
+            Some("kid-2")
+        );
+        assert!(default_cli().profile.is_none());
+
+        for bad in ["", "..", ".hidden", "a/b", "a\\b"] {
+            assert!(
+                Cli::try_parse_from(["klik", "--profile", bad]).is_err(),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_progression_switches_language_on_new_test() {
+        use crate::stats::StatsStore;
+
+        let store_with = |wpm| {
+            let mut store = crate::stats::InMemoryStatsStore::default();
+            for _ in 0..crate::progression::ROLLING_SESSIONS {
+                store.record_session_result(wpm, 98.0, false).unwrap();
+            }
+            Some(Box::new(store) as Box<dyn StatsStore>)
+        };
+
+        let mut app = App::new(Cli {
+            progression: true,
+            ..default_cli()
+        });
+        app.thok.stats_db = store_with(45.0);
+        app.reset(None);
+        assert_eq!(
+            app.runtime_settings.supported_language,
+            SupportedLanguage::English1k
+        );
+
+        // Without the flag the language stays put and the results point at the harder one
+        let mut app = App::new(default_cli());
+        app.thok.stats_db = store_with(70.0);
+        assert_eq!(app.unlocked_language(), Some(SupportedLanguage::English10k));
+        app.reset(None);
+        assert_eq!(
+            app.runtime_settings.supported_language,
+            SupportedLanguage::English
+        );
+    }
+
+    #[test]
+    fn test_warmup_runs_before_the_real_test() {
+        let mut app = App::new(Cli {
+            warmup: true,
+            number_of_words: 5,
+            rounds: Some(2),
+            ..default_cli()
+        });
+        assert!(app.warming_up);
+        assert_eq!(app.thok.session.prompt, WARMUP_PROMPT);
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|f| ui(&mut app, f)).unwrap();
+        let rendered: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(rendered.contains("warmup"));
+
+        // Retrying keeps the warmup going
+        app.thok.write('t');
+        app.reset(Some(app.thok.session.prompt.clone()));
+        assert!(app.warming_up);
+        assert_eq!(app.thok.session.prompt, WARMUP_PROMPT);
+
+        app.thok.stats_db = Some(Box::new(crate::stats::NoopStatsStore));
+        for c in WARMUP_PROMPT.chars() {
+            app.thok.write(c);
+        }
+        assert!(!app.complete_test(80, 24));
+
+        // No result or round is recorded, and the real prompt is up
+        assert!(!app.warming_up);
+        assert_eq!(app.state, AppState::Typing);
+        assert_ne!(app.thok.session.prompt, WARMUP_PROMPT);
+        assert!(app.last_result.is_none());
+        assert!(app.rounds.as_ref().unwrap().summaries.is_empty());
+
+        let app = App::new(Cli {
+            warmup: true,
+            prompt: Some("custom".to_string()),
+            ..default_cli()
+        });
+        assert!(!app.warming_up);
+    }
+
+    #[test]
+    fn test_pomodoro_aggregates_multiple_tests() {
+        let cli = Cli {
+            prompt: Some("ab cd".to_string()),
+            pomodoro: Some(25),
+            ..default_cli()
+        };
+        let mut app = App::new(cli);
+        assert!(app.pomodoro.is_some());
+
+        // Two fast runs inside the budget auto-advance to the next test
+        for _ in 0..2 {
+            for c in "ab cd".chars() {
+                app.thok.write(c);
+            }
+            assert!(app.complete_test(80, 24));
+            app.reset(Some(app.thok.session.prompt.clone()));
+        }
+
+        // Budget runs out: the third run lands on the summary instead
+        app.pomodoro.as_mut().unwrap().budget = Duration::ZERO;
+        for c in "ab cx".chars() {
+            app.thok.write(c);
+        }
+        assert!(!app.complete_test(80, 24));
+        assert_eq!(app.state, AppState::PomodoroSummary);
+
+        let pomodoro = app.pomodoro.as_ref().unwrap();
+        assert_eq!(pomodoro.tests_completed, 3);
+        assert_eq!(pomodoro.total_words, 6);
+        assert_eq!(pomodoro.total_keystrokes, 15);
+        assert_eq!(pomodoro.correct_keystrokes, 14);
+    }
+
+    #[test]
+    fn test_rounds_advance_then_show_aggregate() {
+        use crate::ui::screen::{current_screen, KeyAction};
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut app = App::new(Cli {
+            prompt: Some("ab cd".to_string()),
+            rounds: Some(2),
+            ..default_cli()
+        });
+        app.thok.stats_db = Some(Box::new(crate::stats::NoopStatsStore));
+        for c in "ab cd".chars() {
+            app.thok.write(c);
+        }
+        // Not the last round: straight on to the next one
+        assert!(app.complete_test(80, 24));
+        app.new_test();
+
+        app.thok.stats_db = Some(Box::new(crate::stats::NoopStatsStore));
+        for c in "ab cx".chars() {
+            app.thok.write(c);
+        }
+        assert!(!app.complete_test(80, 24));
+        assert_eq!(app.state, AppState::Aggregate);
+        let rounds = app.rounds.as_ref().unwrap();
+        assert_eq!(rounds.summaries.len(), 2);
+        assert!(rounds.best_round().is_some());
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|f| ui(&mut app, f)).unwrap();
+        let rendered: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(rendered.contains("All rounds complete"));
+        assert!(rendered.contains("median wpm"));
+        assert!(rendered.contains("round 2:"));
+
+        // `n` runs the same number of rounds again
+        let action =
+            current_screen(&app.state).on_key(KeyEvent::from(KeyCode::Char('n')), &mut app);
+        assert_eq!(action, Some(KeyAction::New));
+        assert!(app.rounds.as_ref().unwrap().summaries.is_empty());
+    }
+
+    #[test]
+    fn test_checkpoint_saved_resumed_and_cleared() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("checkpoint.json");
+        let prompt = "the quick brown fox";
+        let mut app = App::new(Cli {
+            prompt: Some(prompt.to_string()),
+            ..default_cli()
+        });
+        app.thok.stats_db = Some(Box::new(crate::stats::NoopStatsStore));
+        app.checkpoint_path = Some(path.clone());
+
+        for c in prompt.chars().take(crate::session::CHECKPOINT_INTERVAL - 1) {
+            app.thok.write(c);
+            app.checkpoint_if_due();
+        }
+        assert!(app.pending_checkpoint().is_none());
+        app.thok.write('q');
+        app.checkpoint_if_due();
+        let checkpoint = app.pending_checkpoint().expect("checkpoint written");
+        assert_eq!(checkpoint.cursor_pos, crate::session::CHECKPOINT_INTERVAL);
+
+        // A fresh start offers the checkpoint and picks up where it left off
+        let mut app = App::new(Cli {
+            prompt: Some("something else".to_string()),
+            ..default_cli()
+        });
+        app.thok.stats_db = Some(Box::new(crate::stats::NoopStatsStore));
+        app.checkpoint_path = Some(path.clone());
+        app.resume(&app.pending_checkpoint().unwrap());
+        assert_eq!(app.thok.session.prompt, prompt);
+        assert_eq!(app.thok.cursor_pos(), crate::session::CHECKPOINT_INTERVAL);
+
+        for c in prompt.chars().skip(crate::session::CHECKPOINT_INTERVAL) {
+            app.thok.write(c);
+        }
+        app.complete_test(80, 24);
+        assert!(app.pending_checkpoint().is_none());
+    }
+
+    #[test]
+    fn test_rounds_wait_holds_results_until_enter() {
+        use crate::ui::screen::{current_screen, KeyAction};
+
+        let mut app = App::new(Cli {
+            prompt: Some("ab".to_string()),
+            rounds: Some(3),
+            rounds_wait: true,
+            ..default_cli()
+        });
+        app.thok.stats_db = Some(Box::new(crate::stats::NoopStatsStore));
+        app.thok.write('a');
+        app.thok.write('b');
+        assert!(!app.complete_test(80, 24));
+        assert_eq!(app.state, AppState::Results);
+
+        let action = current_screen(&app.state).on_key(KeyEvent::from(KeyCode::Enter), &mut app);
+        assert_eq!(action, Some(KeyAction::New));
+    }
+
+    #[test]
+    fn test_enter_types_newlines_in_code_prompts() {
+        use crate::ui::screen::current_screen;
+
+        let mut app = App::new(Cli {
+            prompt: Some("{\n}".to_string()),
+            ..default_cli()
+        });
+        app.thok.stats_db = Some(Box::new(crate::stats::NoopStatsStore));
+        let mut screen = current_screen(&app.state);
+        screen.on_key(KeyEvent::from(KeyCode::Char('{')), &mut app);
+        screen.on_key(KeyEvent::from(KeyCode::Enter), &mut app);
+        screen.on_key(KeyEvent::from(KeyCode::Char('}')), &mut app);
+        assert!(app.thok.has_finished());
+        assert!(app
+            .thok
+            .input()
+            .iter()
+            .all(|input| input.outcome == crate::thok::Outcome::Correct));
+    }
+
+    #[test]
+    fn test_fill_time_extends_timed_prompt() {
+        let cli = Cli {
+            prompt: Some("ab".to_string()),
+            number_of_secs: Some(30),
+            fill_time: true,
+            ..default_cli()
+        };
+        let mut app = App::new(cli);
+        assert!(app.thok.prompt_extender.is_some());
+
+        app.thok.write('a');
+        app.thok.write('b');
+        assert!(!app.thok.has_finished());
+        assert!(app.thok.session.prompt.starts_with("ab "));
+        assert!(app.thok.session.prompt.chars().count() > 3);
+
+        // Untimed tests ignore the flag
+        let cli = Cli {
+            prompt: Some("ab".to_string()),
+            fill_time: true,
+            ..default_cli()
+        };
+        assert!(App::new(cli).thok.prompt_extender.is_none());
+    }
+
+    #[test]
+    fn test_zen_keeps_extending_until_ended() {
+        let cli = Cli {
+            prompt: Some("ab".to_string()),
+            zen: true,
+            ..default_cli()
+        };
+        let mut app = App::new(cli);
+        app.thok.stats_db = Some(Box::new(crate::stats::NoopStatsStore));
+
+        // The prompt grows as soon as the cursor nears its end
+        app.thok.write('a');
+        assert!(app.thok.session.prompt.starts_with("ab "));
+        assert!(app.thok.session.prompt_char_count() >= crate::thok::ZEN_LOOKAHEAD);
+        let typed: String = app.thok.session.prompt.chars().skip(1).take(5).collect();
+        for c in typed.chars() {
+            app.thok.write(c);
+        }
+        assert!(!app.thok.has_finished());
+
+        app.thok.session.end_zen();
+        assert!(app.thok.has_finished());
+        assert_eq!(app.thok.session.prompt_char_count(), 6);
+        app.complete_test(80, 24);
+        assert_eq!(app.state, AppState::Results);
+        assert_eq!(app.thok.accuracy(), 100.0);
+    }
+
+    #[test]
+    fn test_bell_only_wired_up_in_strict_mode() {
+        let cli = Cli {
+            prompt: Some("ab".to_string()),
+            bell: true,
+            strict: true,
+            ..default_cli()
+        };
+        assert!(App::new(cli).thok.bell.is_some());
+
+        let cli = Cli {
+            prompt: Some("ab".to_string()),
+            bell: true,
+            ..default_cli()
+        };
+        assert!(App::new(cli).thok.bell.is_none());
+
+        let cli = Cli {
+            prompt: Some("ab".to_string()),
+            strict: true,
+            ..default_cli()
+        };
+        assert!(App::new(cli).thok.bell.is_none());
+    }
+
+    #[test]
+    fn test_quote_mode_keeps_attribution_on_retry() {
+        let cli = Cli {
+            quote: true,
+            ..default_cli()
+        };
+        let mut app = App::new(cli);
+        let quote = app
+            .quote
+            .clone()
+            .expect("quote mode should attach an attribution");
+        assert_eq!(app.thok.session.prompt, quote.text);
+
+        app.reset(Some(app.thok.session.prompt.clone()));
+        assert_eq!(app.quote, Some(quote));
+    }
+
+    #[test]
+    fn test_run_headless_reports_piped_text() {
+        let cli = Cli {
+            prompt: Some("ab cd".to_string()),
+            json: true,
+            ..default_cli()
+        };
+        let mut app = App::new(cli);
+        app.thok.stats_db = Some(Box::new(crate::stats::NoopStatsStore));
+
+        let result = run_headless(&mut app, "ab\r\ncx\n").expect("prompt fully typed");
+        assert_eq!(result.accuracy, 80.0);
+
+        let report = app.last_report.as_ref().unwrap();
+        assert_eq!(report.prompt, "ab cd");
+        assert!(report.to_json().contains("\"accuracy\":80.0"));
+        let keylog = app.last_keylog.as_ref().unwrap();
+        assert_eq!(keylog.keystrokes.len(), 5);
+        assert_eq!(keylog.keystrokes[4].expected, 'd');
+        assert_eq!(keylog.keystrokes[4].outcome, "incorrect");
+
+        let mut app = App::new(Cli {
+            prompt: Some("ab cd".to_string()),
+            ..default_cli()
+        });
+        assert!(run_headless(&mut app, "ab").is_none());
+    }
+
+    #[test]
+    fn test_run_headless_leaves_stats_and_log_untouched() {
+        let mut app = App::new(Cli {
+            prompt: Some("hello world".to_string()),
+            json: true,
+            ..default_cli()
+        });
+        app.thok.stats_db = Some(Box::new(crate::stats::InMemoryStatsStore::default()));
+        let profile = format!("headless-test-{}", std::process::id());
+        app.thok.profile = Some(profile.clone());
+
+        let result = run_headless(&mut app, "hello world\n").expect("prompt fully typed");
+        // 11 keys 200 ms apart, not as fast as the pipe delivered them: ceil(11 / 5 * 30) wpm
+        assert_eq!(result.wpm, 66.0);
+        assert_eq!(result.accuracy, 100.0);
+
+        let store = app.thok.stats_db.as_ref().unwrap();
+        assert_eq!(store.get_best_wpm().unwrap(), None);
+        assert!(store.get_all_char_summary().unwrap().is_empty());
+        let log = crate::app_dirs::AppDirs::log_path(Some(&profile)).unwrap();
+        assert!(!log.exists());
+    }
+
+    #[test]
+    fn test_confirm() {
+        let answer = |reply: &str| {
+            let mut out = Vec::new();
+            let ok = confirm("Delete 7 rows?", &mut reply.as_bytes(), &mut out).unwrap();
+            assert_eq!(String::from_utf8(out).unwrap(), "Delete 7 rows? [y/N] ");
+            ok
+        };
+        assert!(answer("y\n"));
+        assert!(answer(" YES \n"));
+        assert!(!answer("\n"));
+        assert!(!answer("n\n"));
+        assert!(!answer(""));
+    }
+
     #[test]
     fn test_app_state_clone() {
         let state1 = AppState::Typing;
@@ -735,6 +2624,7 @@ mod tests {
             strict: false,
             symbols: false,
             substitute: true,
+            ..default_cli()
         };
         let app_substitute = App::new(cli_substitute_only);
         // Should generate substituted words without extra formatting
@@ -752,6 +2642,7 @@ mod tests {
             strict: false,
             symbols: false,
             substitute: false,
+            ..default_cli()
         };
         let app_capitalize = App::new(cli_capitalize_only);
         // Should have capitalization
@@ -776,6 +2667,7 @@ mod tests {
             strict: false,
             symbols: true,
             substitute: false,
+            ..default_cli()
         };
         let app_symbols = App::new(cli_symbols_only);
         // Should have symbols available (end punctuation at minimum)
@@ -800,6 +2692,7 @@ mod tests {
             strict: false,
             symbols: true,
             substitute: true,
+            ..default_cli()
         };
         let app_all = App::new(cli_all);
         // Should have all features
@@ -821,6 +2714,7 @@ mod tests {
             strict: false,
             symbols: true,
             substitute: false,
+            ..default_cli()
         };
 
         let runtime_settings = RuntimeSettings::from(&cli);
@@ -849,6 +2743,7 @@ mod tests {
             strict: false,
             symbols: false,
             substitute: false,
+            ..default_cli()
         };
 
         let runtime_settings = RuntimeSettings::from(&cli);
@@ -877,6 +2772,7 @@ mod tests {
         let _time_sort = SortBy::AvgTime;
         let _miss_sort = SortBy::MissRate;
         let _attempts_sort = SortBy::Attempts;
+        let _difficulty_sort = SortBy::Difficulty;
     }
 
     #[test]
@@ -904,10 +2800,16 @@ mod tests {
             strict: true,
             symbols: false,
             substitute: false,
+            ..default_cli()
         };
 
         let app = App::new(cli_strict);
-        assert!(app.thok.session.config.strict);
+        assert!(app.thok.session.config.is_strict());
+
+        let cli = Cli::parse_from(["klik", "-p", "test", "--policy", "lenient"]);
+        let app = App::new(cli);
+        assert_eq!(app.thok.session.config.policy, TypingPolicy::Lenient);
+        assert!(Cli::try_parse_from(["klik", "--policy", "picky"]).is_err());
     }
 
     #[test]
@@ -951,6 +2853,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_start_tui_drives_scripted_session_to_results() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut app = App::new(Cli {
+            prompt: Some("hi".to_string()),
+            ..default_cli()
+        });
+        let key = |code| ThokEvent::Key(KeyEvent::from(code));
+        let events = TestEventSource::scripted([
+            ThokEvent::Tick,
+            key(KeyCode::Char('h')),
+            ThokEvent::Tick,
+            key(KeyCode::Char('i')),
+            ThokEvent::Resize,
+            key(KeyCode::Esc),
+        ]);
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+
+        let result = start_tui(&mut terminal, &mut app, events).unwrap();
+
+        assert_eq!(app.state, AppState::Results);
+        assert!(app.thok.has_finished());
+        assert_eq!(result.unwrap().accuracy, 100.0);
+        assert!(app.last_report.is_some());
+    }
+
     #[test]
     fn test_ui_function_typing_state() {
         use ratatui::{backend::TestBackend, Terminal};
@@ -966,6 +2895,7 @@ mod tests {
             strict: false,
             symbols: false,
             substitute: false,
+            ..default_cli()
         };
 
         let mut app = App::new(cli);
@@ -998,6 +2928,7 @@ mod tests {
             strict: false,
             symbols: false,
             substitute: false,
+            ..default_cli()
         };
 
         let mut app = App::new(cli);
@@ -1016,7 +2947,15 @@ mod tests {
         // Test that ui function renders without panicking
         terminal.draw(|f| ui(&mut app, f)).unwrap();
 
-        // The results screen should render successfully
+        // The results screen should render successfully, with the rhythm panel beside the chart
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(content.contains("gap ms"));
     }
 
     #[test]
@@ -1034,6 +2973,7 @@ mod tests {
             strict: false,
             symbols: false,
             substitute: false,
+            ..default_cli()
         };
 
         let mut app = App::new(cli);
@@ -1048,6 +2988,319 @@ mod tests {
         // The character stats screen should render successfully
     }
 
+    #[test]
+    fn test_min_accuracy_schedules_retry_and_counts_attempts() {
+        let clock = crate::clock::MockClock::default();
+        let mut app = App::new(Cli {
+            prompt: Some("ab".to_string()),
+            min_accuracy: Some(100.0),
+            ..default_cli()
+        });
+        app.thok.stats_db = Some(Box::new(crate::stats::NoopStatsStore));
+        app.thok.session.clock = std::sync::Arc::new(clock.clone());
+        app.thok.write('x');
+        app.thok.write('b');
+        app.complete_test(80, 24);
+
+        assert!(app.below_min_accuracy());
+        assert_eq!(app.attempt, 1);
+        assert!(!app.auto_retry_due());
+        clock.advance(AUTO_RETRY_DELAY);
+        assert!(app.auto_retry_due());
+
+        // The loop restarts with the same prompt
+        app.reset(Some(app.thok.session.prompt.clone()));
+        assert_eq!(app.attempt, 2);
+        assert_eq!(app.thok.session.prompt, "ab");
+        assert!(app.auto_retry_at.is_none());
+
+        app.thok.write('a');
+        app.thok.write('b');
+        app.complete_test(80, 24);
+        assert!(!app.below_min_accuracy());
+        assert!(app.auto_retry_at.is_none());
+
+        // A new prompt starts counting again
+        app.reset(None);
+        assert_eq!(app.attempt, 1);
+    }
+
+    #[test]
+    fn test_auto_advance_flashes_results_then_starts_new_prompt() {
+        let clock = crate::clock::MockClock::default();
+        let mut app = App::new(Cli::parse_from([
+            "klik",
+            "-p",
+            "ab",
+            "--auto-advance",
+            "500",
+        ]));
+        let finish = |app: &mut App, typed: &str| {
+            app.thok.stats_db = Some(Box::new(crate::stats::NoopStatsStore));
+            app.thok.session.clock = std::sync::Arc::new(clock.clone());
+            for c in typed.chars() {
+                app.thok.write(c);
+            }
+            app.complete_test(80, 24);
+        };
+
+        finish(&mut app, "xb");
+        assert_eq!(app.state, AppState::Results);
+        assert!(!app.auto_advance_due());
+        clock.advance(Duration::from_millis(500));
+        assert!(app.auto_advance_due());
+        app.new_test();
+        assert!(app.auto_advance_at.is_none());
+
+        // A perfect test celebrates before moving on
+        let prompt = app.thok.session.prompt.clone();
+        finish(&mut app, &prompt);
+        clock.advance(Duration::from_millis(500));
+        assert!(app.thok.celebration.is_active);
+        assert!(!app.auto_advance_due());
+        app.thok.celebration.is_active = false;
+        assert!(app.auto_advance_due());
+
+        assert_eq!(
+            app.auto_advance_summary().unwrap(),
+            format!(
+                "2 tests: avg {:.0} wpm, best {:.0} wpm, avg 75% acc",
+                (app.auto_advanced[0].wpm + app.auto_advanced[1].wpm) / 2.0,
+                app.auto_advanced[0].wpm.max(app.auto_advanced[1].wpm)
+            )
+        );
+        assert_eq!(
+            Cli::parse_from(["klik", "--auto-advance"]).auto_advance,
+            Some(800)
+        );
+        assert!(App::new(default_cli()).auto_advance_summary().is_none());
+    }
+
+    #[test]
+    fn test_repeat_keeps_prompt_and_tallies_runs() {
+        let mut app = App::new(Cli {
+            number_of_words: 3,
+            repeat: true,
+            ..default_cli()
+        });
+        let prompt = app.thok.session.prompt.clone();
+
+        for run in 1..=3 {
+            app.thok.stats_db = Some(Box::new(crate::stats::NoopStatsStore));
+            for c in prompt.chars() {
+                app.thok.write(c);
+            }
+            app.complete_test(80, 24);
+            assert_eq!(app.repeat_wpms.len(), run);
+            assert_eq!(app.repeat_summary().is_some(), run > 1);
+            app.new_test();
+            assert_eq!(app.thok.session.prompt, prompt);
+        }
+        assert_eq!(app.attempt, 4);
+        assert!(app
+            .repeat_summary()
+            .is_some_and(|s| s.starts_with("3 repeats: avg")));
+    }
+
+    #[test]
+    fn test_replay_plays_back_recorded_keystrokes() {
+        use crate::ui::screen::{current_screen, KeyAction};
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let clock = crate::clock::MockClock::default();
+        let mut app = App::new(Cli {
+            prompt: Some("abc".to_string()),
+            ..default_cli()
+        });
+        app.thok.stats_db = Some(Box::new(crate::stats::NoopStatsStore));
+        app.thok.session.clock = std::sync::Arc::new(clock.clone());
+        for (c, gap_ms) in [('a', 0), ('x', 300), ('c', 600)] {
+            clock.advance(Duration::from_millis(gap_ms));
+            app.thok.write(c);
+        }
+        app.complete_test(80, 24);
+
+        let key = KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE);
+        let action = current_screen(&app.state).on_key(key, &mut app);
+        assert_eq!(action, Some(KeyAction::Continue));
+        assert_eq!(app.state, AppState::Replay);
+        assert_eq!(app.replay.as_ref().unwrap().cursor(), 1);
+
+        clock.advance(Duration::from_millis(300));
+        let now = app.thok.session.now();
+        app.replay.as_mut().unwrap().advance(now);
+        let replay = app.replay.as_ref().unwrap();
+        assert_eq!(replay.cursor(), 2);
+        assert_eq!(replay.visible()[1].outcome, crate::thok::Outcome::Incorrect);
+
+        let backend = TestBackend::new(100, 30);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal.draw(|f| ui(&mut app, f)).unwrap();
+        let rendered: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(rendered.contains("Replay 1.00x (playing)"));
+
+        let key = KeyEvent::new(KeyCode::Char('b'), KeyModifiers::NONE);
+        current_screen(&app.state).on_key(key, &mut app);
+        assert_eq!(app.state, AppState::Results);
+        assert!(app.replay.is_none());
+    }
+
+    #[test]
+    fn test_ui_function_heatmap_state() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut app = App::new(Cli {
+            prompt: Some("test".to_string()),
+            ..default_cli()
+        });
+        app.state = AppState::Heatmap;
+
+        let backend = TestBackend::new(100, 30);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal.draw(|f| ui(&mut app, f)).unwrap();
+
+        let rendered: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(rendered.contains("Keyboard Heatmap (Avg Time)"));
+        assert!(rendered.contains("space"));
+
+        app.heatmap_metric = app.heatmap_metric.toggle();
+        terminal.draw(|f| ui(&mut app, f)).unwrap();
+        let rendered: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(rendered.contains("Keyboard Heatmap (Miss Rate)"));
+    }
+
+    #[test]
+    fn test_finger_stats_screen() {
+        use crate::stats::{CharStat, InMemoryStatsStore, StatsStore};
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut app = App::new(Cli {
+            prompt: Some("test".to_string()),
+            ..default_cli()
+        });
+        let mut store = InMemoryStatsStore::default();
+        store
+            .record_char_stats_batch(&[CharStat {
+                character: 'j',
+                time_to_press_ms: 120,
+                was_correct: true,
+                was_uppercase: false,
+                was_correction: false,
+                timestamp: chrono::Local::now(),
+                context_before: String::new(),
+                context_after: String::new(),
+            }])
+            .unwrap();
+        app.thok.stats_db = Some(Box::new(store));
+        app.state = AppState::CharacterStats;
+
+        current_screen(&app.state).on_key(KeyEvent::from(KeyCode::Char('f')), &mut app);
+        assert_eq!(app.state, AppState::FingerStats);
+
+        let backend = TestBackend::new(100, 30);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal.draw(|f| ui(&mut app, f)).unwrap();
+        let rendered: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(rendered.contains("Finger Statistics (qwerty)"));
+        assert!(rendered.contains("right index"));
+        assert!(rendered.contains("120.0"));
+
+        current_screen(&app.state).on_key(KeyEvent::from(KeyCode::Char('b')), &mut app);
+        assert_eq!(app.state, AppState::CharacterStats);
+    }
+
+    #[test]
+    fn test_ui_function_history_state() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let cli = Cli {
+            number_of_words: 3,
+            number_of_sentences: None,
+            number_of_secs: None,
+            prompt: Some("test".to_string()),
+            supported_language: SupportedLanguage::English,
+            random_words: false,
+            capitalize: false,
+            strict: false,
+            symbols: false,
+            substitute: false,
+            ..default_cli()
+        };
+
+        let mut app = App::new(cli);
+        app.history = crate::history::parse_history(
+            "date,wpm,accuracy,std_dev
+a,50,95,3.0
+b,55,97,2.0
+"
+            .as_bytes(),
+        );
+        app.state = AppState::History;
+
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal.draw(|f| ui(&mut app, f)).unwrap();
+
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(content.contains("Consistency"));
+        assert!(content.contains("WPM over time"));
+    }
+
+    #[test]
+    fn test_ui_function_history_state_without_log() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut app = App::new(Cli {
+            prompt: Some("test".to_string()),
+            ..default_cli()
+        });
+        app.history = Vec::new();
+        app.state = AppState::History;
+
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal.draw(|f| ui(&mut app, f)).unwrap();
+
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(content.contains("No history yet"));
+    }
+
     #[test]
     fn test_render_character_stats_with_data() {
         use ratatui::{backend::TestBackend, Terminal};
@@ -1063,6 +3316,7 @@ mod tests {
             strict: false,
             symbols: false,
             substitute: false,
+            ..default_cli()
         };
 
         let mut app = App::new(cli);
@@ -1101,6 +3355,7 @@ mod tests {
             strict: false,
             symbols: false,
             substitute: false,
+            ..default_cli()
         };
 
         let mut app = App::new(cli);
@@ -1115,6 +3370,89 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_render_char_history() {
+        use crate::ui::character_stats::render_char_history;
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let draw = |history: &[(String, f64)]| {
+            let mut terminal = Terminal::new(TestBackend::new(80, 5)).unwrap();
+            terminal
+                .draw(|f| {
+                    render_char_history(f, f.area(), 'e', history, &crate::theme::Theme::default())
+                })
+                .unwrap();
+            terminal
+                .backend()
+                .buffer()
+                .content
+                .iter()
+                .map(|c| c.symbol())
+                .collect::<String>()
+        };
+        let day = |d: &str, avg| (d.to_string(), avg);
+
+        assert!(draw(&[]).contains("No past sessions for 'e' yet"));
+        assert!(draw(&[day("2026-10-01", 180.0)]).contains("Only one session so far"));
+
+        let trend = draw(&[
+            day("2026-10-01", 200.0),
+            day("2026-10-02", 150.0),
+            day("2026-10-03", 120.0),
+        ]);
+        assert!(trend.contains("3 sessions: 200ms (2026-10-01) → 120ms (2026-10-03)"));
+        assert!(trend.contains('█'));
+    }
+
+    #[test]
+    fn test_character_stats_selection_keys() {
+        use crate::ui::screen::current_screen;
+
+        let mut app = App::new(Cli {
+            prompt: Some("test".to_string()),
+            ..default_cli()
+        });
+        app.state = AppState::CharacterStats;
+        let mut screen = current_screen(&app.state);
+
+        screen.on_key(KeyEvent::from(KeyCode::Up), &mut app);
+        assert_eq!(app.char_stats_state.selected, 0);
+        screen.on_key(KeyEvent::from(KeyCode::Down), &mut app);
+        screen.on_key(KeyEvent::from(KeyCode::PageDown), &mut app);
+        assert_eq!(app.char_stats_state.selected, 11);
+        screen.on_key(KeyEvent::from(KeyCode::Up), &mut app);
+        assert_eq!(app.char_stats_state.selected, 10);
+        screen.on_key(KeyEvent::from(KeyCode::Char('2')), &mut app);
+        assert_eq!(app.char_stats_state.selected, 0);
+    }
+
+    #[test]
+    fn test_results_key_cycles_typing_policy() {
+        use crate::ui::screen::current_screen;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = App::new(Cli {
+            prompt: Some("test".to_string()),
+            policy: TypingPolicy::Lenient,
+            ..default_cli()
+        });
+        app.config_store = Box::new(crate::config::FileConfigStore::with_path(
+            dir.path().join("config.toml"),
+        ));
+        app.state = AppState::Results;
+        let mut screen = current_screen(&app.state);
+
+        for expected in [
+            TypingPolicy::Normal,
+            TypingPolicy::Strict,
+            TypingPolicy::Lenient,
+        ] {
+            screen.on_key(KeyEvent::from(KeyCode::Char('3')), &mut app);
+            assert_eq!(app.runtime_settings.policy, expected);
+            assert_eq!(app.config_store.load().policy, expected.to_string());
+        }
+    }
+
     #[test]
     fn test_character_stats_scrolling() {
         let cli = Cli {
@@ -1128,6 +3466,7 @@ mod tests {
             strict: false,
             symbols: false,
             substitute: false,
+            ..default_cli()
         };
 
         let mut app = App::new(cli);
@@ -1165,6 +3504,7 @@ mod tests {
             strict: false,
             symbols: false,
             substitute: false,
+            ..default_cli()
         };
 
         let mut app = App::new(cli);
@@ -1184,6 +3524,9 @@ mod tests {
         app.char_stats_state.sort_by = SortBy::Attempts;
         assert!(matches!(app.char_stats_state.sort_by, SortBy::Attempts));
 
+        app.char_stats_state.sort_by = SortBy::Difficulty;
+        assert!(matches!(app.char_stats_state.sort_by, SortBy::Difficulty));
+
         // Test toggling sort direction
         app.char_stats_state.sort_ascending = !app.char_stats_state.sort_ascending;
         assert!(!app.char_stats_state.sort_ascending);
@@ -1212,6 +3555,16 @@ mod tests {
         const _: () = assert!(TICK_RATE_MS <= 1000); // Should be sub-second
     }
 
+    #[test]
+    fn test_cli_tick_ms() {
+        assert_eq!(default_cli().tick_ms, TICK_RATE_MS);
+        assert!(Cli::try_parse_from(["klik", "--tick-ms", "5"]).is_err());
+
+        let app = App::new(Cli::parse_from(["klik", "--tick-ms", "16"]));
+        assert_eq!(app.runtime_settings.tick_ms, 16);
+        assert_eq!(app.thok.session.config.tick_ms, 16);
+    }
+
     #[test]
     fn test_integration_complete_typing_session() {
         // Integration test for a complete typing session workflow
@@ -1226,6 +3579,7 @@ mod tests {
             strict: false,
             symbols: false,
             substitute: false,
+            ..default_cli()
         };
 
         let mut app = App::new(cli);
@@ -1279,6 +3633,7 @@ mod tests {
             strict: false,
             symbols: false,
             substitute: false,
+            ..default_cli()
         };
 
         let mut app = App::new(cli);
@@ -1316,12 +3671,13 @@ mod tests {
             strict: true,
             symbols: false,
             substitute: false,
+            ..default_cli()
         };
 
         let mut app = App::new(cli);
 
         // Verify strict mode is enabled
-        assert!(app.thok.session.config.strict);
+        assert!(app.thok.session.config.is_strict());
 
         // Start typing
         app.thok.on_keypress_start();
@@ -1349,6 +3705,7 @@ mod tests {
             strict: false,
             symbols: false,
             substitute: true,
+            ..default_cli()
         };
 
         let app = App::new(cli);
@@ -1373,6 +3730,7 @@ mod tests {
             strict: true,
             symbols: true,
             substitute: false,
+            ..default_cli()
         };
 
         let mut app = App::new(cli.clone());
@@ -1380,7 +3738,7 @@ mod tests {
         // Verify initial settings
         assert_eq!(app.thok.session.config.number_of_words, 25);
         assert_eq!(app.thok.session.config.number_of_secs, Some(60.0));
-        assert!(app.thok.session.config.strict);
+        assert!(app.thok.session.config.is_strict());
 
         // Type something to change state
         app.thok.write('t');
@@ -1392,7 +3750,7 @@ mod tests {
         // Verify settings are preserved after reset
         assert_eq!(app.thok.session.config.number_of_words, 25);
         assert_eq!(app.thok.session.config.number_of_secs, Some(60.0));
-        assert!(app.thok.session.config.strict);
+        assert!(app.thok.session.config.is_strict());
         assert_eq!(app.thok.session.state.input.len(), 0); // But input is cleared
         assert_eq!(app.thok.session.state.cursor_pos, 0); // And cursor is reset
         assert_eq!(app.state, AppState::Typing); // And state is reset
@@ -1405,6 +3763,8 @@ mod tests {
             SupportedLanguage::English,
             SupportedLanguage::English1k,
             SupportedLanguage::English10k,
+            SupportedLanguage::Spanish,
+            SupportedLanguage::Code,
         ];
 
         for lang in languages {
@@ -1419,6 +3779,7 @@ mod tests {
                 strict: false,
                 symbols: false,
                 substitute: false,
+                ..default_cli()
             };
 
             let app = App::new(cli);
@@ -1455,6 +3816,7 @@ mod tests {
                 strict: false,
                 symbols,
                 substitute,
+                ..default_cli()
             };
 
             let app = App::new(cli);
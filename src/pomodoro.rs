@@ -0,0 +1,115 @@
+use crate::thok::{Outcome, Thok};
+use std::time::{Duration, Instant};
+
+/// Aggregate stats for a focus-timer run of back-to-back tests
+#[derive(Debug, Clone)]
+pub struct PomodoroSession {
+    pub budget: Duration,
+    pub started_at: Instant,
+    pub tests_completed: usize,
+    pub total_words: usize,
+    pub total_keystrokes: usize,
+    pub correct_keystrokes: usize,
+    pub wpm_sum: f64,
+}
+
+impl PomodoroSession {
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            budget,
+            started_at: Instant::now(),
+            tests_completed: 0,
+            total_words: 0,
+            total_keystrokes: 0,
+            correct_keystrokes: 0,
+            wpm_sum: 0.0,
+        }
+    }
+
+    pub fn from_minutes(minutes: u64) -> Self {
+        Self::new(Duration::from_secs(minutes * 60))
+    }
+
+    /// Fold a finished test (after `calc_results`) into the running totals
+    pub fn record(&mut self, thok: &Thok) {
+        let input = thok.input();
+        let typed: String = thok.session.prompt.chars().take(input.len()).collect();
+
+        self.tests_completed += 1;
+        self.total_words += typed.split_whitespace().count();
+        self.total_keystrokes += input.len();
+        self.correct_keystrokes += input
+            .iter()
+            .filter(|i| i.outcome == Outcome::Correct)
+            .count();
+        self.wpm_sum += thok.wpm();
+    }
+
+    /// True once the wall-clock budget has been used up
+    pub fn is_over(&self) -> bool {
+        self.started_at.elapsed() >= self.budget
+    }
+
+    pub fn average_wpm(&self) -> f64 {
+        if self.tests_completed == 0 {
+            0.0
+        } else {
+            self.wpm_sum / self.tests_completed as f64
+        }
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        if self.total_keystrokes == 0 {
+            0.0
+        } else {
+            (self.correct_keystrokes as f64 / self.total_keystrokes as f64 * 100.0).round()
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::NoopStatsStore;
+
+    fn finished_thok(prompt: &str, typed: &str) -> Thok {
+        let mut thok =
+            Thok::with_stats_store(prompt.to_string(), 1, None, false, Box::new(NoopStatsStore));
+        for c in typed.chars() {
+            thok.write(c);
+        }
+        thok.session.calc_results();
+        thok
+    }
+
+    #[test]
+    fn test_record_accumulates_multiple_tests() {
+        let mut pomodoro = PomodoroSession::new(Duration::from_secs(60));
+
+        pomodoro.record(&finished_thok("hello world", "hello world"));
+        pomodoro.record(&finished_thok("one two three", "one twx three"));
+
+        assert_eq!(pomodoro.tests_completed, 2);
+        assert_eq!(pomodoro.total_words, 5);
+        assert_eq!(pomodoro.total_keystrokes, 24);
+        assert_eq!(pomodoro.correct_keystrokes, 23);
+        assert_eq!(pomodoro.accuracy(), 96.0);
+    }
+
+    #[test]
+    fn test_budget_expiry() {
+        assert!(PomodoroSession::new(Duration::ZERO).is_over());
+        assert!(!PomodoroSession::from_minutes(25).is_over());
+    }
+
+    #[test]
+    fn test_empty_session_averages() {
+        let pomodoro = PomodoroSession::from_minutes(1);
+        assert_eq!(pomodoro.average_wpm(), 0.0);
+        assert_eq!(pomodoro.accuracy(), 0.0);
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{rounds::SessionSummary, util::mean, SupportedLanguage};
+
+/// How many of the latest finished tests the rolling averages cover; fewer than this
+/// recommend nothing
+pub const ROLLING_SESSIONS: usize = 10;
+
+/// A rung of the progression ladder and the rolling averages that unlock it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tier {
+    pub language: SupportedLanguage,
+    pub min_wpm: f64,
+    pub min_accuracy: f64,
+}
+
+/// From the most common words to the widest vocabulary, easiest first
+pub const TIERS: [Tier; 3] = [
+    Tier {
+        language: SupportedLanguage::English,
+        min_wpm: 0.0,
+        min_accuracy: 0.0,
+    },
+    Tier {
+        language: SupportedLanguage::English1k,
+        min_wpm: 40.0,
+        min_accuracy: 95.0,
+    },
+    Tier {
+        language: SupportedLanguage::English10k,
+        min_wpm: 60.0,
+        min_accuracy: 97.0,
+    },
+];
+
+/// Average WPM and accuracy over the latest `ROLLING_SESSIONS` results, newest first;
+/// `None` until there are that many
+pub fn rolling_average(recent: &[SessionSummary]) -> Option<SessionSummary> {
+    if recent.len() < ROLLING_SESSIONS {
+        return None;
+    }
+    let window = &recent[..ROLLING_SESSIONS];
+    let wpms: Vec<f64> = window.iter().map(|s| s.wpm).collect();
+    let accuracies: Vec<f64> = window.iter().map(|s| s.accuracy).collect();
+    Some(SessionSummary {
+        wpm: mean(&wpms)?,
+        accuracy: mean(&accuracies)?,
+    })
+}
+
+fn tier_index(language: SupportedLanguage) -> Option<usize> {
+    TIERS.iter().position(|tier| tier.language == language)
+}
+
+/// The hardest tier the rolling averages over `recent` (newest first) unlock
+pub fn recommend(recent: &[SessionSummary]) -> SupportedLanguage {
+    let Some(average) = rolling_average(recent) else {
+        return TIERS[0].language;
+    };
+    TIERS
+        .iter()
+        .rev()
+        .find(|tier| average.wpm >= tier.min_wpm && average.accuracy >= tier.min_accuracy)
+        .map_or(TIERS[0].language, |tier| tier.language)
+}
+
+/// The tier to move up to from `current`, if the recent results unlock a harder one. Languages
+/// off the ladder are left alone, and a slump never moves back down.
+pub fn next_language(
+    current: SupportedLanguage,
+    recent: &[SessionSummary],
+) -> Option<SupportedLanguage> {
+    let current_index = tier_index(current)?;
+    let recommended = recommend(recent);
+    (tier_index(recommended)? > current_index).then_some(recommended)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sessions(wpm: f64, accuracy: f64, count: usize) -> Vec<SessionSummary> {
+        vec![SessionSummary { wpm, accuracy }; count]
+    }
+
+    #[test]
+    fn test_recommended_tier_follows_rolling_averages() {
+        assert_eq!(
+            recommend(&sessions(30.0, 98.0, 10)),
+            SupportedLanguage::English
+        );
+        assert_eq!(
+            recommend(&sessions(45.0, 96.0, 10)),
+            SupportedLanguage::English1k
+        );
+        assert_eq!(
+            recommend(&sessions(70.0, 98.0, 10)),
+            SupportedLanguage::English10k
+        );
+        // Fast but sloppy typing stays on the tier its accuracy allows
+        assert_eq!(
+            recommend(&sessions(70.0, 96.0, 10)),
+            SupportedLanguage::English1k
+        );
+        assert_eq!(
+            recommend(&sessions(70.0, 90.0, 10)),
+            SupportedLanguage::English
+        );
+        // Too few results to judge
+        assert_eq!(
+            recommend(&sessions(90.0, 100.0, 9)),
+            SupportedLanguage::English
+        );
+    }
+
+    #[test]
+    fn test_rolling_average_covers_latest_sessions_only() {
+        // Newest first: ten quick tests, then an old slow stretch that has rolled out
+        let mut recent = sessions(50.0, 96.0, ROLLING_SESSIONS);
+        recent.extend(sessions(10.0, 80.0, 20));
+        let average = rolling_average(&recent).unwrap();
+        assert_eq!((average.wpm, average.accuracy), (50.0, 96.0));
+        assert_eq!(recommend(&recent), SupportedLanguage::English1k);
+
+        // Half the window at each pace
+        let mut mixed = sessions(70.0, 99.0, 5);
+        mixed.extend(sessions(30.0, 95.0, 5));
+        assert_eq!(rolling_average(&mixed).unwrap().wpm, 50.0);
+        assert_eq!(recommend(&mixed), SupportedLanguage::English1k);
+    }
+
+    #[test]
+    fn test_next_language_only_moves_up_the_ladder() {
+        let fast = sessions(70.0, 98.0, 10);
+        let slow = sessions(20.0, 90.0, 10);
+        assert_eq!(
+            next_language(SupportedLanguage::English, &fast),
+            Some(SupportedLanguage::English10k)
+        );
+        assert_eq!(next_language(SupportedLanguage::English10k, &fast), None);
+        assert_eq!(next_language(SupportedLanguage::English1k, &slow), None);
+        assert_eq!(next_language(SupportedLanguage::Spanish, &fast), None);
+    }
+}
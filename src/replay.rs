@@ -0,0 +1,152 @@
+use crate::thok::Input;
+use std::time::{Duration, SystemTime};
+
+pub const MIN_PLAYBACK_SPEED: f64 = 0.25;
+pub const MAX_PLAYBACK_SPEED: f64 = 8.0;
+
+/// Playback of a finished test's keystrokes at their recorded pace.
+///
+/// Offsets are measured from the first keystroke, so the replay starts with the first key
+/// rather than with any wait before it.
+#[derive(Debug, Clone)]
+pub struct Replay {
+    inputs: Vec<Input>,
+    offsets: Vec<Duration>,
+    /// Recorded time already played back, scaled by the speed in effect at each tick
+    position: Duration,
+    last_tick: SystemTime,
+    speed: f64,
+    cursor: usize,
+}
+
+impl Replay {
+    pub fn new(inputs: &[Input], now: SystemTime, speed: f64) -> Self {
+        let first = inputs.first().map(|i| i.timestamp).unwrap_or(now);
+        let offsets = inputs
+            .iter()
+            .map(|i| i.timestamp.duration_since(first).unwrap_or_default())
+            .collect();
+        let mut replay = Self {
+            inputs: inputs.to_vec(),
+            offsets,
+            position: Duration::ZERO,
+            last_tick: now,
+            speed: speed.clamp(MIN_PLAYBACK_SPEED, MAX_PLAYBACK_SPEED),
+            cursor: 0,
+        };
+        replay.update_cursor();
+        replay
+    }
+
+    /// Move playback forward by the wall-clock time since the previous call
+    pub fn advance(&mut self, now: SystemTime) {
+        let elapsed = now.duration_since(self.last_tick).unwrap_or_default();
+        self.last_tick = now;
+        self.position += elapsed.mul_f64(self.speed);
+        self.update_cursor();
+    }
+
+    fn update_cursor(&mut self) {
+        self.cursor = self
+            .offsets
+            .iter()
+            .take_while(|offset| **offset <= self.position)
+            .count();
+    }
+
+    /// Play again from the first keystroke
+    pub fn restart(&mut self, now: SystemTime) {
+        self.position = Duration::ZERO;
+        self.last_tick = now;
+        self.update_cursor();
+    }
+
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    pub fn set_speed(&mut self, speed: f64) {
+        self.speed = speed.clamp(MIN_PLAYBACK_SPEED, MAX_PLAYBACK_SPEED);
+    }
+
+    /// Keystrokes played back so far
+    pub fn visible(&self) -> &[Input] {
+        &self.inputs[..self.cursor]
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.cursor == self.inputs.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::thok::Outcome;
+
+    fn inputs_at(start: SystemTime, offsets_ms: &[u64]) -> Vec<Input> {
+        offsets_ms
+            .iter()
+            .map(|ms| Input {
+                char: 'a',
+                outcome: Outcome::Correct,
+                timestamp: start + Duration::from_millis(*ms),
+                keypress_start: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_replay_follows_recorded_intervals() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+        let inputs = inputs_at(start, &[500, 700, 1500]);
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(5000);
+        let mut replay = Replay::new(&inputs, now, 1.0);
+
+        // The first key shows immediately, regardless of the wait before it
+        assert_eq!(replay.cursor(), 1);
+
+        replay.advance(now + Duration::from_millis(100));
+        assert_eq!(replay.cursor(), 1);
+        replay.advance(now + Duration::from_millis(200));
+        assert_eq!(replay.cursor(), 2);
+        replay.advance(now + Duration::from_millis(1000));
+        assert_eq!(replay.cursor(), 3);
+        assert!(replay.is_done());
+
+        replay.restart(now + Duration::from_millis(2000));
+        assert_eq!(replay.cursor(), 1);
+    }
+
+    #[test]
+    fn test_replay_speed_scales_playback() {
+        let start = SystemTime::UNIX_EPOCH;
+        let inputs = inputs_at(start, &[0, 1000, 2000]);
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(10);
+        let mut replay = Replay::new(&inputs, now, 2.0);
+
+        replay.advance(now + Duration::from_millis(500));
+        assert_eq!(replay.cursor(), 2);
+
+        // Slowing down mid-way only affects what's left
+        replay.set_speed(0.5);
+        replay.advance(now + Duration::from_millis(1500));
+        assert_eq!(replay.cursor(), 2);
+        replay.advance(now + Duration::from_millis(2500));
+        assert_eq!(replay.cursor(), 3);
+
+        replay.set_speed(100.0);
+        assert_eq!(replay.speed(), MAX_PLAYBACK_SPEED);
+    }
+
+    #[test]
+    fn test_replay_of_empty_session() {
+        let replay = Replay::new(&[], SystemTime::now(), 1.0);
+        assert!(replay.visible().is_empty());
+        assert!(replay.is_done());
+    }
+}
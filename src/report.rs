@@ -0,0 +1,187 @@
+use crate::stats::time_diff_ms;
+use crate::thok::{Outcome, Thok};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Per-character tally for a single session
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CharReport {
+    pub character: char,
+    pub attempts: usize,
+    pub misses: usize,
+}
+
+/// Machine-readable summary of a finished test, printed by `--json`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionReport {
+    pub wpm: f64,
+    pub accuracy: f64,
+    pub std_dev: f64,
+    pub elapsed_secs: f64,
+    pub prompt: String,
+    pub chars: Vec<CharReport>,
+}
+
+impl SessionReport {
+    /// Build the report for a finished test (after `calc_results`)
+    pub fn from_thok(thok: &Thok) -> Self {
+        let elapsed_secs = thok
+            .started_at()
+            .and_then(|start| thok.session.now().duration_since(start).ok())
+            .unwrap_or_default()
+            .as_secs_f64();
+
+        let mut tally: BTreeMap<char, CharReport> = BTreeMap::new();
+        for (idx, input) in thok.input().iter().enumerate() {
+            let character = thok.get_expected_char(idx);
+            let entry = tally.entry(character).or_insert(CharReport {
+                character,
+                attempts: 0,
+                misses: 0,
+            });
+            entry.attempts += 1;
+            if input.outcome == Outcome::Incorrect {
+                entry.misses += 1;
+            }
+        }
+
+        Self {
+            wpm: thok.wpm(),
+            accuracy: thok.accuracy(),
+            std_dev: thok.std_dev(),
+            elapsed_secs,
+            prompt: thok.session.prompt.clone(),
+            chars: tally.into_values().collect(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+/// One keystroke of a finished test
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KeystrokeReport {
+    pub typed: char,
+    pub expected: char,
+    pub outcome: &'static str,
+    /// Milliseconds since the test started
+    pub offset_ms: u64,
+}
+
+/// Every keystroke of a finished test in order, written by `--export-keylog`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KeyLog {
+    pub prompt: String,
+    pub keystrokes: Vec<KeystrokeReport>,
+}
+
+impl KeyLog {
+    pub fn from_thok(thok: &Thok) -> Self {
+        let started_at = thok.started_at();
+        let keystrokes = thok
+            .input()
+            .iter()
+            .enumerate()
+            .map(|(idx, input)| KeystrokeReport {
+                typed: input.char,
+                expected: thok.get_expected_char(idx),
+                outcome: match input.outcome {
+                    Outcome::Correct => "correct",
+                    Outcome::Incorrect => "incorrect",
+                },
+                offset_ms: started_at.map_or(0, |start| time_diff_ms(start, input.timestamp)),
+            })
+            .collect();
+
+        Self {
+            prompt: thok.session.prompt.clone(),
+            keystrokes,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::stats::NoopStatsStore;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_report_tallies_characters() {
+        let clock = MockClock::default();
+        let mut thok =
+            Thok::with_stats_store("aab".to_string(), 1, None, false, Box::new(NoopStatsStore));
+        thok.session.clock = Arc::new(clock.clone());
+        for c in "axb".chars() {
+            thok.write(c);
+            clock.advance(Duration::from_millis(500));
+        }
+        thok.session.calc_results();
+
+        let report = SessionReport::from_thok(&thok);
+        assert_eq!(report.prompt, "aab");
+        assert_eq!(report.elapsed_secs, 1.5);
+        assert_eq!(
+            report.chars,
+            vec![
+                CharReport {
+                    character: 'a',
+                    attempts: 2,
+                    misses: 1
+                },
+                CharReport {
+                    character: 'b',
+                    attempts: 1,
+                    misses: 0
+                },
+            ]
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(json["accuracy"], 67.0);
+        assert_eq!(json["chars"][0]["character"], "a");
+    }
+
+    #[test]
+    fn test_keylog_records_each_keystroke() {
+        let clock = MockClock::default();
+        let mut thok =
+            Thok::with_stats_store("ab".to_string(), 1, None, false, Box::new(NoopStatsStore));
+        thok.session.clock = Arc::new(clock.clone());
+        for c in "xb".chars() {
+            thok.write(c);
+            clock.advance(Duration::from_millis(250));
+        }
+
+        let keylog = KeyLog::from_thok(&thok);
+        assert_eq!(
+            keylog.keystrokes,
+            vec![
+                KeystrokeReport {
+                    typed: 'x',
+                    expected: 'a',
+                    outcome: "incorrect",
+                    offset_ms: 0
+                },
+                KeystrokeReport {
+                    typed: 'b',
+                    expected: 'b',
+                    outcome: "correct",
+                    offset_ms: 250
+                },
+            ]
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&keylog.to_json()).unwrap();
+        assert_eq!(json["prompt"], "ab");
+        assert_eq!(json["keystrokes"][1]["offset_ms"], 250);
+    }
+}
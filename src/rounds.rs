@@ -0,0 +1,143 @@
+use crate::thok::Thok;
+use crate::util::{mean, std_dev};
+
+/// The headline numbers of one finished round
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SessionSummary {
+    pub wpm: f64,
+    pub accuracy: f64,
+}
+
+/// A fixed number of consecutive tests (`--rounds`), summarized together once all are done
+#[derive(Debug, Clone)]
+pub struct Rounds {
+    pub target: usize,
+    pub summaries: Vec<SessionSummary>,
+}
+
+impl Rounds {
+    pub fn new(target: usize) -> Self {
+        Self {
+            target: target.max(1),
+            summaries: Vec::new(),
+        }
+    }
+
+    /// Keep a finished test (after `calc_results`) as the next round
+    pub fn record(&mut self, thok: &Thok) {
+        self.summaries.push(SessionSummary {
+            wpm: thok.wpm(),
+            accuracy: thok.accuracy(),
+        });
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.summaries.len() >= self.target
+    }
+
+    /// 1-based number of the round being typed, or the last one once all are done
+    pub fn current(&self) -> usize {
+        (self.summaries.len() + 1).min(self.target)
+    }
+
+    fn wpms(&self) -> Vec<f64> {
+        self.summaries.iter().map(|s| s.wpm).collect()
+    }
+
+    pub fn mean_wpm(&self) -> f64 {
+        mean(&self.wpms()).unwrap_or(0.0)
+    }
+
+    pub fn median_wpm(&self) -> f64 {
+        let mut wpms = self.wpms();
+        if wpms.is_empty() {
+            return 0.0;
+        }
+        wpms.sort_by(|a, b| a.total_cmp(b));
+        let mid = wpms.len() / 2;
+        if wpms.len().is_multiple_of(2) {
+            (wpms[mid - 1] + wpms[mid]) / 2.0
+        } else {
+            wpms[mid]
+        }
+    }
+
+    /// Spread of WPM across rounds; lower means more consistent
+    pub fn wpm_std_dev(&self) -> f64 {
+        std_dev(&self.wpms()).unwrap_or(0.0)
+    }
+
+    pub fn mean_accuracy(&self) -> f64 {
+        let accuracies: Vec<f64> = self.summaries.iter().map(|s| s.accuracy).collect();
+        mean(&accuracies).unwrap_or(0.0)
+    }
+
+    /// The fastest round and its 1-based number; the earlier one wins a tie
+    pub fn best_round(&self) -> Option<(usize, SessionSummary)> {
+        self.summaries
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by(|(_, a), (_, b)| a.wpm.total_cmp(&b.wpm))
+            .map(|(i, s)| (i + 1, *s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rounds_of(wpms: &[f64]) -> Rounds {
+        let mut rounds = Rounds::new(wpms.len());
+        rounds.summaries = wpms
+            .iter()
+            .map(|wpm| SessionSummary {
+                wpm: *wpm,
+                accuracy: 90.0 + wpm / 10.0,
+            })
+            .collect();
+        rounds
+    }
+
+    #[test]
+    fn test_aggregates_across_rounds() {
+        let rounds = rounds_of(&[40.0, 60.0, 50.0, 70.0]);
+
+        assert!(rounds.is_over());
+        assert_eq!(rounds.mean_wpm(), 55.0);
+        assert_eq!(rounds.median_wpm(), 55.0);
+        assert!((rounds.wpm_std_dev() - 11.18).abs() < 0.01);
+        assert_eq!(rounds.mean_accuracy(), 95.5);
+        assert_eq!(
+            rounds.best_round().map(|(n, s)| (n, s.wpm)),
+            Some((4, 70.0))
+        );
+
+        assert_eq!(rounds_of(&[30.0, 10.0, 20.0]).median_wpm(), 20.0);
+        assert_eq!(rounds_of(&[50.0, 50.0]).best_round().unwrap().0, 1);
+    }
+
+    #[test]
+    fn test_round_progress() {
+        let mut rounds = Rounds::new(2);
+        assert_eq!(rounds.current(), 1);
+        assert!(!rounds.is_over());
+        assert_eq!(rounds.mean_wpm(), 0.0);
+        assert_eq!(rounds.median_wpm(), 0.0);
+        assert!(rounds.best_round().is_none());
+
+        rounds.summaries.push(SessionSummary {
+            wpm: 50.0,
+            accuracy: 100.0,
+        });
+        assert_eq!(rounds.current(), 2);
+        rounds.summaries.push(SessionSummary {
+            wpm: 60.0,
+            accuracy: 100.0,
+        });
+        assert_eq!(rounds.current(), 2);
+        assert!(rounds.is_over());
+
+        assert_eq!(Rounds::new(0).target, 1);
+    }
+}
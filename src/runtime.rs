@@ -3,6 +3,11 @@ use std::time::Duration;
 
 use crossterm::event::{self, Event as CtEvent, KeyEvent};
 
+use crate::clock::MockClock;
+use crate::stats::NoopStatsStore;
+use crate::thok::Thok;
+use crate::time_series::TimeSeriesPoint;
+
 /// Unified event type consumed by the app runner
 #[derive(Clone, Debug)]
 pub enum ThokEvent {
@@ -92,6 +97,16 @@ impl TestEventSource {
     pub fn new(rx: Receiver<ThokEvent>) -> Self {
         Self { rx }
     }
+
+    /// Replay `events` in order; once they run out every step is a Tick, so a script that
+    /// should end the loop finishes with Esc
+    pub fn scripted(events: impl IntoIterator<Item = ThokEvent>) -> Self {
+        let (tx, rx) = mpsc::channel();
+        for event in events {
+            let _ = tx.send(event);
+        }
+        Self { rx }
+    }
 }
 
 impl ThokEventSource for TestEventSource {
@@ -123,6 +138,42 @@ impl<E: ThokEventSource, T: Ticker> Runner<E, T> {
     }
 }
 
+/// Results of a headless session run
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionResult {
+    pub wpm: f64,
+    pub accuracy: f64,
+    pub std_dev: f64,
+    pub wpm_coords: Vec<TimeSeriesPoint>,
+}
+
+/// Drive a full session without a terminal. Each keystroke is typed after waiting its
+/// delay on a simulated clock, so results are deterministic. Nothing is persisted.
+pub fn run_session(prompt: &str, keystrokes: &[(char, Duration)]) -> SessionResult {
+    let clock = MockClock::default();
+    let mut thok = Thok::with_stats_store(
+        prompt.to_string(),
+        prompt.split_whitespace().count(),
+        None,
+        false,
+        Box::new(NoopStatsStore),
+    );
+    thok.session.clock = std::sync::Arc::new(clock.clone());
+
+    for &(c, delay) in keystrokes {
+        clock.advance(delay);
+        thok.write(c);
+    }
+    thok.session.calc_results();
+
+    SessionResult {
+        wpm: thok.wpm(),
+        accuracy: thok.accuracy(),
+        std_dev: thok.std_dev(),
+        wpm_coords: thok.wpm_coords().to_vec(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -143,6 +194,29 @@ mod tests {
         }
     }
 
+    fn steady(text: &str, every: Duration) -> Vec<(char, Duration)> {
+        text.chars().map(|c| (c, every)).collect()
+    }
+
+    #[test]
+    fn run_session_is_deterministic() {
+        // 21 correct chars, 2s from the first key to the last => ceil(21 / 5 * 30) wpm
+        let keys = steady("aaaa aaaa aaaa aaaa a", Duration::from_millis(100));
+        let first = run_session("aaaa aaaa aaaa aaaa a", &keys);
+        let second = run_session("aaaa aaaa aaaa aaaa a", &keys);
+
+        assert_eq!(first, second);
+        assert_eq!(first.accuracy, 100.0);
+        assert_eq!(first.wpm, 126.0);
+        assert_eq!(first.wpm_coords.len(), 2);
+    }
+
+    #[test]
+    fn run_session_counts_mistakes() {
+        let result = run_session("abcd", &steady("abxd", Duration::from_millis(200)));
+        assert_eq!(result.accuracy, 75.0);
+    }
+
     #[test]
     fn step_passes_through_events() {
         let (tx, rx) = mpsc::channel();
@@ -1,15 +1,67 @@
-use crate::stats::time_diff_ms;
+use crate::clock::{Clock, SystemClock};
+use crate::stats::{time_diff_ms, WordStat};
 use crate::thok::{Input, Outcome, TICK_RATE_MS};
+use crate::typing_policy::TypingPolicy;
 use crate::util::std_dev;
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::time::SystemTime;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// Seconds without input before a running test is considered idle
+pub const DEFAULT_IDLE_TIMEOUT_SECS: f64 = 30.0;
+
+/// The same key again within this many milliseconds is taken for the keyboard's autorepeat
+/// (a held key), not a real keystroke, and kept out of the character statistics
+pub const AUTOREPEAT_THRESHOLD_MS: u64 = 10;
+
+/// A result above this WPM is taken for pasted or scripted input rather than typing
+pub const MAX_PLAUSIBLE_WPM: f64 = 250.0;
+
+/// Keystrokes closer together than this many milliseconds are too fast to be typed one by one
+pub const BURST_INTERVAL_MS: u64 = 5;
+
+/// A session whose share of keystroke gaps under [`BURST_INTERVAL_MS`] exceeds this looks pasted
+pub const MAX_BURST_SHARE: f64 = 0.5;
+
+/// Fewest keystroke gaps needed before their spacing is judged at all
+pub const MIN_BURST_SAMPLE: usize = 10;
+
+/// A trailing partial minute shorter than this many seconds is folded into the minute before
+/// it rather than scaled up to a whole minute
+pub const MIN_PARTIAL_MINUTE_SECS: f64 = 10.0;
+
+/// With `--checkpoint`, an unfinished test is saved every this many keystrokes
+pub const CHECKPOINT_INTERVAL: usize = 10;
 
 #[derive(Debug, Clone)]
 pub struct SessionConfig {
     pub number_of_words: usize,
     pub number_of_secs: Option<f64>,
-    pub strict: bool,
+    pub policy: TypingPolicy,
+    /// Idle threshold in seconds; 0 disables idle detection
+    pub idle_timeout_secs: f64,
+    /// Endless test: typing to the end of the prompt doesn't finish it, only the timer or `end_zen`
+    pub zen: bool,
+    /// Interval between `on_tick` calls; the timers count down by this much per tick
+    pub tick_ms: u64,
+    /// End the test as soon as `errors()` goes over this many
+    pub max_errors: Option<usize>,
+    /// Only errors that are still standing count towards `max_errors`, not fixed ones
+    pub forgive_corrected: bool,
+}
+
+impl SessionConfig {
+    pub fn is_strict(&self) -> bool {
+        self.policy == TypingPolicy::Strict
+    }
+
+    pub fn tick_secs(&self) -> f64 {
+        self.tick_ms as f64 / 1000_f64
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -18,17 +70,41 @@ pub struct SessionState {
     pub seconds_remaining: Option<f64>,
     pub last_activity: Option<SystemTime>,
     pub is_idle: bool,
-    pub idle_timeout_secs: f64,
+    /// When the running test went idle; the time from then until the next key isn't counted
+    pub idle_since: Option<SystemTime>,
+    /// Explicit user pause; the timer and input are frozen while set
+    pub paused: bool,
+    pub paused_at: Option<SystemTime>,
+    /// Seconds left in the pre-test countdown; input is refused until it runs out
+    pub countdown_remaining: Option<f64>,
     pub keypress_start_time: Option<SystemTime>,
     // Typing state
     pub cursor_pos: usize,
     pub input: Vec<Input>,
     pub corrected_positions: std::collections::HashSet<usize>,
+    /// Normal mode: positions whose wrong keystroke was backspaced but not yet retyped
+    pub erased_errors: std::collections::HashSet<usize>,
+    /// Correct keystrokes in a row so far, and the best run this session
+    pub current_streak: usize,
+    pub longest_streak: usize,
+    /// Keystrokes that looked like autorepeat from a held key; left out of the char stats
+    pub suspicious_inputs: usize,
+    /// Every wrong keystroke so far, including ones since fixed or backspaced over
+    pub wrong_keystrokes: usize,
     // Results
     pub wpm: f64,
+    /// Accuracy rounded to a whole percentage, for display
     pub accuracy: f64,
+    /// Accuracy without rounding, for logs and library consumers
+    pub accuracy_precise: f64,
+    /// WPM less uncorrected errors per minute
+    pub net_wpm: f64,
     pub std_dev: f64,
     pub wpm_coords: Vec<crate::time_series::TimeSeriesPoint>,
+    /// WPM within each minute of the test; the last entry covers a partial minute
+    pub per_minute_wpm: Vec<f64>,
+    /// Implausibly fast or bursty input, e.g. a paste; set by `calc_results`
+    pub suspicious: bool,
 }
 
 impl Default for SessionState {
@@ -38,15 +114,27 @@ impl Default for SessionState {
             seconds_remaining: None,
             last_activity: None,
             is_idle: false,
-            idle_timeout_secs: 30.0,
+            idle_since: None,
+            paused: false,
+            paused_at: None,
+            countdown_remaining: None,
             keypress_start_time: None,
             cursor_pos: 0,
             input: Vec::new(),
             corrected_positions: std::collections::HashSet::new(),
+            erased_errors: std::collections::HashSet::new(),
+            current_streak: 0,
+            longest_streak: 0,
+            suspicious_inputs: 0,
+            wrong_keystrokes: 0,
             wpm: 0.0,
             accuracy: 0.0,
+            accuracy_precise: 0.0,
+            net_wpm: 0.0,
             std_dev: 0.0,
             wpm_coords: Vec::new(),
+            per_minute_wpm: Vec::new(),
+            suspicious: false,
         }
     }
 }
@@ -58,6 +146,8 @@ pub struct Session {
     pub prompt: String,
     pub config: SessionConfig,
     pub state: SessionState,
+    /// Time source for all timestamps; the real clock unless replaced for headless runs
+    pub clock: Arc<dyn Clock>,
 }
 
 impl Session {
@@ -72,61 +162,99 @@ impl Session {
             config: SessionConfig {
                 number_of_words,
                 number_of_secs,
-                strict: strict_mode,
+                policy: if strict_mode {
+                    TypingPolicy::Strict
+                } else {
+                    TypingPolicy::Normal
+                },
+                idle_timeout_secs: DEFAULT_IDLE_TIMEOUT_SECS,
+                zen: false,
+                tick_ms: TICK_RATE_MS,
+                max_errors: None,
+                forgive_corrected: false,
             },
             state: SessionState {
                 seconds_remaining: number_of_secs,
                 ..Default::default()
             },
+            clock: Arc::new(SystemClock),
         }
     }
 
+    pub fn now(&self) -> SystemTime {
+        self.clock.now()
+    }
+
+    /// Seconds since the first keystroke, less any paused or idle time; 0 before it. Stands
+    /// still while the test is paused or idle.
+    pub fn elapsed_secs(&self) -> f64 {
+        let until = self
+            .state
+            .paused_at
+            .or(self.state.idle_since)
+            .unwrap_or_else(|| self.now());
+        self.state
+            .started_at
+            .and_then(|started_at| until.duration_since(started_at).ok())
+            .unwrap_or_default()
+            .as_secs_f64()
+    }
+
+    /// Clear everything typed so far, keeping the prompt, config and clock
+    pub fn restart(&mut self) {
+        self.state = SessionState {
+            seconds_remaining: self.config.number_of_secs,
+            ..Default::default()
+        };
+    }
+
     pub fn on_tick(&mut self) {
+        if let Some(remaining) = self.state.countdown_remaining {
+            let next = remaining - self.config.tick_secs();
+            self.state.countdown_remaining = (next > 0.0).then_some(next);
+            return;
+        }
+        if self.state.paused || self.state.is_idle {
+            return;
+        }
         if let Some(remaining) = self.state.seconds_remaining {
-            let next = remaining - (TICK_RATE_MS as f64 / 1000_f64);
+            let next = remaining - self.config.tick_secs();
             self.state.seconds_remaining = Some(next.max(0.0));
         }
         self.check_idle_timeout();
     }
 
     fn check_idle_timeout(&mut self) {
+        if self.config.idle_timeout_secs <= 0.0 {
+            return;
+        }
         if let Some(last_activity) = self.state.last_activity {
-            let now = SystemTime::now();
+            let now = self.now();
             if let Ok(duration) = now.duration_since(last_activity) {
                 let idle_duration = duration.as_secs_f64();
-                if idle_duration >= self.state.idle_timeout_secs && !self.state.is_idle {
+                if idle_duration >= self.config.idle_timeout_secs && !self.state.is_idle {
                     self.state.is_idle = true;
                     if self.has_started() && !self.has_finished() {
-                        if let Some(started_at) = self.state.started_at {
-                            if let Ok(elapsed) = last_activity.duration_since(started_at) {
-                                let adj = Some(now.checked_sub(elapsed).unwrap_or(now));
-                                self.state.started_at = adj;
-                            }
-                        }
+                        // The wait for the timeout wasn't typing time either
+                        self.rebase_start(last_activity, now);
+                        self.sync_seconds_remaining(now);
+                        self.state.idle_since = Some(now);
                     }
                 }
             }
         }
     }
 
-    /// Mark activity and exit idle state if necessary.
-    /// Returns true if we were exiting idle state.
+    /// Mark activity and exit idle state if necessary; the test carries on from where it
+    /// went idle. Returns true if we were exiting idle state.
     pub fn mark_activity(&mut self) -> bool {
-        let now = SystemTime::now();
+        let now = self.now();
         let was_idle = self.state.is_idle;
 
         if self.state.is_idle {
             self.state.is_idle = false;
-            if self.has_started() && !self.has_finished() {
-                if let Some(started_at) = self.state.started_at {
-                    if let Some(last_activity) = self.state.last_activity {
-                        if let Ok(elapsed_before_idle) = last_activity.duration_since(started_at) {
-                            self.state.started_at =
-                                Some(now.checked_sub(elapsed_before_idle).unwrap_or(started_at));
-                        }
-                    }
-                }
-                self.state.seconds_remaining = self.config.number_of_secs;
+            if let Some(idle_since) = self.state.idle_since.take() {
+                self.rebase_start(idle_since, now);
             }
         }
 
@@ -134,6 +262,59 @@ impl Session {
         was_idle
     }
 
+    /// Move `started_at` and every keystroke so far forward so the time between `active_until`
+    /// and `now` is not counted, in the results or anywhere keystrokes are timed against each other
+    fn rebase_start(&mut self, active_until: SystemTime, now: SystemTime) {
+        let Ok(gap) = now.duration_since(active_until) else {
+            return;
+        };
+        let shift = |time: SystemTime| time.checked_add(gap).unwrap_or(time);
+        self.state.started_at = self.state.started_at.map(shift);
+        self.state.keypress_start_time = self.state.keypress_start_time.map(shift);
+        for input in &mut self.state.input {
+            input.timestamp = shift(input.timestamp);
+            input.keypress_start = input.keypress_start.map(shift);
+        }
+    }
+
+    /// Recompute a timed test's remaining seconds from the active time since `started_at`
+    fn sync_seconds_remaining(&mut self, now: SystemTime) {
+        if let (Some(total), Some(started_at)) = (self.config.number_of_secs, self.state.started_at)
+        {
+            let elapsed = now.duration_since(started_at).unwrap_or_default();
+            self.state.seconds_remaining = Some((total - elapsed.as_secs_f64()).max(0.0));
+        }
+    }
+
+    /// Hold off input for `secs` seconds, counted down by `on_tick`
+    pub fn start_countdown(&mut self, secs: f64) {
+        self.state.countdown_remaining = (secs > 0.0).then_some(secs);
+    }
+
+    pub fn is_counting_down(&self) -> bool {
+        self.state.countdown_remaining.is_some()
+    }
+
+    /// Pause or resume a running test. Paused time is excluded from elapsed time.
+    /// Returns the new paused state.
+    pub fn toggle_pause(&mut self) -> bool {
+        if !self.has_started() || self.has_finished() {
+            return self.state.paused;
+        }
+        let now = self.now();
+        if self.state.paused {
+            if let Some(paused_at) = self.state.paused_at.take() {
+                self.rebase_start(paused_at, now);
+            }
+            self.state.paused = false;
+        } else {
+            self.state.paused = true;
+            self.state.paused_at = Some(now);
+        }
+        self.state.last_activity = Some(now);
+        self.state.paused
+    }
+
     pub fn get_expected_char(&self, idx: usize) -> char {
         self.prompt.chars().nth(idx).unwrap_or(' ')
     }
@@ -153,25 +334,63 @@ impl Session {
     pub fn backspace(&mut self) {
         let _ = self.mark_activity();
 
-        if self.config.strict {
+        let removed = if self.config.is_strict() {
             if self.state.cursor_pos > 0 {
                 self.decrement_cursor();
-                if self.state.cursor_pos < self.state.input.len() {
-                    self.state.input.remove(self.state.cursor_pos);
-                }
+                (self.state.cursor_pos < self.state.input.len())
+                    .then(|| self.state.input.remove(self.state.cursor_pos))
+            } else {
+                None
             }
         } else if self.state.cursor_pos > 0 {
-            self.state.input.remove(self.state.cursor_pos - 1);
+            let removed = self.state.input.remove(self.state.cursor_pos - 1);
             self.decrement_cursor();
+            if removed.outcome == Outcome::Incorrect {
+                self.state.erased_errors.insert(self.state.cursor_pos);
+            }
+            Some(removed)
+        } else {
+            None
+        };
+
+        // Undo the streak credit for a deleted correct key so retyping it can't count twice
+        if removed.is_some_and(|input| input.outcome == Outcome::Correct) {
+            self.state.current_streak = self.state.current_streak.saturating_sub(1);
+        }
+    }
+
+    /// Normal mode: a correct keystroke over a backspaced error marks the position corrected,
+    /// as strict mode does when an error is fixed in place
+    pub fn record_retype(&mut self, idx: usize, outcome: Outcome) {
+        if outcome == Outcome::Correct && self.state.erased_errors.remove(&idx) {
+            self.state.corrected_positions.insert(idx);
+        }
+    }
+
+    pub fn record_streak(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Correct => {
+                self.state.current_streak += 1;
+                self.state.longest_streak =
+                    self.state.longest_streak.max(self.state.current_streak);
+            }
+            Outcome::Incorrect => self.state.current_streak = 0,
         }
     }
 
     pub fn start(&mut self) {
-        self.state.started_at = Some(SystemTime::now());
+        self.state.started_at = Some(self.now());
     }
 
     pub fn on_keypress_start(&mut self) {
-        self.state.keypress_start_time = Some(SystemTime::now());
+        self.state.keypress_start_time = Some(self.now());
+    }
+
+    /// Whether `c` at `now` repeats the previous keystroke too fast for a human press
+    pub fn is_autorepeat(&self, c: char, now: SystemTime) -> bool {
+        self.state.input.last().is_some_and(|last| {
+            last.char == c && time_diff_ms(last.timestamp, now) < AUTOREPEAT_THRESHOLD_MS
+        })
     }
 
     pub fn calculate_inter_key_time(&self, now: SystemTime) -> u64 {
@@ -186,11 +405,107 @@ impl Session {
         self.state.started_at.is_some()
     }
 
+    /// Append more text to the prompt, separated by a space
+    pub fn extend_prompt(&mut self, more: &str) {
+        if more.is_empty() {
+            return;
+        }
+        if !self.prompt.is_empty() {
+            self.prompt.push(' ');
+        }
+        self.prompt.push_str(more);
+    }
+
+    /// Number of characters (not bytes) in the prompt
+    pub fn prompt_char_count(&self) -> usize {
+        self.prompt.chars().count()
+    }
+
+    /// True once the cursor sits past the last prompt character; no further input is accepted
+    pub fn is_at_end(&self) -> bool {
+        self.state.cursor_pos >= self.prompt_char_count()
+    }
+
     pub fn has_finished(&self) -> bool {
-        let prompt_chars = self.prompt.chars().count();
-        (self.state.input.len() == prompt_chars)
+        let prompt_chars = self.prompt_char_count();
+        (!self.config.zen
+            && self.state.input.len() == prompt_chars
+            && self.config.policy.accepts_completion(&self.state.input))
             || (self.state.seconds_remaining.is_some()
                 && self.state.seconds_remaining.unwrap() <= 0.0)
+            || self.has_too_many_errors()
+    }
+
+    /// Errors counted against `--max-errors`: every wrong keystroke, or with
+    /// `forgive_corrected` only the wrong keys still in the input
+    pub fn errors(&self) -> usize {
+        if self.config.forgive_corrected {
+            self.state
+                .input
+                .iter()
+                .enumerate()
+                .filter(|(i, input)| {
+                    input.outcome == Outcome::Incorrect
+                        && !self.state.corrected_positions.contains(i)
+                })
+                .count()
+        } else {
+            self.state.wrong_keystrokes
+        }
+    }
+
+    /// Whether the test was cut short by going over `max_errors`
+    pub fn has_too_many_errors(&self) -> bool {
+        self.config
+            .max_errors
+            .is_some_and(|max| self.errors() > max)
+    }
+
+    /// Stop an endless test at the cursor: the prompt is cut to what was typed, so the test
+    /// counts as finished and results cover only that
+    pub fn end_zen(&mut self) {
+        self.config.zen = false;
+        // Errors left in a lenient test can't hold it open either
+        if self.config.policy == TypingPolicy::Lenient {
+            self.config.policy = TypingPolicy::Normal;
+        }
+        self.prompt = self.prompt.chars().take(self.state.input.len()).collect();
+    }
+
+    /// Timing for each prompt word typed to its end. A word runs from the keystroke before it
+    /// (the separating space, or the test start for the first word) to its last character.
+    pub fn word_stats(&self) -> Vec<WordStat> {
+        let prompt: Vec<char> = self.prompt.chars().collect();
+        let input = &self.state.input;
+        let mut stats = Vec::new();
+        let mut start = 0;
+        while start < prompt.len() {
+            if prompt[start].is_whitespace() {
+                start += 1;
+                continue;
+            }
+            let end = prompt[start..]
+                .iter()
+                .position(|c| c.is_whitespace())
+                .map_or(prompt.len(), |offset| start + offset);
+            if end > input.len() {
+                break;
+            }
+            let began = match start {
+                0 => self.state.started_at.unwrap_or(input[0].timestamp),
+                _ => input[start - 1].timestamp,
+            };
+            stats.push(WordStat {
+                word: prompt[start..end].iter().collect(),
+                time_ms: time_diff_ms(began, input[end - 1].timestamp),
+                clean: (start..end).all(|i| {
+                    input[i].outcome == Outcome::Correct
+                        && !self.state.corrected_positions.contains(&i)
+                }),
+            });
+            start = end;
+        }
+        stats
     }
 
     /// Calculate WPM, accuracy, and standard deviation from the current input.
@@ -202,9 +517,51 @@ impl Session {
             .filter(|i| i.outcome == Outcome::Correct)
             .collect();
 
-        let started_at = self.state.started_at.unwrap_or_else(SystemTime::now);
-        let elapsed_secs = started_at.elapsed().unwrap_or_default().as_millis() as f64;
-        let whole_second_limit = elapsed_secs.floor();
+        let now = self.now();
+        let started_at = self.state.started_at.unwrap_or(now);
+        let elapsed_ms = self.elapsed_secs() * 1000.0;
+        let whole_second_limit = elapsed_ms.floor();
+
+        // Minute windows: correct characters per started minute, scaled by how much of it ran;
+        // a few trailing seconds count towards the last whole minute
+        let minutes = (elapsed_ms / 60_000.0).ceil() as usize;
+        let mut minute_counts = vec![0u32; minutes];
+        for input in &correct_chars {
+            let secs = input
+                .timestamp
+                .duration_since(started_at)
+                .unwrap_or_default()
+                .as_secs_f64();
+            let minute = ((secs / 60.0) as usize).min(minutes.saturating_sub(1));
+            if let Some(count) = minute_counts.get_mut(minute) {
+                *count += 1;
+            }
+        }
+        let mut windows: Vec<(u32, f64)> = minute_counts
+            .iter()
+            .enumerate()
+            .map(|(minute, count)| {
+                let minute_secs = (elapsed_ms / 1000.0 - minute as f64 * 60.0).min(60.0);
+                (*count, minute_secs)
+            })
+            .collect();
+        if windows.len() > 1 && windows[windows.len() - 1].1 < MIN_PARTIAL_MINUTE_SECS {
+            let (count, secs) = windows.pop().unwrap_or_default();
+            if let Some(last) = windows.last_mut() {
+                last.0 += count;
+                last.1 += secs;
+            }
+        }
+        self.state.per_minute_wpm = windows
+            .iter()
+            .map(|(count, secs)| {
+                if *secs > 0.0 {
+                    (*count as f64 / 5.0) * (60.0 / secs)
+                } else {
+                    0.0
+                }
+            })
+            .collect();
 
         let mut char_counts: HashMap<String, u32> = HashMap::new();
         for input in &correct_chars {
@@ -223,7 +580,7 @@ impl Session {
                     num_secs = num_secs.ceil();
                 }
             } else {
-                num_secs = elapsed_secs;
+                num_secs = elapsed_ms;
             }
 
             *char_counts.entry(num_secs.to_string()).or_insert(0) += 1;
@@ -261,10 +618,155 @@ impl Session {
         } else {
             self.state.wpm = 0.0;
         }
-        self.state.accuracy = if self.state.input.is_empty() {
+        self.state.accuracy_precise = if self.state.input.is_empty() {
             0.0
         } else {
-            ((correct_chars.len() as f64 / self.state.input.len() as f64) * 100.0).round()
+            (correct_chars.len() as f64 / self.state.input.len() as f64) * 100.0
+        };
+        self.state.accuracy = self.state.accuracy_precise.round();
+
+        let uncorrected_errors = self
+            .state
+            .input
+            .iter()
+            .enumerate()
+            .filter(|(i, input)| {
+                input.outcome == Outcome::Incorrect && !self.state.corrected_positions.contains(i)
+            })
+            .count();
+        let minutes = self.elapsed_secs() / 60.0;
+        self.state.net_wpm = if minutes > 0.0 {
+            (self.state.wpm - uncorrected_errors as f64 / minutes)
+                .max(0.0)
+                .round()
+        } else {
+            self.state.wpm
         };
+
+        self.state.suspicious =
+            self.state.wpm > MAX_PLAUSIBLE_WPM || self.burst_share() > MAX_BURST_SHARE;
     }
+
+    /// Share of the gaps between consecutive keystrokes shorter than [`BURST_INTERVAL_MS`];
+    /// 0 with fewer than [`MIN_BURST_SAMPLE`] gaps to judge
+    fn burst_share(&self) -> f64 {
+        let gaps: Vec<u64> = self
+            .state
+            .input
+            .windows(2)
+            .map(|pair| time_diff_ms(pair[0].timestamp, pair[1].timestamp))
+            .collect();
+        if gaps.len() < MIN_BURST_SAMPLE {
+            return 0.0;
+        }
+        let bursts = gaps.iter().filter(|gap| **gap < BURST_INTERVAL_MS).count();
+        bursts as f64 / gaps.len() as f64
+    }
+}
+
+/// One keystroke in a checkpoint, timed relative to the start of the test
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckpointInput {
+    pub char: char,
+    pub correct: bool,
+    pub offset_ms: u64,
+}
+
+/// An unfinished test saved to disk so it can be resumed after a crash.
+///
+/// Times are stored as offsets from `started_at`, so a resumed test continues from its last
+/// keystroke rather than counting the time it was not running.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub prompt: String,
+    pub number_of_words: usize,
+    pub cursor_pos: usize,
+    pub corrected_positions: Vec<usize>,
+    pub input: Vec<CheckpointInput>,
+}
+
+impl Session {
+    /// Snapshot the typing progress; `None` before the first keystroke
+    pub fn checkpoint(&self) -> Option<Checkpoint> {
+        let started_at = self.state.started_at?;
+        Some(Checkpoint {
+            prompt: self.prompt.clone(),
+            number_of_words: self.config.number_of_words,
+            cursor_pos: self.state.cursor_pos,
+            corrected_positions: self
+                .state
+                .corrected_positions
+                .iter()
+                .copied()
+                .sorted()
+                .collect(),
+            input: self
+                .state
+                .input
+                .iter()
+                .map(|i| CheckpointInput {
+                    char: i.char,
+                    correct: i.outcome == Outcome::Correct,
+                    offset_ms: time_diff_ms(started_at, i.timestamp),
+                })
+                .collect(),
+        })
+    }
+
+    /// Continue from `checkpoint` as if its last keystroke had just been typed
+    pub fn restore(&mut self, checkpoint: &Checkpoint) {
+        let now = self.now();
+        let elapsed = Duration::from_millis(checkpoint.input.last().map_or(0, |i| i.offset_ms));
+        let started_at = now - elapsed;
+
+        self.prompt = checkpoint.prompt.clone();
+        self.config.number_of_words = checkpoint.number_of_words;
+        self.state.started_at = Some(started_at);
+        self.state.last_activity = Some(now);
+        self.state.countdown_remaining = None;
+        self.state.cursor_pos = checkpoint.cursor_pos;
+        self.state.corrected_positions = checkpoint.corrected_positions.iter().copied().collect();
+        // Wrong keystrokes that were fixed without leaving a correction behind aren't saved
+        self.state.wrong_keystrokes = checkpoint.corrected_positions.len()
+            + checkpoint.input.iter().filter(|i| !i.correct).count();
+        self.state.input = checkpoint
+            .input
+            .iter()
+            .map(|i| Input {
+                char: i.char,
+                outcome: if i.correct {
+                    Outcome::Correct
+                } else {
+                    Outcome::Incorrect
+                },
+                timestamp: started_at + Duration::from_millis(i.offset_ms),
+                keypress_start: None,
+            })
+            .collect();
+        if let Some(secs) = self.config.number_of_secs {
+            self.state.seconds_remaining = Some((secs - elapsed.as_secs_f64()).max(0.0));
+        }
+    }
+}
+
+/// Write the session's progress to `path`; does nothing before the first keystroke
+pub fn save_checkpoint(session: &Session, path: &Path) -> io::Result<()> {
+    let Some(checkpoint) = session.checkpoint() else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, serde_json::to_string(&checkpoint)?)
+}
+
+/// The checkpoint at `path`, if there is a readable one
+pub fn load_checkpoint(path: &Path) -> Option<Checkpoint> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// Remove the checkpoint at `path`; a missing file is fine
+pub fn clear_checkpoint(path: &Path) {
+    let _ = std::fs::remove_file(path);
 }
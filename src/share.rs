@@ -0,0 +1,42 @@
+/// Share link opened by `t` on the results screen unless `--share-template` replaces it
+pub const DEFAULT_SHARE_TEMPLATE: &str = "https://twitter.com/intent/tweet?text={wpm}%20wpm%20%2F%20{acc}%25%20acc%20%2F%20{sd}%20sd%0A%0Ahttps%3A%2F%2Fgithub.com%2Fmartintrojer%2Fklik";
+
+/// Fill `{wpm}`, `{acc}` and `{sd}` in `template`; `None` when the template is blank, which
+/// turns sharing off. The values are plain numbers, so they need no URL encoding.
+pub fn share_url(template: &str, wpm: f64, acc: f64, sd: f64) -> Option<String> {
+    let template = template.trim();
+    if template.is_empty() {
+        return None;
+    }
+    Some(
+        template
+            .replace("{wpm}", &wpm.to_string())
+            .replace("{acc}", &acc.to_string())
+            .replace("{sd}", &format!("{sd:.2}")),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_share_url_fills_placeholders() {
+        assert_eq!(
+            share_url(DEFAULT_SHARE_TEMPLATE, 72.0, 96.0, 1.234).unwrap(),
+            "https://twitter.com/intent/tweet?text=72%20wpm%20%2F%2096%25%20acc%20%2F%201.23%20sd%0A%0Ahttps%3A%2F%2Fgithub.com%2Fmartintrojer%2Fklik"
+        );
+        assert_eq!(
+            share_url(
+                "https://example.com/?w={wpm}&a={acc}&w2={wpm}",
+                80.5,
+                100.0,
+                0.0
+            )
+            .unwrap(),
+            "https://example.com/?w=80.5&a=100&w2=80.5"
+        );
+        assert_eq!(share_url("", 72.0, 96.0, 1.0), None);
+        assert_eq!(share_url("  ", 72.0, 96.0, 1.0), None);
+    }
+}
@@ -1,11 +1,13 @@
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, NaiveDate};
 // ProjectDirs used via app_dirs; keep import minimal here
 use rusqlite::{params, Connection, OptionalExtension, Result};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 use crate::language::CharacterDifficulty;
+use crate::layout::{Finger, KeyboardLayout};
+use crate::rounds::SessionSummary;
 
 /// Character statistics with session deltas for UI display
 #[derive(Debug, Clone)]
@@ -18,8 +20,23 @@ pub struct CharSummaryWithDeltas {
     pub miss_delta: Option<f64>,
     pub session_attempts: i64,
     pub latest_datetime: Option<String>,
+    /// Percentage of the character's errors later fixed; `None` until it has been missed
+    pub correction_rate: Option<f64>,
 }
 
+impl CharSummaryWithDeltas {
+    /// The combined score the intelligent selector ranks weak characters by
+    pub fn difficulty(&self) -> f64 {
+        crate::language::practice_score(self.miss_rate, self.avg_time)
+    }
+}
+
+/// Averages of one day's finished tests: (date, avg wpm, avg accuracy)
+pub type DailySummary = (String, f64, f64);
+
+/// Attempts pooled over the characters one finger types: (finger, avg time ms, miss rate %)
+pub type FingerSummary = (Finger, f64, f64);
+
 /// Type alias for character statistics with datetime
 /// (char, avg_time, miss_rate, attempts, latest_datetime)
 pub type CharSummaryWithDateTime = (char, f64, f64, i64, Option<String>);
@@ -30,7 +47,8 @@ pub struct CharStat {
     pub character: char, // The base character (always lowercase for letters)
     pub time_to_press_ms: u64,
     pub was_correct: bool,
-    pub was_uppercase: bool, // True if the original character was uppercase
+    pub was_uppercase: bool,  // True if the original character was uppercase
+    pub was_correction: bool, // True if this correct press fixed an earlier error at its position
     pub timestamp: DateTime<Local>,
     pub context_before: String,
     pub context_after: String,
@@ -51,6 +69,69 @@ pub struct CharSessionStats {
     pub uppercase_time_ms: u64,  // Total time for correct uppercase attempts
     pub uppercase_min_time: u64, // Fastest uppercase time
     pub uppercase_max_time: u64, // Slowest uppercase time
+    pub corrected_attempts: u32, // Correct presses that fixed an earlier error
+}
+
+/// Time taken for one whole prompt word, split on whitespace so glued punctuation stays part
+/// of the word
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordStat {
+    pub word: String,
+    pub time_ms: u64,
+    /// Typed without any error, including errors corrected in strict mode
+    pub clean: bool,
+}
+
+/// A word's timing across all recorded tests
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordSummary {
+    pub word: String,
+    pub avg_time_ms: f64,
+    /// Percentage of attempts typed cleanly
+    pub clean_rate: f64,
+    pub attempts: i64,
+}
+
+/// What a test's character stats were recorded under, stored with each `char_session_stats`
+/// row so summaries can be narrowed to e.g. tests with symbols on
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionContext {
+    /// Word list the prompt came from, lowercase; empty for rows recorded before it was kept
+    pub language: String,
+    /// Bitmask of the `SessionContext::*` flag constants
+    pub flags: u32,
+}
+
+impl SessionContext {
+    pub const SYMBOLS: u32 = 1;
+    pub const CAPITALIZE: u32 = 1 << 1;
+    pub const NUMBERS: u32 = 1 << 2;
+    pub const STRICT: u32 = 1 << 3;
+}
+
+/// One `char_session_stats` row, as exported by `--export-stats`
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CharSessionRow {
+    pub id: i64,
+    pub character: String,
+    pub total_attempts: i64,
+    pub correct_attempts: i64,
+    pub total_time_ms: i64,
+    pub min_time_ms: i64,
+    pub max_time_ms: i64,
+    pub uppercase_attempts: i64,
+    pub uppercase_correct: i64,
+    pub uppercase_time_ms: i64,
+    pub uppercase_min_time: i64,
+    pub uppercase_max_time: i64,
+    #[serde(default)]
+    pub corrected_attempts: i64,
+    pub session_date: String,
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub language: String,
+    #[serde(default)]
+    pub flags: i64,
 }
 
 /// Database manager for character statistics
@@ -58,6 +139,7 @@ pub struct CharSessionStats {
 pub struct StatsDb {
     conn: Connection,
     session_buffer: HashMap<char, Vec<CharStat>>,
+    context: SessionContext,
 }
 
 /// Abstraction for character statistics persistence
@@ -65,6 +147,10 @@ pub trait StatsStore: Send + std::fmt::Debug {
     fn record_char_stat(&mut self, stat: &CharStat) -> Result<()>;
     fn record_char_stats_batch(&mut self, stats: &[CharStat]) -> Result<()>;
     fn flush(&mut self) -> Result<()>;
+    /// Drop the stats buffered since the last flush, e.g. for a test that was abandoned
+    fn discard_unflushed(&mut self) {}
+    /// Language and options the next flushed stats are recorded under
+    fn set_session_context(&mut self, _context: SessionContext) {}
 
     fn get_char_stats(&self, character: char) -> Result<Vec<CharStat>>;
     fn get_avg_time_to_press(&self, character: char) -> Result<Option<f64>>;
@@ -89,6 +175,40 @@ pub trait StatsStore: Send + std::fmt::Debug {
     ) -> Result<std::collections::HashMap<char, crate::language::CharacterDifficulty>> {
         Ok(std::collections::HashMap::new())
     }
+    fn get_weakest_characters_summary(&self, n: usize) -> Result<Vec<(char, f64)>> {
+        Ok(crate::language::rank_weakest_characters(
+            &self.get_character_difficulties()?,
+            n,
+        ))
+    }
+    fn record_session_result(
+        &mut self,
+        _wpm: f64,
+        _accuracy: f64,
+        _suspicious: bool,
+    ) -> Result<()> {
+        Ok(())
+    }
+    fn get_best_wpm(&self) -> Result<Option<f64>> {
+        Ok(None)
+    }
+    /// The latest `limit` results, newest first; see [`StatsDb::get_recent_session_results`]
+    fn get_recent_session_results(&self, _limit: usize) -> Result<Vec<SessionSummary>> {
+        Ok(Vec::new())
+    }
+    fn record_word_stats(&mut self, _stats: &[WordStat]) -> Result<()> {
+        Ok(())
+    }
+    /// Average time per session day for `character`, oldest first; see [`StatsDb::get_char_history`]
+    fn get_char_history(&self, _character: char, _limit: usize) -> Result<Vec<(String, f64)>> {
+        Ok(Vec::new())
+    }
+    fn get_slowest_words(&self, _limit: usize) -> Result<Vec<WordSummary>> {
+        Ok(vec![])
+    }
+    fn get_finger_summary(&self, _layout: KeyboardLayout) -> Result<Vec<FingerSummary>> {
+        Ok(vec![])
+    }
 }
 
 impl StatsStore for StatsDb {
@@ -101,6 +221,9 @@ impl StatsStore for StatsDb {
     fn flush(&mut self) -> Result<()> {
         StatsDb::flush(self)
     }
+    fn discard_unflushed(&mut self) {
+        self.session_buffer.clear();
+    }
 
     fn get_char_stats(&self, character: char) -> Result<Vec<CharStat>> {
         StatsDb::get_char_stats(self, character)
@@ -135,6 +258,33 @@ impl StatsStore for StatsDb {
     ) -> Result<std::collections::HashMap<char, crate::language::CharacterDifficulty>> {
         StatsDb::get_character_difficulties(self)
     }
+    fn get_finger_summary(&self, layout: KeyboardLayout) -> Result<Vec<FingerSummary>> {
+        StatsDb::get_finger_summary(self, layout)
+    }
+    fn get_weakest_characters_summary(&self, n: usize) -> Result<Vec<(char, f64)>> {
+        StatsDb::get_weakest_characters_summary(self, n)
+    }
+    fn record_session_result(&mut self, wpm: f64, accuracy: f64, suspicious: bool) -> Result<()> {
+        StatsDb::record_session_result(self, wpm, accuracy, suspicious)
+    }
+    fn get_best_wpm(&self) -> Result<Option<f64>> {
+        StatsDb::get_best_wpm(self)
+    }
+    fn get_recent_session_results(&self, limit: usize) -> Result<Vec<SessionSummary>> {
+        StatsDb::get_recent_session_results(self, limit)
+    }
+    fn record_word_stats(&mut self, stats: &[WordStat]) -> Result<()> {
+        StatsDb::record_word_stats(self, stats)
+    }
+    fn get_slowest_words(&self, limit: usize) -> Result<Vec<WordSummary>> {
+        StatsDb::get_slowest_words(self, limit)
+    }
+    fn get_char_history(&self, character: char, limit: usize) -> Result<Vec<(String, f64)>> {
+        StatsDb::get_char_history(self, character, limit)
+    }
+    fn set_session_context(&mut self, context: SessionContext) {
+        self.context = context;
+    }
 }
 
 /// No-op implementation for tests that don't care about persistence
@@ -187,11 +337,112 @@ impl StatsStore for NoopStatsStore {
     }
 }
 
+/// Days from a `session_date` to `today`. Compacted rows carry the day they were compacted,
+/// and only hold sessions already 30 days old by then; unreadable dates count as today.
+fn session_age_days(session_date: &str, today: NaiveDate) -> f64 {
+    let (date, offset) = match session_date.strip_prefix("compacted_") {
+        Some(date) => (date, 30),
+        None => (session_date, 0),
+    };
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|date| ((today - date).num_days() + offset).max(0) as f64)
+        .unwrap_or(0.0)
+}
+
+/// Assemble a character's difficulty, scoring how much worse its capitals are than the
+/// lowercase letter
+fn character_difficulty(
+    avg_time: f64,
+    miss_rate: f64,
+    total_attempts: i64,
+    uppercase_avg_time: f64,
+    uppercase_miss_rate: f64,
+    uppercase_attempts: i64,
+) -> CharacterDifficulty {
+    // Calculate uppercase penalty based on performance difference
+    let uppercase_penalty = if uppercase_attempts > 0 {
+        let time_penalty = (uppercase_avg_time - avg_time).max(0.0) / avg_time;
+        let miss_penalty = (uppercase_miss_rate - miss_rate).max(0.0) / 100.0;
+        (time_penalty + miss_penalty).min(1.0) // Cap at 1.0
+    } else {
+        0.5 // Default penalty when no uppercase data
+    };
+
+    CharacterDifficulty {
+        miss_rate,
+        avg_time_ms: avg_time,
+        total_attempts,
+        uppercase_miss_rate,
+        uppercase_avg_time,
+        uppercase_attempts,
+        uppercase_penalty,
+    }
+}
+
+/// Per character, the percentage of errors later fixed: corrected presses over missed
+/// attempts. Characters never missed are left out.
+fn correction_rates<'a>(
+    stats: impl IntoIterator<Item = &'a CharSessionStats>,
+) -> HashMap<char, f64> {
+    let mut totals: HashMap<char, (u32, u32)> = HashMap::new();
+    for stat in stats {
+        let entry = totals.entry(stat.character).or_default();
+        entry.0 += stat.total_attempts - stat.correct_attempts;
+        entry.1 += stat.corrected_attempts;
+    }
+    totals
+        .into_iter()
+        .filter(|(_, (errors, _))| *errors > 0)
+        .map(|(character, (errors, corrected))| {
+            // Capped, as an error can be fixed over more than one press in normal mode
+            let rate = (corrected as f64 * 100.0 / errors as f64).min(100.0);
+            (character, rate)
+        })
+        .collect()
+}
+
+/// Pool per-character totals `(char, time ms of correct presses, correct, attempts)` by the
+/// finger that types each character on `layout`. Fingers without attempts and characters
+/// off the main block are left out.
+fn finger_summary(
+    layout: KeyboardLayout,
+    totals: impl IntoIterator<Item = (char, u64, u64, u64)>,
+) -> Vec<FingerSummary> {
+    let mut by_finger: HashMap<Finger, (u64, u64, u64)> = HashMap::new();
+    for (character, time_ms, correct, attempts) in totals {
+        if let Some(position) = layout.position(character) {
+            let entry = by_finger.entry(position.finger).or_default();
+            entry.0 += time_ms;
+            entry.1 += correct;
+            entry.2 += attempts;
+        }
+    }
+    Finger::ALL
+        .into_iter()
+        .filter_map(|finger| {
+            let (time_ms, correct, attempts) = by_finger.get(&finger).copied()?;
+            if attempts == 0 {
+                return None;
+            }
+            let avg_time = if correct > 0 {
+                time_ms as f64 / correct as f64
+            } else {
+                0.0
+            };
+            let miss_rate = (attempts - correct) as f64 * 100.0 / attempts as f64;
+            Some((finger, avg_time, miss_rate))
+        })
+        .collect()
+}
+
 /// In-memory implementation for tests/bench without IO
 #[derive(Debug, Default)]
 pub struct InMemoryStatsStore {
     session_buffer: std::collections::HashMap<char, Vec<CharStat>>,
     historical: std::collections::HashMap<char, Vec<CharSessionStats>>,
+    /// Each result with whether it looked suspicious, kept like the `suspicious` column
+    session_results: Vec<(SessionSummary, bool)>,
+    word_stats: Vec<WordStat>,
 }
 
 impl InMemoryStatsStore {
@@ -212,6 +463,7 @@ impl InMemoryStatsStore {
                     uppercase_time_ms: 0,
                     uppercase_min_time: u64::MAX,
                     uppercase_max_time: 0,
+                    corrected_attempts: 0,
                 });
                 entry.total_attempts += stat.total_attempts;
                 entry.correct_attempts += stat.correct_attempts;
@@ -223,6 +475,7 @@ impl InMemoryStatsStore {
                 entry.uppercase_time_ms += stat.uppercase_time_ms;
                 entry.uppercase_min_time = entry.uppercase_min_time.min(stat.uppercase_min_time);
                 entry.uppercase_max_time = entry.uppercase_max_time.max(stat.uppercase_max_time);
+                entry.corrected_attempts += stat.corrected_attempts;
             }
         }
         acc.into_values().collect()
@@ -257,6 +510,10 @@ impl StatsStore for InMemoryStatsStore {
         Ok(())
     }
 
+    fn discard_unflushed(&mut self) {
+        self.session_buffer.clear();
+    }
+
     fn get_char_stats(&self, _character: char) -> Result<Vec<CharStat>> {
         Ok(vec![])
     }
@@ -349,6 +606,7 @@ impl StatsStore for InMemoryStatsStore {
                     miss_delta: Some(s_miss - h_miss),
                     session_attempts: s_attempts,
                     latest_datetime: None,
+                    correction_rate: None,
                 });
             } else {
                 combined.push(CharSummaryWithDeltas {
@@ -360,9 +618,15 @@ impl StatsStore for InMemoryStatsStore {
                     miss_delta: None,
                     session_attempts: s_attempts,
                     latest_datetime: None,
+                    correction_rate: None,
                 });
             }
         }
+        let session = StatsDb::aggregate_char_stats_from_buffer(&self.session_buffer);
+        let rates = correction_rates(self.aggregate().iter().chain(&session));
+        for summary in &mut combined {
+            summary.correction_rate = rates.get(&summary.character).copied();
+        }
         Ok(combined)
     }
 
@@ -383,14 +647,165 @@ impl StatsStore for InMemoryStatsStore {
     ) -> Result<std::collections::HashMap<char, crate::language::CharacterDifficulty>> {
         Ok(std::collections::HashMap::new())
     }
+    fn get_finger_summary(&self, layout: KeyboardLayout) -> Result<Vec<FingerSummary>> {
+        Ok(finger_summary(
+            layout,
+            self.aggregate().into_iter().map(|s| {
+                (
+                    s.character,
+                    s.total_time_ms,
+                    s.correct_attempts as u64,
+                    s.total_attempts as u64,
+                )
+            }),
+        ))
+    }
+    fn record_session_result(&mut self, wpm: f64, accuracy: f64, suspicious: bool) -> Result<()> {
+        self.session_results
+            .push((SessionSummary { wpm, accuracy }, suspicious));
+        Ok(())
+    }
+    fn get_best_wpm(&self) -> Result<Option<f64>> {
+        Ok(self
+            .session_results
+            .iter()
+            .filter(|(_, suspicious)| !suspicious)
+            .map(|(s, _)| s.wpm)
+            .reduce(f64::max))
+    }
+    fn get_recent_session_results(&self, limit: usize) -> Result<Vec<SessionSummary>> {
+        Ok(self
+            .session_results
+            .iter()
+            .rev()
+            .filter(|(_, suspicious)| !suspicious)
+            .take(limit)
+            .map(|(s, _)| *s)
+            .collect())
+    }
+    fn record_word_stats(&mut self, stats: &[WordStat]) -> Result<()> {
+        self.word_stats.extend_from_slice(stats);
+        Ok(())
+    }
+    fn get_slowest_words(&self, limit: usize) -> Result<Vec<WordSummary>> {
+        let mut by_word: std::collections::HashMap<&str, Vec<&WordStat>> =
+            std::collections::HashMap::new();
+        for stat in &self.word_stats {
+            by_word.entry(stat.word.as_str()).or_default().push(stat);
+        }
+        let mut summaries: Vec<WordSummary> = by_word
+            .into_iter()
+            .map(|(word, stats)| {
+                let attempts = stats.len() as f64;
+                WordSummary {
+                    word: word.to_string(),
+                    avg_time_ms: stats.iter().map(|s| s.time_ms as f64).sum::<f64>() / attempts,
+                    clean_rate: stats.iter().filter(|s| s.clean).count() as f64 / attempts * 100.0,
+                    attempts: stats.len() as i64,
+                }
+            })
+            .collect();
+        summaries.sort_by(|a, b| {
+            per_char_ms(b)
+                .partial_cmp(&per_char_ms(a))
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.word.cmp(&b.word))
+        });
+        summaries.truncate(limit);
+        Ok(summaries)
+    }
+}
+
+/// Average time per character, so long words don't top the slowest list just for being long
+fn per_char_ms(summary: &WordSummary) -> f64 {
+    summary.avg_time_ms / summary.word.chars().count().max(1) as f64
+}
+
+/// One row per finished test, used for personal-best tracking
+const SESSION_RESULTS_SCHEMA: &str = r#"
+    CREATE TABLE IF NOT EXISTS session_results (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        date TEXT NOT NULL,
+        wpm REAL NOT NULL,
+        accuracy REAL NOT NULL
+    )
+"#;
+
+/// Add the `language` and `flags` columns to a `char_session_stats` table created before they
+/// existed; a no-op once they are there
+fn add_session_context_columns(conn: &Connection) -> Result<()> {
+    let columns = conn
+        .prepare("SELECT name FROM pragma_table_info('char_session_stats')")?
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>>>()?;
+    if !columns.iter().any(|c| c == "language") {
+        conn.execute(
+            "ALTER TABLE char_session_stats ADD COLUMN language TEXT NOT NULL DEFAULT ''",
+            [],
+        )?;
+    }
+    if !columns.iter().any(|c| c == "flags") {
+        conn.execute(
+            "ALTER TABLE char_session_stats ADD COLUMN flags INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
+    }
+    Ok(())
 }
 
+/// Add the `suspicious` column to a `session_results` table created before it existed
+fn add_suspicious_column(conn: &Connection) -> Result<()> {
+    let has_column: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info('session_results') WHERE name = 'suspicious'",
+        [],
+        |row| row.get(0),
+    )?;
+    if !has_column {
+        conn.execute(
+            "ALTER TABLE session_results ADD COLUMN suspicious INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+/// Add the `corrected_attempts` column to a `char_session_stats` table created before it existed
+fn add_corrected_attempts_column(conn: &Connection) -> Result<()> {
+    let has_column: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info('char_session_stats') WHERE name = 'corrected_attempts'",
+        [],
+        |row| row.get(0),
+    )?;
+    if !has_column {
+        conn.execute(
+            "ALTER TABLE char_session_stats ADD COLUMN corrected_attempts INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+/// One row per completed prompt word, used to find the slowest words
+const WORD_STATS_SCHEMA: &str = r#"
+    CREATE TABLE IF NOT EXISTS word_stats (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        word TEXT NOT NULL,
+        time_ms INTEGER NOT NULL,
+        clean INTEGER NOT NULL,
+        session_date TEXT NOT NULL
+    )
+"#;
+
 impl StatsDb {
-    /// Initialize the database connection and create tables if needed
-    pub fn new() -> Result<Self> {
-        let db_path =
-            crate::app_dirs::AppDirs::db_path().unwrap_or_else(|| PathBuf::from("klik_stats.db"));
+    /// Open the database of `profile` (`None` for the default one), creating it if needed
+    pub fn new(profile: Option<&str>) -> Result<Self> {
+        let db_path = crate::app_dirs::AppDirs::db_path(profile)
+            .unwrap_or_else(|| PathBuf::from("klik_stats.db"));
+        Self::open(&db_path)
+    }
 
+    /// Initialize the database connection at `db_path` and create tables if needed
+    pub fn open(db_path: &Path) -> Result<Self> {
         // Create parent directory if it doesn't exist
         if let Some(parent) = db_path.parent() {
             std::fs::create_dir_all(parent).map_err(|e| {
@@ -401,7 +816,7 @@ impl StatsDb {
             })?;
         }
 
-        let conn = Connection::open(&db_path)?;
+        let conn = Connection::open(db_path)?;
 
         // Create the aggregated character statistics table
         conn.execute(
@@ -437,15 +852,22 @@ impl StatsDb {
             [],
         )?;
 
+        conn.execute(SESSION_RESULTS_SCHEMA, [])?;
+        add_suspicious_column(&conn)?;
+        conn.execute(WORD_STATS_SCHEMA, [])?;
+        add_session_context_columns(&conn)?;
+        add_corrected_attempts_column(&conn)?;
+
         Ok(StatsDb {
             conn,
             session_buffer: HashMap::new(),
+            context: SessionContext::default(),
         })
     }
 
-    /// Get the database file path under $HOME/.local/state/klik
-    fn get_db_path() -> Option<PathBuf> {
-        crate::app_dirs::AppDirs::db_path()
+    /// Get the database file path under $HOME/.local/state/klik, in a subdirectory for a named profile
+    fn get_db_path(profile: Option<&str>) -> Option<PathBuf> {
+        crate::app_dirs::AppDirs::db_path(profile)
     }
 
     /// Record a character statistic (buffers for session aggregation)
@@ -471,8 +893,9 @@ impl StatsDb {
                 r#"
                 INSERT INTO char_session_stats 
                 (character, total_attempts, correct_attempts, total_time_ms, min_time_ms, max_time_ms, 
-                 uppercase_attempts, uppercase_correct, uppercase_time_ms, uppercase_min_time, uppercase_max_time, session_date)
-                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+                 uppercase_attempts, uppercase_correct, uppercase_time_ms, uppercase_min_time, uppercase_max_time, session_date,
+                 language, flags, corrected_attempts)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
                 "#,
                 params![
                     stat.character.to_string(),
@@ -487,6 +910,9 @@ impl StatsDb {
                     stat.uppercase_min_time,
                     stat.uppercase_max_time,
                     session_date,
+                    self.context.language,
+                    self.context.flags,
+                    stat.corrected_attempts,
                 ],
             )?;
         }
@@ -542,6 +968,7 @@ impl StatsDb {
                 uppercase_time_ms: 0,
                 uppercase_min_time: u64::MAX,
                 uppercase_max_time: 0,
+                corrected_attempts: 0,
             };
 
             for stat in stats {
@@ -551,6 +978,10 @@ impl StatsDb {
                     char_session.uppercase_attempts += 1;
                 }
 
+                if stat.was_correction {
+                    char_session.corrected_attempts += 1;
+                }
+
                 if stat.was_correct {
                     char_session.correct_attempts += 1;
                     char_session.total_time_ms += stat.time_to_press_ms;
@@ -594,7 +1025,8 @@ impl StatsDb {
         let mut stmt = self.conn.prepare(
             r#"
             SELECT character, total_attempts, correct_attempts, total_time_ms, min_time_ms, max_time_ms,
-                   uppercase_attempts, uppercase_correct, uppercase_time_ms, uppercase_min_time, uppercase_max_time
+                   uppercase_attempts, uppercase_correct, uppercase_time_ms, uppercase_min_time, uppercase_max_time,
+                   corrected_attempts
             FROM char_session_stats 
             WHERE character = ?1
             ORDER BY session_date DESC
@@ -614,6 +1046,7 @@ impl StatsDb {
                 uppercase_time_ms: row.get(8)?,
                 uppercase_min_time: row.get(9)?,
                 uppercase_max_time: row.get(10)?,
+                corrected_attempts: row.get(11)?,
             })
         })?;
 
@@ -673,6 +1106,29 @@ impl StatsDb {
         }
     }
 
+    /// Average press time and miss rate per finger of `layout`, see [`FingerSummary`]
+    pub fn get_finger_summary(&self, layout: KeyboardLayout) -> Result<Vec<FingerSummary>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT character, SUM(total_time_ms), SUM(correct_attempts), SUM(total_attempts)
+            FROM char_session_stats
+            GROUP BY character
+            "#,
+        )?;
+        let totals = stmt
+            .query_map([], |row| {
+                let char_str: String = row.get(0)?;
+                Ok((
+                    char_str.chars().next().unwrap_or('\0'),
+                    row.get::<_, i64>(1)? as u64,
+                    row.get::<_, i64>(2)? as u64,
+                    row.get::<_, i64>(3)? as u64,
+                ))
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(finger_summary(layout, totals))
+    }
+
     /// Get all character statistics summary from aggregated session data
     pub fn get_all_char_summary(&self) -> Result<Vec<(char, f64, f64, i64)>> {
         let mut stmt = self.conn.prepare(
@@ -714,6 +1170,50 @@ impl StatsDb {
         Ok(summary)
     }
 
+    /// `get_all_char_summary` over the tests recorded in `language` (any, when `None`) with at
+    /// least the `SessionContext` bits in `flags` set
+    pub fn get_all_char_summary_filtered(
+        &self,
+        language: Option<&str>,
+        flags: u32,
+    ) -> Result<Vec<(char, f64, f64, i64)>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT 
+                character,
+                CASE 
+                    WHEN SUM(correct_attempts) > 0 THEN 
+                        CAST(SUM(total_time_ms) AS FLOAT) / SUM(correct_attempts)
+                    ELSE 0.0
+                END as avg_time,
+                CASE 
+                    WHEN SUM(total_attempts) > 0 THEN 
+                        (SUM(total_attempts - correct_attempts) * 100.0 / SUM(total_attempts))
+                    ELSE 0.0
+                END as miss_rate,
+                SUM(total_attempts) as total_attempts
+            FROM char_session_stats 
+            WHERE (?1 IS NULL OR language = ?1) AND (flags & ?2) = ?2
+            GROUP BY character
+            ORDER BY character
+            "#,
+        )?;
+
+        let summary = stmt
+            .query_map(params![language, flags], |row| {
+                let char_str: String = row.get(0)?;
+                Ok((
+                    char_str.chars().next().unwrap_or('\0'),
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(summary)
+    }
+
     /// Get all character statistics summary with latest datetime from aggregated session data
     pub fn get_all_char_summary_with_datetime(&self) -> Result<Vec<CharSummaryWithDateTime>> {
         let mut stmt = self.conn.prepare(
@@ -904,7 +1404,7 @@ impl StatsDb {
             summary.push((character, avg_time, miss_rate, total_attempts));
         }
 
-        summary.sort_by(|a, b| a.0.cmp(&b.0));
+        summary.sort_by_key(|a| a.0);
         summary
     }
 
@@ -938,7 +1438,7 @@ impl StatsDb {
             summary.push((character, avg_time, miss_rate, total_attempts, None));
         }
 
-        summary.sort_by(|a, b| a.0.cmp(&b.0));
+        summary.sort_by_key(|a| a.0);
         summary
     }
 
@@ -1121,6 +1621,7 @@ impl StatsDb {
                 miss_delta: session_miss_delta,
                 session_attempts,
                 latest_datetime,
+                correction_rate: None,
             });
         }
 
@@ -1138,27 +1639,282 @@ impl StatsDb {
                     miss_delta: None,
                     session_attempts: *session_attempts,
                     latest_datetime: session_datetime.clone(),
+                    correction_rate: None,
                 });
             }
         }
 
+        let rates = self.get_correction_rates()?;
+        for summary in &mut combined_summary {
+            summary.correction_rate = rates.get(&summary.character).copied();
+        }
+
         Ok(combined_summary)
     }
 
+    /// Per character, the percentage of its errors later fixed, across the database and the
+    /// unflushed session; characters never missed are left out
+    pub fn get_correction_rates(&self) -> Result<HashMap<char, f64>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT character, SUM(total_attempts), SUM(correct_attempts), SUM(corrected_attempts)
+            FROM char_session_stats
+            GROUP BY character
+            "#,
+        )?;
+        let mut stats = stmt
+            .query_map([], |row| {
+                Ok(CharSessionStats {
+                    character: row.get::<_, String>(0)?.chars().next().unwrap_or('\0'),
+                    total_attempts: row.get(1)?,
+                    correct_attempts: row.get(2)?,
+                    total_time_ms: 0,
+                    min_time_ms: 0,
+                    max_time_ms: 0,
+                    uppercase_attempts: 0,
+                    uppercase_correct: 0,
+                    uppercase_time_ms: 0,
+                    uppercase_min_time: 0,
+                    uppercase_max_time: 0,
+                    corrected_attempts: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        stats.extend(Self::aggregate_char_stats_from_buffer(&self.session_buffer));
+        Ok(correction_rates(&stats))
+    }
+
     /// Clear all statistics (for testing or reset purposes)
     pub fn clear_all_stats(&self) -> Result<()> {
         self.conn.execute("DELETE FROM char_session_stats", [])?;
+        self.conn.execute("DELETE FROM session_results", [])?;
+        self.conn.execute("DELETE FROM word_stats", [])?;
+        Ok(())
+    }
+
+    /// Record a finished test's headline numbers
+    pub fn record_session_result(&self, wpm: f64, accuracy: f64, suspicious: bool) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO session_results (date, wpm, accuracy, suspicious) VALUES (?1, ?2, ?3, ?4)",
+            params![Local::now().to_rfc3339(), wpm, accuracy, suspicious],
+        )?;
         Ok(())
     }
 
+    /// Highest WPM ever recorded, or `None` before the first finished test. Suspicious
+    /// results (likely pasted) don't count.
+    pub fn get_best_wpm(&self) -> Result<Option<f64>> {
+        self.conn.query_row(
+            "SELECT MAX(wpm) FROM session_results WHERE suspicious = 0",
+            [],
+            |row| row.get(0),
+        )
+    }
+
+    /// WPM and accuracy of the latest `limit` finished tests, newest first. Suspicious results
+    /// (likely pasted) are left out.
+    pub fn get_recent_session_results(&self, limit: usize) -> Result<Vec<SessionSummary>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT wpm, accuracy FROM session_results WHERE suspicious = 0 ORDER BY id DESC LIMIT ?1",
+        )?;
+        let rows = stmt.query_map([limit as i64], |row| {
+            Ok(SessionSummary {
+                wpm: row.get(0)?,
+                accuracy: row.get(1)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Average time to press `character` for each of its `limit` most recent session dates,
+    /// oldest first. Compacted rows have no real date and are left out.
+    pub fn get_char_history(&self, character: char, limit: usize) -> Result<Vec<(String, f64)>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT * FROM (
+                SELECT session_date, CAST(SUM(total_time_ms) AS REAL) / SUM(correct_attempts)
+                FROM char_session_stats
+                WHERE character = ?1
+                  AND session_date NOT LIKE 'compacted_%'
+                GROUP BY session_date
+                HAVING SUM(correct_attempts) > 0
+                ORDER BY session_date DESC
+                LIMIT ?2
+            )
+            ORDER BY session_date
+            "#,
+        )?;
+        let history = stmt
+            .query_map(params![character.to_string(), limit as i64], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(history)
+    }
+
+    /// Average WPM and accuracy per local day, for the `days` most recent days with finished
+    /// tests on or after `since`; oldest first
+    pub fn get_daily_summary(
+        &self,
+        days: usize,
+        since: Option<NaiveDate>,
+    ) -> Result<Vec<DailySummary>> {
+        // Dates are stored as local RFC 3339 timestamps, so the first ten characters are the day
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT * FROM (
+                SELECT substr(date, 1, 10) AS day, AVG(wpm), AVG(accuracy)
+                FROM session_results
+                WHERE (?1 IS NULL OR substr(date, 1, 10) >= ?1) AND suspicious = 0
+                GROUP BY day
+                ORDER BY day DESC
+                LIMIT ?2
+            )
+            ORDER BY day
+            "#,
+        )?;
+        let since = since.map(|d| d.format("%Y-%m-%d").to_string());
+        let rows = stmt.query_map(params![since, days as i64], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })?;
+        rows.collect()
+    }
+
+    /// Record the per-word timings of a finished test
+    pub fn record_word_stats(&mut self, stats: &[WordStat]) -> Result<()> {
+        let session_date = Local::now().format("%Y-%m-%d").to_string();
+        let tx = self.conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO word_stats (word, time_ms, clean, session_date) VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for stat in stats {
+                stmt.execute(params![
+                    stat.word,
+                    stat.time_ms as i64,
+                    stat.clean,
+                    session_date
+                ])?;
+            }
+        }
+        tx.commit()
+    }
+
+    /// The `limit` words with the highest average time per character, slowest first
+    pub fn get_slowest_words(&self, limit: usize) -> Result<Vec<WordSummary>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT word, AVG(time_ms), AVG(clean) * 100.0, COUNT(*)
+            FROM word_stats
+            GROUP BY word
+            ORDER BY AVG(time_ms) / LENGTH(word) DESC, word
+            LIMIT ?1
+            "#,
+        )?;
+        let rows = stmt.query_map(params![limit as i64], |row| {
+            Ok(WordSummary {
+                word: row.get(0)?,
+                avg_time_ms: row.get(1)?,
+                clean_rate: row.get(2)?,
+                attempts: row.get(3)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Serialize every `char_session_stats` row to a JSON array
+    pub fn export_json(&self) -> Result<String> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT id, character, total_attempts, correct_attempts, total_time_ms,
+                   min_time_ms, max_time_ms, uppercase_attempts, uppercase_correct,
+                   uppercase_time_ms, uppercase_min_time, uppercase_max_time,
+                   session_date, created_at, language, flags, corrected_attempts
+            FROM char_session_stats
+            ORDER BY id
+            "#,
+        )?;
+
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(CharSessionRow {
+                    id: row.get(0)?,
+                    character: row.get(1)?,
+                    total_attempts: row.get(2)?,
+                    correct_attempts: row.get(3)?,
+                    total_time_ms: row.get(4)?,
+                    min_time_ms: row.get(5)?,
+                    max_time_ms: row.get(6)?,
+                    uppercase_attempts: row.get(7)?,
+                    uppercase_correct: row.get(8)?,
+                    uppercase_time_ms: row.get(9)?,
+                    uppercase_min_time: row.get(10)?,
+                    uppercase_max_time: row.get(11)?,
+                    session_date: row.get(12)?,
+                    created_at: row.get(13)?,
+                    language: row.get(14)?,
+                    flags: row.get(15)?,
+                    corrected_attempts: row.get(16)?,
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+
+        serde_json::to_string_pretty(&rows)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+    }
+
+    /// Insert rows produced by `export_json`. Row ids are reassigned; returns the number imported.
+    pub fn import_json(&self, json: &str) -> Result<usize> {
+        let rows: Vec<CharSessionRow> = serde_json::from_str(json)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        let tx = self.conn.unchecked_transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                r#"
+                INSERT INTO char_session_stats
+                (character, total_attempts, correct_attempts, total_time_ms,
+                 min_time_ms, max_time_ms, uppercase_attempts, uppercase_correct,
+                 uppercase_time_ms, uppercase_min_time, uppercase_max_time,
+                 session_date, created_at, language, flags, corrected_attempts)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12,
+                        COALESCE(?13, CURRENT_TIMESTAMP), ?14, ?15, ?16)
+                "#,
+            )?;
+            for row in &rows {
+                stmt.execute(params![
+                    row.character,
+                    row.total_attempts,
+                    row.correct_attempts,
+                    row.total_time_ms,
+                    row.min_time_ms,
+                    row.max_time_ms,
+                    row.uppercase_attempts,
+                    row.uppercase_correct,
+                    row.uppercase_time_ms,
+                    row.uppercase_min_time,
+                    row.uppercase_max_time,
+                    row.session_date,
+                    row.created_at,
+                    row.language,
+                    row.flags,
+                    row.corrected_attempts,
+                ])?;
+            }
+        }
+        tx.commit()?;
+
+        Ok(rows.len())
+    }
+
     /// Get the actual database file path being used (for debugging)
-    pub fn get_database_path() -> Option<PathBuf> {
-        Self::get_db_path()
+    pub fn get_database_path(profile: Option<&str>) -> Option<PathBuf> {
+        Self::get_db_path(profile)
     }
 
     /// Check if the database file exists on disk
-    pub fn database_exists() -> bool {
-        if let Some(path) = Self::get_db_path() {
+    pub fn database_exists(profile: Option<&str>) -> bool {
+        if let Some(path) = Self::get_db_path(profile) {
             path.exists()
         } else {
             false
@@ -1215,10 +1971,13 @@ impl StatsDb {
                 SUM(uppercase_time_ms) as uppercase_time_ms,
                 MIN(CASE WHEN uppercase_min_time > 0 THEN uppercase_min_time ELSE NULL END) as uppercase_min_time,
                 MAX(uppercase_max_time) as uppercase_max_time,
-                'compacted_' || date('now') as session_date
+                SUM(corrected_attempts) as corrected_attempts,
+                'compacted_' || date('now') as session_date,
+                language,
+                flags
             FROM char_session_stats
             WHERE session_date < date('now', '-30 days')  -- Only compact data older than 30 days
-            GROUP BY character
+            GROUP BY character, language, flags
             HAVING COUNT(*) > 1  -- Only compact characters with multiple sessions
             "#,
             [],
@@ -1249,12 +2008,14 @@ impl StatsDb {
                 INSERT INTO char_session_stats (
                     character, total_attempts, correct_attempts, total_time_ms, 
                     min_time_ms, max_time_ms, uppercase_attempts, uppercase_correct, 
-                    uppercase_time_ms, uppercase_min_time, uppercase_max_time, session_date
+                    uppercase_time_ms, uppercase_min_time, uppercase_max_time, session_date,
+                    language, flags, corrected_attempts
                 )
                 SELECT 
                     character, total_attempts, correct_attempts, total_time_ms,
                     COALESCE(min_time_ms, 0), max_time_ms, uppercase_attempts, uppercase_correct,
-                    uppercase_time_ms, COALESCE(uppercase_min_time, 0), uppercase_max_time, session_date
+                    uppercase_time_ms, COALESCE(uppercase_min_time, 0), uppercase_max_time, session_date,
+                    language, flags, corrected_attempts
                 FROM compacted_stats
                 "#,
                 [],
@@ -1295,6 +2056,15 @@ impl StatsDb {
         Ok((session_count, db_size, db_size_mb))
     }
 
+    /// The `n` characters most in need of practice with their scores, worst first.
+    /// Only characters with at least 3 attempts are considered.
+    pub fn get_weakest_characters_summary(&self, n: usize) -> Result<Vec<(char, f64)>> {
+        Ok(crate::language::rank_weakest_characters(
+            &self.get_character_difficulties()?,
+            n,
+        ))
+    }
+
     /// Get character difficulty metrics for intelligent word selection
     pub fn get_character_difficulties(&self) -> Result<HashMap<char, CharacterDifficulty>> {
         let mut stmt = self.conn.prepare(
@@ -1333,33 +2103,16 @@ impl StatsDb {
         let difficulty_iter = stmt.query_map([], |row| {
             let char_str: String = row.get(0)?;
             let character = char_str.chars().next().unwrap_or('\0');
-            let avg_time: f64 = row.get(1)?;
-            let miss_rate: f64 = row.get(2)?;
-            let total_attempts: i64 = row.get(3)?;
-            let uppercase_avg_time: f64 = row.get(4)?;
-            let uppercase_miss_rate: f64 = row.get(5)?;
-            let uppercase_attempts: i64 = row.get(6)?;
-
-            // Calculate uppercase penalty based on performance difference
-            let uppercase_penalty = if uppercase_attempts > 0 {
-                let time_penalty = (uppercase_avg_time - avg_time).max(0.0) / avg_time;
-                let miss_penalty = (uppercase_miss_rate - miss_rate).max(0.0) / 100.0;
-                (time_penalty + miss_penalty).min(1.0) // Cap at 1.0
-            } else {
-                0.5 // Default penalty when no uppercase data
-            };
-
             Ok((
                 character,
-                CharacterDifficulty {
-                    miss_rate,
-                    avg_time_ms: avg_time,
-                    total_attempts,
-                    uppercase_miss_rate,
-                    uppercase_avg_time,
-                    uppercase_attempts,
-                    uppercase_penalty,
-                },
+                character_difficulty(
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                    row.get(6)?,
+                ),
             ))
         })?;
 
@@ -1371,6 +2124,89 @@ impl StatsDb {
 
         Ok(difficulties)
     }
+
+    /// Like `get_character_difficulties`, but each session's attempts count for half as much
+    /// every `halflife_days` of age, so averages follow current skill rather than old
+    /// weaknesses. Attempt counts, and the minimum of 3 a character needs, stay unweighted.
+    pub fn get_character_difficulties_decayed(
+        &self,
+        halflife_days: f64,
+    ) -> Result<HashMap<char, CharacterDifficulty>> {
+        self.character_difficulties_decayed_at(halflife_days, Local::now().date_naive())
+    }
+
+    fn character_difficulties_decayed_at(
+        &self,
+        halflife_days: f64,
+        today: NaiveDate,
+    ) -> Result<HashMap<char, CharacterDifficulty>> {
+        #[derive(Default)]
+        struct Weighted {
+            attempts: i64,
+            total: f64,
+            correct: f64,
+            time_ms: f64,
+            uppercase_attempts: i64,
+            uppercase_total: f64,
+            uppercase_correct: f64,
+            uppercase_time_ms: f64,
+        }
+
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT character, session_date, total_attempts, correct_attempts, total_time_ms,
+                   uppercase_attempts, uppercase_correct, uppercase_time_ms
+            FROM char_session_stats
+            "#,
+        )?;
+        let mut rows = stmt.query([])?;
+        let mut by_char: HashMap<char, Weighted> = HashMap::new();
+        while let Some(row) = rows.next()? {
+            let character = row.get::<_, String>(0)?.chars().next().unwrap_or('\0');
+            let weight =
+                0.5_f64.powf(session_age_days(&row.get::<_, String>(1)?, today) / halflife_days);
+            let (total, correct): (i64, i64) = (row.get(2)?, row.get(3)?);
+            let (uppercase_total, uppercase_correct): (i64, i64) = (row.get(5)?, row.get(6)?);
+            let entry = by_char.entry(character).or_default();
+            entry.attempts += total;
+            entry.total += weight * total as f64;
+            entry.correct += weight * correct as f64;
+            entry.time_ms += weight * row.get::<_, i64>(4)? as f64;
+            entry.uppercase_attempts += uppercase_total;
+            entry.uppercase_total += weight * uppercase_total as f64;
+            entry.uppercase_correct += weight * uppercase_correct as f64;
+            entry.uppercase_time_ms += weight * row.get::<_, i64>(7)? as f64;
+        }
+
+        Ok(by_char
+            .into_iter()
+            .filter(|(_, w)| w.attempts >= 3)
+            .map(|(character, w)| {
+                let ratio = |part: f64, whole: f64, default: f64| {
+                    if whole > 0.0 {
+                        part / whole
+                    } else {
+                        default
+                    }
+                };
+                (
+                    character,
+                    character_difficulty(
+                        ratio(w.time_ms, w.correct, 500.0),
+                        ratio((w.total - w.correct) * 100.0, w.total, 50.0),
+                        w.attempts,
+                        ratio(w.uppercase_time_ms, w.uppercase_correct, 700.0),
+                        ratio(
+                            (w.uppercase_total - w.uppercase_correct) * 100.0,
+                            w.uppercase_total,
+                            75.0,
+                        ),
+                        w.uppercase_attempts,
+                    ),
+                )
+            })
+            .collect())
+    }
 }
 
 /// Helper function to calculate time difference in milliseconds
@@ -1433,9 +2269,16 @@ mod tests {
             [],
         ).unwrap();
 
+        conn.execute(SESSION_RESULTS_SCHEMA, []).unwrap();
+        add_suspicious_column(&conn).unwrap();
+        conn.execute(WORD_STATS_SCHEMA, []).unwrap();
+        add_session_context_columns(&conn).unwrap();
+        add_corrected_attempts_column(&conn).unwrap();
+
         StatsDb {
             conn,
             session_buffer: HashMap::new(),
+            context: SessionContext::default(),
         }
     }
 
@@ -1487,6 +2330,7 @@ mod tests {
                 time_to_press_ms: 150,
                 was_correct: true,
                 was_uppercase: false,
+                was_correction: false,
                 timestamp: Local::now(),
                 context_before: "".to_string(),
                 context_after: "ello".to_string(),
@@ -1496,6 +2340,7 @@ mod tests {
                 time_to_press_ms: 120,
                 was_correct: true,
                 was_uppercase: false,
+                was_correction: false,
                 timestamp: Local::now(),
                 context_before: "".to_string(),
                 context_after: "ello".to_string(),
@@ -1518,6 +2363,7 @@ mod tests {
                 time_to_press_ms: 100,
                 was_correct: true,
                 was_uppercase: false,
+                was_correction: false,
                 timestamp: Local::now(),
                 context_before: "".to_string(),
                 context_after: "est".to_string(),
@@ -1527,6 +2373,7 @@ mod tests {
                 time_to_press_ms: 150,
                 was_correct: false,
                 was_uppercase: false,
+                was_correction: false,
                 timestamp: Local::now(),
                 context_before: "".to_string(),
                 context_after: "est".to_string(),
@@ -1536,6 +2383,7 @@ mod tests {
                 time_to_press_ms: 120,
                 was_correct: true,
                 was_uppercase: false,
+                was_correction: false,
                 timestamp: Local::now(),
                 context_before: "".to_string(),
                 context_after: "est".to_string(),
@@ -1560,6 +2408,7 @@ mod tests {
             time_to_press_ms: 100,
             was_correct: true,
             was_uppercase: false,
+            was_correction: false,
             timestamp: Local::now(),
             context_before: "".to_string(),
             context_after: "yz".to_string(),
@@ -1574,6 +2423,555 @@ mod tests {
         assert_eq!(summary_after.len(), 0);
     }
 
+    #[test]
+    fn test_accented_characters_round_trip() {
+        let mut db = create_test_db();
+        let (context_before, context_after) = extract_context("camión", 4, 3);
+        assert_eq!(context_before, "ami");
+        assert_eq!(context_after, "n");
+
+        let stat = CharStat {
+            character: 'ó',
+            time_to_press_ms: 180,
+            was_correct: false,
+            was_uppercase: false,
+            was_correction: false,
+            timestamp: Local::now(),
+            context_before,
+            context_after,
+        };
+        db.record_char_stats_batch(&[stat]).unwrap();
+
+        let summary = db.get_all_char_summary().unwrap();
+        assert_eq!(summary.len(), 1);
+        assert_eq!(summary[0].0, 'ó');
+        assert_eq!(summary[0].2, 100.0);
+    }
+
+    #[test]
+    fn test_best_wpm_tracks_maximum() {
+        let db = create_test_db();
+        assert_eq!(db.get_best_wpm().unwrap(), None);
+
+        db.record_session_result(42.0, 95.0, false).unwrap();
+        db.record_session_result(61.5, 90.0, false).unwrap();
+        db.record_session_result(50.0, 100.0, false).unwrap();
+        assert_eq!(db.get_best_wpm().unwrap(), Some(61.5));
+
+        // A pasted result is kept but never becomes the best
+        db.record_session_result(900.0, 100.0, true).unwrap();
+        assert_eq!(db.get_best_wpm().unwrap(), Some(61.5));
+
+        db.clear_all_stats().unwrap();
+        assert_eq!(db.get_best_wpm().unwrap(), None);
+    }
+
+    #[test]
+    fn test_decayed_difficulties_follow_recent_sessions() {
+        let db = create_test_db();
+        let today = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        // 'a' used to be slow and missed half the time, and is clean and quick lately
+        for (date, correct, time_ms) in [("2024-04-01", 5, 2000), ("2024-06-30", 10, 1000)] {
+            db.conn
+                .execute(
+                    "INSERT INTO char_session_stats (character, total_attempts, correct_attempts, total_time_ms, min_time_ms, max_time_ms, session_date) VALUES ('a', 10, ?1, ?2, 0, 0, ?3)",
+                    params![correct, time_ms, date],
+                )
+                .unwrap();
+        }
+
+        let flat = &db.get_character_difficulties().unwrap()[&'a'];
+        assert_eq!(flat.miss_rate, 25.0);
+        assert_eq!(flat.avg_time_ms, 200.0);
+
+        let decayed = &db.character_difficulties_decayed_at(7.0, today).unwrap()[&'a'];
+        assert!(decayed.miss_rate < 1.0, "{}", decayed.miss_rate);
+        assert!(
+            (decayed.avg_time_ms - 100.0).abs() < 2.0,
+            "{}",
+            decayed.avg_time_ms
+        );
+        assert_eq!(decayed.total_attempts, 20);
+
+        // A long half-life barely tells the sessions apart
+        let slow_decay = &db.character_difficulties_decayed_at(3650.0, today).unwrap()[&'a'];
+        assert!((slow_decay.miss_rate - 25.0).abs() < 1.0);
+
+        assert_eq!(session_age_days("2024-06-23", today), 7.0);
+        assert_eq!(session_age_days("compacted_2024-06-23", today), 37.0);
+        assert_eq!(session_age_days("garbage", today), 0.0);
+    }
+
+    #[test]
+    fn test_profiles_keep_separate_stats() {
+        // Laid out like the real state directory: the default database beside one per profile
+        let dir = tempfile::tempdir().unwrap();
+        let open = |profile: &str| StatsDb::open(&dir.path().join(profile).join("stats.db"));
+
+        let mut alice = open("alice").unwrap();
+        let stat = CharStat {
+            character: 'a',
+            time_to_press_ms: 100,
+            was_correct: true,
+            was_uppercase: false,
+            was_correction: false,
+            timestamp: Local::now(),
+            context_before: String::new(),
+            context_after: String::new(),
+        };
+        alice.record_char_stats_batch(&[stat]).unwrap();
+        alice.record_session_result(50.0, 100.0, false).unwrap();
+
+        let bob = open("bob").unwrap();
+        assert!(bob.get_all_char_summary().unwrap().is_empty());
+        assert_eq!(bob.get_best_wpm().unwrap(), None);
+
+        // Reopening finds the profile's own data again
+        let alice = open("alice").unwrap();
+        assert_eq!(alice.get_all_char_summary().unwrap().len(), 1);
+        assert_eq!(alice.get_best_wpm().unwrap(), Some(50.0));
+    }
+
+    #[test]
+    fn test_recent_session_results_newest_first() {
+        let stores: Vec<Box<dyn StatsStore>> = vec![
+            Box::new(create_test_db()),
+            Box::new(InMemoryStatsStore::default()),
+        ];
+        for mut store in stores {
+            assert!(store.get_recent_session_results(10).unwrap().is_empty());
+
+            for (wpm, accuracy) in [(30.0, 90.0), (40.0, 95.0), (50.0, 97.0)] {
+                store.record_session_result(wpm, accuracy, false).unwrap();
+            }
+            store.record_session_result(900.0, 100.0, true).unwrap();
+
+            // The suspicious result is kept but never read back
+            assert_eq!(store.get_best_wpm().unwrap(), Some(50.0));
+            let recent = store.get_recent_session_results(2).unwrap();
+            assert_eq!(
+                recent,
+                vec![
+                    SessionSummary {
+                        wpm: 50.0,
+                        accuracy: 97.0
+                    },
+                    SessionSummary {
+                        wpm: 40.0,
+                        accuracy: 95.0
+                    },
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_slowest_words_ranked_per_character() {
+        let mut db = create_test_db();
+        let stat = |word: &str, time_ms, clean| WordStat {
+            word: word.to_string(),
+            time_ms,
+            clean,
+        };
+        db.record_word_stats(&[
+            stat("the", 300, true),
+            stat("rhythm", 1800, false),
+            stat("rhythm", 1200, true),
+            stat("hello,", 900, true),
+        ])
+        .unwrap();
+
+        let slowest = db.get_slowest_words(2).unwrap();
+        assert_eq!(slowest.len(), 2);
+        assert_eq!(slowest[0].word, "rhythm");
+        assert_eq!(slowest[0].avg_time_ms, 1500.0);
+        assert_eq!(slowest[0].clean_rate, 50.0);
+        assert_eq!(slowest[0].attempts, 2);
+        assert_eq!(slowest[1].word, "hello,");
+
+        let mut store = InMemoryStatsStore::default();
+        store
+            .record_word_stats(&[stat("the", 300, true), stat("hello,", 900, true)])
+            .unwrap();
+        assert_eq!(store.get_slowest_words(1).unwrap()[0].word, "hello,");
+
+        db.clear_all_stats().unwrap();
+        assert!(db.get_slowest_words(5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_get_weakest_characters_summary() {
+        let mut db = create_test_db();
+        let stat = |character, ms, was_correct| CharStat {
+            character,
+            time_to_press_ms: ms,
+            was_correct,
+            was_uppercase: false,
+            was_correction: false,
+            timestamp: Local::now(),
+            context_before: String::new(),
+            context_after: String::new(),
+        };
+
+        // Too few attempts for any character yet
+        db.record_char_stats_batch(&[stat('q', 400, false), stat('q', 400, true)])
+            .unwrap();
+        assert!(db.get_weakest_characters_summary(3).unwrap().is_empty());
+
+        let mut batch = Vec::new();
+        for _ in 0..3 {
+            batch.push(stat('a', 100, true));
+            batch.push(stat('z', 500, true));
+            batch.push(stat('x', 100, false));
+        }
+        batch.push(stat('q', 400, false));
+        db.record_char_stats_batch(&batch).unwrap();
+
+        let weakest = db.get_weakest_characters_summary(2).unwrap();
+        let chars: Vec<char> = weakest.iter().map(|(c, _)| *c).collect();
+        // x misses every time; q misses 2 of 3 and is slow
+        assert_eq!(chars, vec!['x', 'q']);
+        assert!(weakest[0].1 >= weakest[1].1);
+    }
+
+    #[test]
+    fn test_get_finger_summary() {
+        let mut db = create_test_db();
+        assert!(db
+            .get_finger_summary(KeyboardLayout::Qwerty)
+            .unwrap()
+            .is_empty());
+
+        let stat = |character, ms, was_correct| CharStat {
+            character,
+            time_to_press_ms: ms,
+            was_correct,
+            was_uppercase: false,
+            was_correction: false,
+            timestamp: Local::now(),
+            context_before: String::new(),
+            context_after: String::new(),
+        };
+        db.record_char_stats_batch(&[
+            stat('f', 100, true),
+            stat('g', 200, true),
+            stat('r', 300, false),
+            stat('a', 180, true),
+            stat('é', 500, false),
+        ])
+        .unwrap();
+
+        // On QWERTY f, g and r all belong to the left index finger; é is off the main block
+        let qwerty = db.get_finger_summary(KeyboardLayout::Qwerty).unwrap();
+        assert_eq!(qwerty.len(), 2);
+        assert_eq!(qwerty[0], (Finger::LeftPinky, 180.0, 0.0));
+        let (finger, avg_time, miss_rate) = qwerty[1];
+        assert_eq!((finger, avg_time), (Finger::LeftIndex, 150.0));
+        assert!((miss_rate - 100.0 / 3.0).abs() < 1e-9);
+
+        // Dvorak spreads the same keys over other fingers
+        let dvorak = db.get_finger_summary(KeyboardLayout::Dvorak).unwrap();
+        let fingers: Vec<Finger> = dvorak.iter().map(|(f, ..)| *f).collect();
+        assert_eq!(
+            fingers,
+            vec![Finger::LeftPinky, Finger::RightIndex, Finger::RightRing]
+        );
+
+        let mut store = InMemoryStatsStore::default();
+        store
+            .record_char_stats_batch(&[stat('f', 100, true), stat('g', 200, true)])
+            .unwrap();
+        assert_eq!(
+            store.get_finger_summary(KeyboardLayout::Qwerty).unwrap(),
+            vec![(Finger::LeftIndex, 150.0, 0.0)]
+        );
+    }
+
+    #[test]
+    fn test_get_daily_summary() {
+        let db = create_test_db();
+        assert!(db.get_daily_summary(7, None).unwrap().is_empty());
+
+        for (date, wpm, accuracy) in [
+            ("2026-10-01T09:00:00+02:00", 50.0, 90.0),
+            ("2026-10-01T21:30:00+02:00", 60.0, 100.0),
+            ("2026-10-03T08:15:00+02:00", 70.0, 95.0),
+            ("2026-10-04T12:00:00+02:00", 80.0, 97.0),
+            ("2026-10-04T12:05:00+02:00", 40.0, 93.0),
+        ] {
+            db.conn
+                .execute(
+                    "INSERT INTO session_results (date, wpm, accuracy) VALUES (?1, ?2, ?3)",
+                    params![date, wpm, accuracy],
+                )
+                .unwrap();
+        }
+        // A pasted run on a day of its own and one on a day with real tests: neither counts
+        for date in ["2026-10-02T10:00:00+02:00", "2026-10-03T10:00:00+02:00"] {
+            db.conn
+                .execute(
+                    "INSERT INTO session_results (date, wpm, accuracy, suspicious) VALUES (?1, 900.0, 100.0, 1)",
+                    params![date],
+                )
+                .unwrap();
+        }
+
+        let day = |d: &str, wpm, accuracy| (d.to_string(), wpm, accuracy);
+        assert_eq!(
+            db.get_daily_summary(7, None).unwrap(),
+            vec![
+                day("2026-10-01", 55.0, 95.0),
+                day("2026-10-03", 70.0, 95.0),
+                day("2026-10-04", 60.0, 95.0),
+            ]
+        );
+        // Only the most recent days, still oldest first
+        assert_eq!(
+            db.get_daily_summary(2, None).unwrap(),
+            vec![day("2026-10-03", 70.0, 95.0), day("2026-10-04", 60.0, 95.0)]
+        );
+        assert_eq!(
+            db.get_daily_summary(7, NaiveDate::from_ymd_opt(2026, 10, 2))
+                .unwrap(),
+            vec![day("2026-10-03", 70.0, 95.0), day("2026-10-04", 60.0, 95.0)]
+        );
+    }
+
+    #[test]
+    fn test_get_char_history() {
+        let db = create_test_db();
+        assert!(db.get_char_history('a', 5).unwrap().is_empty());
+
+        for (character, attempts, time_ms, date) in [
+            ("a", 2, 400, "2026-10-01"),
+            ("a", 2, 200, "2026-10-01"),
+            ("a", 4, 600, "2026-10-02"),
+            ("a", 1, 90, "2026-10-03"),
+            ("a", 10, 9000, "compacted_2026-10-04"),
+            ("b", 1, 500, "2026-10-03"),
+        ] {
+            db.conn
+                .execute(
+                    "INSERT INTO char_session_stats (character, total_attempts, correct_attempts, total_time_ms, min_time_ms, max_time_ms, session_date) VALUES (?1, ?2, ?2, ?3, 0, 0, ?4)",
+                    params![character, attempts, time_ms, date],
+                )
+                .unwrap();
+        }
+
+        let day = |d: &str, avg| (d.to_string(), avg);
+        assert_eq!(
+            db.get_char_history('a', 5).unwrap(),
+            vec![
+                day("2026-10-01", 150.0),
+                day("2026-10-02", 150.0),
+                day("2026-10-03", 90.0)
+            ]
+        );
+        // Only the most recent sessions, still oldest first
+        assert_eq!(
+            db.get_char_history('a', 1).unwrap(),
+            vec![day("2026-10-03", 90.0)]
+        );
+    }
+
+    #[test]
+    fn test_char_history_averages_correct_presses_only() {
+        let db = create_test_db();
+        // Only correct presses are timed; a day of nothing but misses has no average
+        for (attempts, correct, time_ms, date) in [
+            (4, 2, 300, "2026-10-01"),
+            (3, 0, 0, "2026-10-02"),
+            (5, 4, 400, "2026-10-03"),
+        ] {
+            db.conn
+                .execute(
+                    "INSERT INTO char_session_stats (character, total_attempts, correct_attempts, total_time_ms, min_time_ms, max_time_ms, session_date) VALUES ('a', ?1, ?2, ?3, 0, 0, ?4)",
+                    params![attempts, correct, time_ms, date],
+                )
+                .unwrap();
+        }
+
+        assert_eq!(
+            db.get_char_history('a', 5).unwrap(),
+            vec![
+                ("2026-10-01".to_string(), 150.0),
+                ("2026-10-03".to_string(), 100.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_summary_difficulty_matches_selector_score() {
+        let summary = CharSummaryWithDeltas {
+            character: 'q',
+            avg_time: 350.0,
+            miss_rate: 10.0,
+            attempts: 20,
+            time_delta: None,
+            miss_delta: None,
+            session_attempts: 0,
+            latest_datetime: None,
+            correction_rate: None,
+        };
+        let difficulty = CharacterDifficulty {
+            miss_rate: 10.0,
+            avg_time_ms: 350.0,
+            total_attempts: 20,
+            uppercase_miss_rate: 0.0,
+            uppercase_avg_time: 0.0,
+            uppercase_attempts: 0,
+            uppercase_penalty: 0.0,
+        };
+        assert_eq!(summary.difficulty(), 21.5);
+        assert_eq!(summary.difficulty(), difficulty.practice_score());
+    }
+
+    #[test]
+    fn test_export_import_json_roundtrip() {
+        let mut db = create_test_db();
+        let stats: Vec<CharStat> = [
+            ('a', 100, true, false),
+            ('a', 140, false, true),
+            ('b', 90, true, true),
+        ]
+        .into_iter()
+        .map(|(character, ms, was_correct, was_uppercase)| CharStat {
+            character,
+            time_to_press_ms: ms,
+            was_correct,
+            was_uppercase,
+            was_correction: false,
+            timestamp: Local::now(),
+            context_before: String::new(),
+            context_after: String::new(),
+        })
+        .collect();
+        db.record_char_stats_batch(&stats).unwrap();
+
+        let exported = db.export_json().unwrap();
+        let rows: Vec<CharSessionRow> = serde_json::from_str(&exported).unwrap();
+        assert_eq!(rows.len(), 2);
+        let b = rows.iter().find(|r| r.character == "b").unwrap();
+        assert_eq!(b.uppercase_attempts, 1);
+        assert_eq!(b.uppercase_correct, 1);
+        assert!(!b.session_date.is_empty());
+
+        let restored = create_test_db();
+        assert_eq!(restored.import_json(&exported).unwrap(), 2);
+        assert_eq!(
+            restored.get_all_char_summary().unwrap(),
+            db.get_all_char_summary().unwrap()
+        );
+
+        // Same rows apart from reassigned ids
+        let strip_ids = |json: &str| -> Vec<CharSessionRow> {
+            serde_json::from_str::<Vec<CharSessionRow>>(json)
+                .unwrap()
+                .into_iter()
+                .map(|r| CharSessionRow { id: 0, ..r })
+                .collect()
+        };
+        assert_eq!(
+            strip_ids(&restored.export_json().unwrap()),
+            strip_ids(&exported)
+        );
+    }
+
+    #[test]
+    fn test_session_context_columns_added_to_old_databases() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            r#"
+            CREATE TABLE char_session_stats (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                character TEXT NOT NULL,
+                total_attempts INTEGER NOT NULL,
+                correct_attempts INTEGER NOT NULL,
+                total_time_ms INTEGER NOT NULL,
+                min_time_ms INTEGER NOT NULL,
+                max_time_ms INTEGER NOT NULL,
+                session_date TEXT NOT NULL
+            )
+            "#,
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO char_session_stats (character, total_attempts, correct_attempts, total_time_ms, min_time_ms, max_time_ms, session_date) VALUES ('a', 4, 3, 300, 90, 110, '2024-01-01')",
+            [],
+        )
+        .unwrap();
+
+        add_session_context_columns(&conn).unwrap();
+        // Running it again leaves the migrated table alone
+        add_session_context_columns(&conn).unwrap();
+
+        let (language, flags): (String, i64) = conn
+            .query_row(
+                "SELECT language, flags FROM char_session_stats",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((language.as_str(), flags), ("", 0));
+    }
+
+    #[test]
+    fn test_char_summary_filtered_by_session_context() {
+        let mut db = create_test_db();
+        let record = |db: &mut StatsDb, language: &str, flags, was_correct| {
+            db.set_session_context(SessionContext {
+                language: language.to_string(),
+                flags,
+            });
+            let stat = CharStat {
+                character: 'a',
+                time_to_press_ms: 100,
+                was_correct,
+                was_uppercase: false,
+                was_correction: false,
+                timestamp: Local::now(),
+                context_before: String::new(),
+                context_after: String::new(),
+            };
+            db.record_char_stats_batch(&[stat]).unwrap();
+        };
+        record(&mut db, "english", 0, true);
+        record(&mut db, "english", SessionContext::SYMBOLS, false);
+        record(
+            &mut db,
+            "spanish",
+            SessionContext::SYMBOLS | SessionContext::STRICT,
+            false,
+        );
+
+        let miss_rate = |language, flags| {
+            let summary = db.get_all_char_summary_filtered(language, flags).unwrap();
+            summary
+                .first()
+                .map(|(_, _, miss_rate, attempts)| (*miss_rate, *attempts))
+        };
+        assert_eq!(miss_rate(None, 0), Some((200.0 / 3.0, 3)));
+        assert_eq!(miss_rate(None, SessionContext::SYMBOLS), Some((100.0, 2)));
+        assert_eq!(miss_rate(Some("english"), 0), Some((50.0, 2)));
+        assert_eq!(
+            miss_rate(Some("english"), SessionContext::SYMBOLS),
+            Some((100.0, 1))
+        );
+        assert_eq!(miss_rate(Some("english"), SessionContext::STRICT), None);
+
+        let rows: Vec<CharSessionRow> = serde_json::from_str(&db.export_json().unwrap()).unwrap();
+        assert_eq!(rows[2].language, "spanish");
+        assert_eq!(rows[2].flags, 9);
+    }
+
+    #[test]
+    fn test_import_json_rejects_malformed_input() {
+        let db = create_test_db();
+        assert!(db.import_json("{not json").is_err());
+        assert_eq!(db.get_session_count().unwrap(), 0);
+    }
+
     #[test]
     fn test_flush() {
         let mut db = create_test_db();
@@ -1583,6 +2981,7 @@ mod tests {
             time_to_press_ms: 120,
             was_correct: true,
             was_uppercase: false,
+            was_correction: false,
             timestamp: Local::now(),
             context_before: "".to_string(),
             context_after: "oo".to_string(),
@@ -1759,6 +3158,7 @@ mod tests {
                 time_to_press_ms: 100,
                 was_correct: true,
                 was_uppercase: false,
+                was_correction: false,
                 timestamp: Local::now(),
                 context_before: "".to_string(),
                 context_after: "bc".to_string(),
@@ -1768,6 +3168,7 @@ mod tests {
                 time_to_press_ms: 150,
                 was_correct: false,
                 was_uppercase: false,
+                was_correction: false,
                 timestamp: Local::now(),
                 context_before: "".to_string(),
                 context_after: "bc".to_string(),
@@ -1777,6 +3178,7 @@ mod tests {
                 time_to_press_ms: 120,
                 was_correct: true,
                 was_uppercase: false,
+                was_correction: false,
                 timestamp: Local::now(),
                 context_before: "a".to_string(),
                 context_after: "c".to_string(),
@@ -1820,6 +3222,7 @@ mod tests {
             time_to_press_ms: 200, // This will be the historical average
             was_correct: true,
             was_uppercase: false,
+            was_correction: false,
             timestamp: Local::now(),
             context_before: "".to_string(),
             context_after: "bc".to_string(),
@@ -1840,6 +3243,7 @@ mod tests {
                 time_to_press_ms: 150, // Faster than historical (200ms avg)
                 was_correct: true,
                 was_uppercase: false,
+                was_correction: false,
                 timestamp: Local::now(),
                 context_before: "".to_string(),
                 context_after: "bc".to_string(),
@@ -1849,6 +3253,7 @@ mod tests {
                 time_to_press_ms: 170,
                 was_correct: true,
                 was_uppercase: false,
+                was_correction: false,
                 timestamp: Local::now(),
                 context_before: "".to_string(),
                 context_after: "bc".to_string(),
@@ -1892,6 +3297,7 @@ mod tests {
             time_to_press_ms: 180,
             was_correct: true,
             was_uppercase: false,
+            was_correction: false,
             timestamp: Local::now(),
             context_before: "".to_string(),
             context_after: "".to_string(),
@@ -1916,4 +3322,43 @@ mod tests {
         assert!(s.time_delta.is_none());
         assert!(s.miss_delta.is_none());
     }
+
+    #[test]
+    fn test_uppercase_drill_prompt_records_uppercase_attempts() {
+        use crate::language::{TextFormatter, UppercaseDrillFormatter};
+        use rand::SeedableRng;
+
+        let drill = UppercaseDrillFormatter {
+            difficulties: HashMap::new(),
+        };
+        let prompt = drill.format(
+            vec!["banana".to_string(); 4],
+            &mut rand::rngs::StdRng::seed_from_u64(11),
+        );
+        assert!(prompt.chars().any(char::is_uppercase));
+
+        let mut thok = crate::thok::Thok::with_stats_store(
+            prompt.clone(),
+            4,
+            None,
+            false,
+            Box::new(create_test_db()),
+        );
+        for c in prompt.chars() {
+            thok.write(c);
+        }
+        thok.flush_char_stats();
+
+        // Stats are keyed by the lowercase letter, with the capitals counted on the side
+        let difficulties = thok.get_character_difficulties().unwrap();
+        for letter in ['b', 'a', 'n'] {
+            let typed = |c: char| prompt.chars().filter(|&p| p == c).count() as i64;
+            let upper = letter.to_ascii_uppercase();
+            assert_eq!(
+                difficulties[&letter].total_attempts,
+                typed(letter) + typed(upper)
+            );
+            assert_eq!(difficulties[&letter].uppercase_attempts, typed(upper));
+        }
+    }
 }
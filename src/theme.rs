@@ -0,0 +1,198 @@
+use clap::ValueEnum;
+use ratatui::style::Color;
+
+/// Built-in color presets selectable with `--theme`
+#[derive(Debug, Copy, Clone, Default, PartialEq, ValueEnum, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum ThemeName {
+    #[default]
+    Default,
+    Mono,
+    Solarized,
+}
+
+/// Colors shared by every screen, so a preset restyles the whole app at once
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Theme {
+    /// Correctly typed characters, fast keys and improvements
+    pub correct: Color,
+    /// Mistyped characters, slow keys and regressions
+    pub incorrect: Color,
+    /// Errors fixed in strict mode
+    pub corrected: Color,
+    /// In-between buckets, overlays and highlighted legends
+    pub warning: Color,
+    /// The character under the cursor
+    pub cursor: Color,
+    /// The untyped rest of the prompt and secondary text
+    pub dim: Color,
+    /// Chart lines and progress bars
+    pub accent: Color,
+    /// Screen titles and the session delta summary
+    pub title: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            correct: Color::Green,
+            incorrect: Color::Red,
+            corrected: Color::Rgb(255, 165, 0),
+            warning: Color::Yellow,
+            cursor: Color::Reset,
+            dim: Color::Gray,
+            accent: Color::Magenta,
+            title: Color::Cyan,
+        }
+    }
+}
+
+impl From<ThemeName> for Theme {
+    fn from(name: ThemeName) -> Self {
+        match name {
+            ThemeName::Default => Self::default(),
+            ThemeName::Mono => Self {
+                correct: Color::Gray,
+                incorrect: Color::White,
+                corrected: Color::White,
+                warning: Color::White,
+                cursor: Color::White,
+                dim: Color::DarkGray,
+                accent: Color::White,
+                title: Color::White,
+            },
+            ThemeName::Solarized => Self {
+                correct: Color::Rgb(133, 153, 0),
+                incorrect: Color::Rgb(220, 50, 47),
+                corrected: Color::Rgb(203, 75, 22),
+                warning: Color::Rgb(181, 137, 0),
+                cursor: Color::Rgb(38, 139, 210),
+                dim: Color::Rgb(88, 110, 117),
+                accent: Color::Rgb(211, 54, 130),
+                title: Color::Rgb(42, 161, 152),
+            },
+        }
+    }
+}
+
+/// Where the character stats switch between good, middling and bad colors
+/// (`--good-ms`/`--slow-ms`, `--good-miss`/`--bad-miss`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StatsThresholds {
+    /// Average keypress times below this are good
+    pub good_ms: f64,
+    /// Average keypress times at or above this are slow
+    pub slow_ms: f64,
+    /// Miss rates (%) at or below this are good
+    pub good_miss: f64,
+    /// Miss rates (%) at or above this are bad
+    pub bad_miss: f64,
+}
+
+impl Default for StatsThresholds {
+    fn default() -> Self {
+        Self {
+            good_ms: 150.0,
+            slow_ms: 250.0,
+            good_miss: 0.0,
+            bad_miss: 10.0,
+        }
+    }
+}
+
+impl StatsThresholds {
+    /// Why these thresholds can't be used, if they overlap
+    pub fn validate(&self) -> Result<(), String> {
+        if self.good_ms >= self.slow_ms {
+            return Err(format!(
+                "--good-ms ({}) must be below --slow-ms ({})",
+                self.good_ms, self.slow_ms
+            ));
+        }
+        if self.good_miss >= self.bad_miss {
+            return Err(format!(
+                "--good-miss ({}) must be below --bad-miss ({})",
+                self.good_miss, self.bad_miss
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Theme {
+    /// Color for an average keypress time: below `good_ms` is good, `slow_ms` and over is slow
+    pub fn time_color(&self, avg_time_ms: f64, thresholds: &StatsThresholds) -> Color {
+        if avg_time_ms < thresholds.good_ms {
+            self.correct
+        } else if avg_time_ms < thresholds.slow_ms {
+            self.warning
+        } else {
+            self.incorrect
+        }
+    }
+
+    /// Color for a miss rate: up to `good_miss` is good, `bad_miss` and over is bad
+    pub fn miss_rate_color(&self, miss_rate: f64, thresholds: &StatsThresholds) -> Color {
+        if miss_rate <= thresholds.good_miss {
+            self.correct
+        } else if miss_rate < thresholds.bad_miss {
+            self.warning
+        } else {
+            self.incorrect
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_differ() {
+        let default = Theme::from(ThemeName::Default);
+        assert_eq!(default, Theme::default());
+        assert_ne!(default, Theme::from(ThemeName::Mono));
+        assert_ne!(default, Theme::from(ThemeName::Solarized));
+        assert_eq!(ThemeName::Solarized.to_string(), "solarized");
+    }
+
+    #[test]
+    fn test_threshold_buckets_use_theme_colors() {
+        let theme = Theme::from(ThemeName::Solarized);
+        let defaults = StatsThresholds::default();
+        assert_eq!(theme.time_color(120.0, &defaults), theme.correct);
+        assert_eq!(theme.time_color(200.0, &defaults), theme.warning);
+        assert_eq!(theme.time_color(250.0, &defaults), theme.incorrect);
+        assert_eq!(theme.miss_rate_color(0.0, &defaults), theme.correct);
+        assert_eq!(theme.miss_rate_color(5.0, &defaults), theme.warning);
+        assert_eq!(theme.miss_rate_color(10.0, &defaults), theme.incorrect);
+    }
+
+    #[test]
+    fn test_custom_thresholds() {
+        let theme = Theme::default();
+        let beginner = StatsThresholds {
+            good_ms: 300.0,
+            slow_ms: 500.0,
+            good_miss: 5.0,
+            bad_miss: 20.0,
+        };
+        assert!(beginner.validate().is_ok());
+        assert_eq!(theme.time_color(250.0, &beginner), theme.correct);
+        assert_eq!(theme.time_color(400.0, &beginner), theme.warning);
+        assert_eq!(theme.miss_rate_color(5.0, &beginner), theme.correct);
+        assert_eq!(theme.miss_rate_color(15.0, &beginner), theme.warning);
+
+        let crossed = StatsThresholds {
+            good_ms: 300.0,
+            slow_ms: 200.0,
+            ..StatsThresholds::default()
+        };
+        assert!(crossed.validate().unwrap_err().contains("--good-ms"));
+        let crossed = StatsThresholds {
+            good_miss: 10.0,
+            ..StatsThresholds::default()
+        };
+        assert!(crossed.validate().unwrap_err().contains("--good-miss"));
+    }
+}
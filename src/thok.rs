@@ -1,14 +1,17 @@
-use crate::celebration::CelebrationAnimation;
+use crate::celebration::{CelebrationAnimation, CelebrationStyle};
+use crate::language::CharacterDifficulty;
+use crate::layout::KeyboardLayout;
 use crate::session::Session;
 use crate::stats::{StatsDb, StatsStore};
 
-/// Default tick rate used for timing calculations (100ms)
+/// Default interval between ticks (100ms); `--tick-ms` overrides it per session
 pub const TICK_RATE_MS: u64 = 100;
 use chrono::prelude::*;
 use csv::Writer;
-use directories::ProjectDirs;
+use std::collections::HashMap;
 use std::fs::OpenOptions;
-use std::io;
+use std::io::{self, BufRead};
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 #[derive(Clone, Debug, Copy, PartialEq)]
@@ -25,12 +28,98 @@ pub struct Input {
     pub keypress_start: Option<SystemTime>,
 }
 
+/// Characters kept ahead of the cursor in zen mode, so the next words are always visible
+pub const ZEN_LOOKAHEAD: usize = 40;
+
+/// Columns of the CSV results log, in order; a log with any other header is rotated away
+pub const LOG_HEADER: [&str; 14] = [
+    "date",
+    "num_words",
+    "num_secs",
+    "elapsed_secs",
+    "wpm",
+    "accuracy",
+    "std_dev",
+    "accuracy_precise",
+    "net_wpm",
+    "suspicious",
+    "keystrokes",
+    "correct_keys",
+    "incorrect_keys",
+    "corrected_keys",
+];
+
+/// First line of the log at `path`, or `None` when there is no log or it is empty
+fn read_header(path: &Path) -> io::Result<Option<String>> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let mut header = String::new();
+    io::BufReader::new(file).read_line(&mut header)?;
+    let header = header.trim_end();
+    Ok((!header.is_empty()).then(|| header.to_string()))
+}
+
+/// `<log>.v1`, or the first of `.v2`, `.v3`, ... not taken by an earlier rotation
+fn rotated_log_path(path: &Path) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    (1..)
+        .map(|version| path.with_file_name(format!("{name}.v{version}")))
+        .find(|candidate| !candidate.exists())
+        .expect("an unused version number")
+}
+
+/// Characters typed after `elapsed_secs` at a steady `wpm`, taking a word as five characters
+pub fn ghost_position(wpm: f64, elapsed_secs: f64) -> usize {
+    (wpm.max(0.0) * 5.0 / 60.0 * elapsed_secs.max(0.0)) as usize
+}
+
+/// Raw keystroke totals for a test, counted over the typed input
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KeystrokeStats {
+    pub total: usize,
+    pub correct: usize,
+    pub incorrect: usize,
+    /// Positions typed wrong at first and later fixed
+    pub corrected: usize,
+}
+
+/// Source of extra words appended when a timed or zen prompt runs out
+pub struct PromptExtender(pub Box<dyn FnMut() -> String + Send>);
+
+impl std::fmt::Debug for PromptExtender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PromptExtender")
+    }
+}
+
+/// Audible feedback fired on each incorrect keystroke in strict mode
+pub struct Bell(pub Box<dyn FnMut() + Send>);
+
+impl std::fmt::Debug for Bell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Bell")
+    }
+}
+
 /// Top-level typing test: a Session plus persistence (stats DB, CSV) and celebration.
 #[derive(Debug)]
 pub struct Thok {
     pub session: Session,
     pub stats_db: Option<Box<dyn StatsStore>>,
     pub celebration: CelebrationAnimation,
+    pub prompt_extender: Option<PromptExtender>,
+    pub bell: Option<Bell>,
+    /// Set by `calc_results` when this test beat every earlier recorded WPM
+    pub is_personal_best: bool,
+    /// Only letters and digits go into the character stats; spaces and punctuation are skipped
+    pub stats_letters_only: bool,
+    /// Pace of the `--ghost` cursor: the best WPM on record when the test was set up
+    pub ghost_wpm: Option<f64>,
+    /// `--profile` whose stats database and CSV log this test is saved to; `None` for the default
+    pub profile: Option<String>,
 }
 
 impl Thok {
@@ -44,6 +133,15 @@ impl Thok {
         self.session.state.accuracy
     }
 
+    pub fn accuracy_precise(&self) -> f64 {
+        self.session.state.accuracy_precise
+    }
+
+    /// Gross WPM less uncorrected errors per minute, never below zero
+    pub fn net_wpm(&self) -> f64 {
+        self.session.state.net_wpm
+    }
+
     pub fn std_dev(&self) -> f64 {
         self.session.state.std_dev
     }
@@ -52,6 +150,21 @@ impl Thok {
         &self.session.state.wpm_coords
     }
 
+    /// `wpm_coords` as a rolling average over `window` seconds; the reported WPM is unaffected
+    pub fn wpm_coords_smoothed(&self, window: usize) -> Vec<crate::time_series::TimeSeriesPoint> {
+        crate::time_series::rolling_average(self.wpm_coords(), window)
+    }
+
+    /// Milliseconds between each keystroke and the one before it; empty with fewer than two
+    pub fn inter_key_intervals(&self) -> Vec<u64> {
+        self.session
+            .state
+            .input
+            .windows(2)
+            .map(|pair| crate::stats::time_diff_ms(pair[0].timestamp, pair[1].timestamp))
+            .collect()
+    }
+
     pub fn input(&self) -> &[Input] {
         &self.session.state.input
     }
@@ -68,6 +181,49 @@ impl Thok {
         self.session.state.is_idle
     }
 
+    pub fn is_paused(&self) -> bool {
+        self.session.state.paused
+    }
+
+    pub fn longest_streak(&self) -> usize {
+        self.session.state.longest_streak
+    }
+
+    /// Keystrokes taken for a held key's autorepeat and left out of the char stats
+    pub fn suspicious_inputs(&self) -> usize {
+        self.session.state.suspicious_inputs
+    }
+
+    /// Keystroke totals behind the WPM and accuracy
+    pub fn keystroke_stats(&self) -> KeystrokeStats {
+        let input = &self.session.state.input;
+        let correct = input
+            .iter()
+            .filter(|i| i.outcome == Outcome::Correct)
+            .count();
+        KeystrokeStats {
+            total: input.len(),
+            correct,
+            incorrect: input.len() - correct,
+            corrected: self.session.state.corrected_positions.len(),
+        }
+    }
+
+    /// WPM within each minute of the finished test, for the results breakdown of long tests
+    pub fn per_minute_wpm(&self) -> Vec<f64> {
+        self.session.state.per_minute_wpm.clone()
+    }
+
+    /// Whether the finished test was implausibly fast or bursty, e.g. pasted; such results are
+    /// flagged in the log and never count as a best WPM
+    pub fn is_session_suspicious(&self) -> bool {
+        self.session.state.suspicious
+    }
+
+    pub fn countdown_remaining(&self) -> Option<f64> {
+        self.session.state.countdown_remaining
+    }
+
     pub fn started_at(&self) -> Option<SystemTime> {
         self.session.state.started_at
     }
@@ -76,6 +232,140 @@ impl Thok {
         &self.session.state.corrected_positions
     }
 
+    /// Seconds since the first keystroke; 0 before it
+    pub fn elapsed_secs(&self) -> f64 {
+        self.session.elapsed_secs()
+    }
+
+    /// Words the prompt was generated with
+    pub fn number_of_words(&self) -> usize {
+        self.session.config.number_of_words
+    }
+
+    /// Length of a timed test; `None` for tests that end with the prompt
+    pub fn number_of_secs(&self) -> Option<f64> {
+        self.session.config.number_of_secs
+    }
+
+    /// Instantaneous WPM from correct input so far; 0 before the first keystroke
+    pub fn live_wpm(&self) -> f64 {
+        let elapsed_secs = self.elapsed_secs();
+        if elapsed_secs <= 0.0 {
+            return 0.0;
+        }
+        let correct = self
+            .input()
+            .iter()
+            .filter(|i| i.outcome == Outcome::Correct)
+            .count();
+        (correct as f64 / 5.0) * (60.0 / elapsed_secs)
+    }
+
+    /// Percentage of keystrokes so far that were correct; 0 before the first keystroke
+    pub fn live_accuracy(&self) -> f64 {
+        let input = self.input();
+        if input.is_empty() {
+            return 0.0;
+        }
+        let correct = input
+            .iter()
+            .filter(|i| i.outcome == Outcome::Correct)
+            .count();
+        (correct as f64 / input.len() as f64 * 100.0).round()
+    }
+
+    /// Fraction of the prompt typed so far, 0.0-1.0
+    pub fn prompt_progress(&self) -> f64 {
+        let total = self.session.prompt_char_count();
+        if total == 0 {
+            return 1.0;
+        }
+        (self.cursor_pos() as f64 / total as f64).min(1.0)
+    }
+
+    /// Words the cursor hasn't moved past yet; a partly typed word counts as remaining
+    pub fn words_remaining(&self) -> usize {
+        let cursor = self.cursor_pos();
+        let chars: Vec<char> = self.session.prompt.chars().collect();
+        // A word ends at each non-space char followed by a space or the end of the prompt
+        (0..chars.len())
+            .filter(|&i| {
+                !chars[i].is_whitespace()
+                    && chars.get(i + 1).is_none_or(|c| c.is_whitespace())
+                    && i >= cursor
+            })
+            .count()
+    }
+
+    /// Words in the whole prompt, typed or not
+    pub fn words_total(&self) -> usize {
+        self.session.prompt.split_whitespace().count()
+    }
+
+    /// Fraction of a timed test's duration used up, 0.0-1.0; `None` for untimed tests
+    pub fn time_progress(&self) -> Option<f64> {
+        let total = self.number_of_secs()?;
+        let remaining = self.seconds_remaining()?;
+        if total <= 0.0 {
+            return Some(1.0);
+        }
+        Some((1.0 - remaining / total).clamp(0.0, 1.0))
+    }
+
+    /// Each prompt word the input reached, with the percentage of its typed characters that
+    /// were right first time; a mistake fixed afterwards counts half. Punctuation stays part of
+    /// its word, and the whitespace after a word is scored with it so a missed space shows.
+    pub fn word_outcomes(&self) -> Vec<(String, f64)> {
+        let chars: Vec<char> = self.session.prompt.chars().collect();
+        let input = self.input();
+        let corrected = self.corrected_positions();
+        let mut outcomes = Vec::new();
+        let mut idx = 0;
+
+        while idx < chars.len().min(input.len()) {
+            if chars[idx].is_whitespace() {
+                idx += 1;
+                continue;
+            }
+            let word_end = chars[idx..]
+                .iter()
+                .position(|c| c.is_whitespace())
+                .map_or(chars.len(), |p| idx + p);
+            let token_end = chars[word_end..]
+                .iter()
+                .position(|c| !c.is_whitespace())
+                .map_or(chars.len(), |p| word_end + p);
+            let typed = &input[idx..token_end.min(input.len())];
+            let score: f64 = typed
+                .iter()
+                .enumerate()
+                .map(|(offset, i)| match i.outcome {
+                    Outcome::Incorrect => 0.0,
+                    Outcome::Correct if corrected.contains(&(idx + offset)) => 0.5,
+                    Outcome::Correct => 1.0,
+                })
+                .sum();
+            outcomes.push((
+                chars[idx..word_end].iter().collect(),
+                score / typed.len() as f64 * 100.0,
+            ));
+            idx = token_end;
+        }
+        outcomes
+    }
+
+    /// Where the `--ghost` cursor is: the prompt position a typist at `ghost_wpm` would have
+    /// reached by now, kept on the last character. `None` without a best on record or before
+    /// the first keystroke.
+    pub fn ghost_pos(&self) -> Option<usize> {
+        let wpm = self.ghost_wpm?;
+        if !self.has_started() {
+            return None;
+        }
+        let last = self.session.prompt_char_count().checked_sub(1)?;
+        Some(ghost_position(wpm, self.elapsed_secs()).min(last))
+    }
+
     // --- Constructors ---
 
     pub fn with_stats_store(
@@ -96,13 +386,42 @@ impl Thok {
         number_of_secs: Option<f64>,
         strict_mode: bool,
     ) -> Self {
-        let stats_db = StatsDb::new()
+        Self::with_profile(prompt, number_of_words, number_of_secs, strict_mode, None)
+    }
+
+    /// Like `new`, saving to the stats database and CSV log of `profile`
+    pub fn with_profile(
+        prompt: String,
+        number_of_words: usize,
+        number_of_secs: Option<f64>,
+        strict_mode: bool,
+        profile: Option<&str>,
+    ) -> Self {
+        let stats_db = StatsDb::new(profile)
             .ok()
             .map(|db| Box::new(db) as Box<dyn StatsStore>);
         Self {
             session: Session::new(prompt, number_of_words, number_of_secs, strict_mode),
             stats_db,
             celebration: CelebrationAnimation::default(),
+            prompt_extender: None,
+            bell: None,
+            is_personal_best: false,
+            stats_letters_only: false,
+            ghost_wpm: None,
+            profile: profile.map(String::from),
+        }
+    }
+
+    /// Start the same prompt over: typing state and results are cleared while the prompt,
+    /// session config and stats store are kept. Stats not yet flushed are dropped with the
+    /// abandoned attempt.
+    pub fn restart_same_prompt(&mut self) {
+        self.session.restart();
+        self.celebration = CelebrationAnimation::with_style(self.celebration.style);
+        self.is_personal_best = false;
+        if let Some(stats_db) = self.stats_db.as_mut() {
+            stats_db.discard_unflushed();
         }
     }
 
@@ -116,6 +435,10 @@ impl Thok {
         self.session.mark_activity()
     }
 
+    pub fn toggle_pause(&mut self) -> bool {
+        self.session.toggle_pause()
+    }
+
     pub fn get_expected_char(&self, idx: usize) -> char {
         self.session.get_expected_char(idx)
     }
@@ -132,6 +455,32 @@ impl Thok {
         self.session.backspace();
     }
 
+    /// Truncate the input back to the earliest error that hasn't been corrected and put the
+    /// cursor on it, as if backspacing to it one key at a time; a no-op without errors
+    pub fn rewind_to_first_error(&mut self) {
+        let state = &self.session.state;
+        let Some(first) = state.input.iter().enumerate().position(|(i, input)| {
+            input.outcome == Outcome::Incorrect && !state.corrected_positions.contains(&i)
+        }) else {
+            return;
+        };
+        let _ = self.session.mark_activity();
+
+        let state = &mut self.session.state;
+        let removed = state.input.split_off(first);
+        for (i, input) in (first..).zip(&removed) {
+            if input.outcome == Outcome::Incorrect {
+                state.erased_errors.insert(i);
+            }
+        }
+        let correct = removed
+            .iter()
+            .filter(|input| input.outcome == Outcome::Correct)
+            .count();
+        state.current_streak = state.current_streak.saturating_sub(correct);
+        state.cursor_pos = first;
+    }
+
     pub fn start(&mut self) {
         self.session.start();
     }
@@ -157,94 +506,153 @@ impl Thok {
     pub fn write(&mut self, c: char) {
         let _ = self.session.mark_activity();
         crate::typing_policy::apply_write(self, c);
+        self.extend_prompt_if_exhausted();
+    }
+
+    /// Append more words from the extender so that the prompt never runs out: in zen mode
+    /// while fewer than `ZEN_LOOKAHEAD` characters are left, in timed tests once the prompt
+    /// has been typed so that only the timer ends the test.
+    fn extend_prompt_if_exhausted(&mut self) {
+        let remaining = self
+            .session
+            .prompt_char_count()
+            .saturating_sub(self.session.state.input.len());
+        let due = if self.session.config.zen {
+            remaining < ZEN_LOOKAHEAD
+        } else {
+            self.session.config.number_of_secs.is_some() && remaining == 0
+        };
+        if !due {
+            return;
+        }
+        if let Some(PromptExtender(next_words)) = self.prompt_extender.as_mut() {
+            let more = next_words();
+            self.session.extend_prompt(&more);
+        }
     }
 
     pub fn calc_results(&mut self) {
         self.session.calc_results();
 
         let _ = self.save_results();
+        self.record_session_result();
+        self.record_word_stats();
 
         if self.flush_char_stats().is_some() {
             self.auto_compact_database();
         };
     }
 
-    /// Start celebration animation for perfect sessions.
-    pub fn start_celebration_if_worthy(&mut self, terminal_width: u16, terminal_height: u16) {
+    /// Compare against the best WPM so far, then store this test. The comparison happens first so
+    /// the very first test has nothing to beat and never counts as a personal best.
+    fn record_session_result(&mut self) {
+        self.is_personal_best = false;
         if self.session.state.input.is_empty() {
             return;
         }
-        if self.session.state.accuracy >= 100.0 {
+        let Some(stats_db) = self.stats_db.as_mut() else {
+            return;
+        };
+        let wpm = self.session.state.wpm;
+        if let Ok(Some(previous_best)) = stats_db.get_best_wpm() {
+            self.is_personal_best = !self.session.state.suspicious && wpm > previous_best;
+        }
+        let state = &self.session.state;
+        let _ = stats_db.record_session_result(wpm, state.accuracy, state.suspicious);
+    }
+
+    fn record_word_stats(&mut self) {
+        let word_stats = self.session.word_stats();
+        if let Some(stats_db) = self.stats_db.as_mut() {
+            let _ = stats_db.record_word_stats(&word_stats);
+        }
+    }
+
+    /// Start celebration animation for personal bests and perfect sessions.
+    pub fn start_celebration_if_worthy(&mut self, terminal_width: u16, terminal_height: u16) {
+        if self.session.state.input.is_empty() || self.celebration.style == CelebrationStyle::None {
+            return;
+        }
+        if self.is_personal_best {
+            self.celebration
+                .start_personal_best(terminal_width, terminal_height);
+        } else if self.session.state.accuracy >= 100.0 {
             self.celebration.start(terminal_width, terminal_height);
         }
     }
 
     /// Update celebration animation (should be called on each frame/tick)
     pub fn update_celebration(&mut self) {
-        self.celebration.update();
+        self.celebration.update(self.session.config.tick_secs());
     }
 
     pub fn save_results(&self) -> io::Result<()> {
-        if let Some(proj_dirs) = ProjectDirs::from("", "", "klik") {
-            let config_dir = proj_dirs.config_dir();
-            let log_path = config_dir.join("log.csv");
+        match crate::app_dirs::AppDirs::log_path(self.profile.as_deref()) {
+            Some(log_path) => self.save_results_to(&log_path),
+            None => Ok(()),
+        }
+    }
 
+    /// Append this test to the CSV log at `log_path`. A log written with other columns is
+    /// moved aside to `<name>.v1` (or the next free `.vN`) and a fresh one started, so rows
+    /// never end up under a header they don't match.
+    pub fn save_results_to(&self, log_path: &Path) -> io::Result<()> {
+        if let Some(config_dir) = log_path.parent() {
             std::fs::create_dir_all(config_dir)?;
+        }
 
-            let needs_header = !log_path.exists();
-
-            let log_file = OpenOptions::new()
-                .append(true)
-                .create(true)
-                .open(log_path)?;
-
-            let mut writer = Writer::from_writer(log_file);
-
-            if needs_header {
-                writer.write_record([
-                    "date",
-                    "num_words",
-                    "num_secs",
-                    "elapsed_secs",
-                    "wpm",
-                    "accuracy",
-                    "std_dev",
-                ])?;
+        let needs_header = match read_header(log_path)? {
+            None => true,
+            Some(header) if header == LOG_HEADER.join(",") => false,
+            Some(_) => {
+                std::fs::rename(log_path, rotated_log_path(log_path))?;
+                true
             }
+        };
+
+        let log_file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(log_path)?;
 
-            let elapsed_secs = self
-                .session
-                .state
-                .started_at
-                .unwrap_or_else(SystemTime::now)
-                .elapsed()
-                .unwrap_or_default()
-                .as_secs_f64();
-
-            let date_str = Local::now().format("%c").to_string();
-            let num_secs_str = self
-                .session
-                .config
-                .number_of_secs
-                .map_or(String::from(""), |ns| format!("{:.2}", ns));
-            let elapsed_secs_str = format!("{:.2}", elapsed_secs);
-            let wpm_str = self.session.state.wpm.to_string();
-            let accuracy_str = self.session.state.accuracy.to_string();
-            let std_dev_str = format!("{:.2}", self.session.state.std_dev);
-
-            writer.write_record([
-                &date_str,
-                &self.session.config.number_of_words.to_string(),
-                &num_secs_str,
-                &elapsed_secs_str,
-                &wpm_str,
-                &accuracy_str,
-                &std_dev_str,
-            ])?;
-
-            writer.flush()?;
+        let mut writer = Writer::from_writer(log_file);
+
+        if needs_header {
+            writer.write_record(LOG_HEADER)?;
         }
 
+        let date_str = Local::now().format("%c").to_string();
+        let num_secs_str = self
+            .number_of_secs()
+            .map_or(String::from(""), |ns| format!("{:.2}", ns));
+        let elapsed_secs_str = format!("{:.2}", self.elapsed_secs());
+        let wpm_str = self.session.state.wpm.to_string();
+        let accuracy_str = self.session.state.accuracy.to_string();
+        let std_dev_str = format!("{:.2}", self.session.state.std_dev);
+        let accuracy_precise_str = self.session.state.accuracy_precise.to_string();
+        let net_wpm_str = self.session.state.net_wpm.to_string();
+        let suspicious_str = self.session.state.suspicious.to_string();
+        let keys = self.keystroke_stats();
+
+        writer.write_record([
+            &date_str,
+            &self.number_of_words().to_string(),
+            &num_secs_str,
+            &elapsed_secs_str,
+            &wpm_str,
+            &accuracy_str,
+            &std_dev_str,
+            &accuracy_precise_str,
+            &net_wpm_str,
+            &suspicious_str,
+            &keys.total.to_string(),
+            &keys.correct.to_string(),
+            &keys.incorrect.to_string(),
+            &keys.corrected.to_string(),
+        ])?;
+
+        writer.flush()?;
+
         Ok(())
     }
 
@@ -274,6 +682,73 @@ impl Thok {
         self.stats_db.as_ref()?.get_char_summary_with_deltas().ok()
     }
 
+    /// Average time per session day for `character`, oldest first; `None` without a stats database
+    pub fn get_char_history(&self, character: char, limit: usize) -> Option<Vec<(String, f64)>> {
+        self.stats_db
+            .as_ref()?
+            .get_char_history(character, limit)
+            .ok()
+    }
+
+    pub fn get_finger_summary(
+        &self,
+        layout: KeyboardLayout,
+    ) -> Option<Vec<crate::stats::FingerSummary>> {
+        self.stats_db.as_ref()?.get_finger_summary(layout).ok()
+    }
+
+    /// The per-character difficulty scores that drive intelligent word selection; `None`
+    /// without a stats database
+    ///
+    /// ```
+    /// use klik::language::CharacterDifficulty;
+    /// use klik::stats::NoopStatsStore;
+    /// use klik::thok::Thok;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut thok = Thok::with_stats_store("abc".into(), 1, None, false, Box::new(NoopStatsStore));
+    /// let difficulties: Option<HashMap<char, CharacterDifficulty>> =
+    ///     thok.get_character_difficulties();
+    /// assert_eq!(difficulties.map(|d| d.len()), Some(0));
+    ///
+    /// thok.stats_db = None;
+    /// assert!(thok.get_character_difficulties().is_none());
+    /// ```
+    pub fn get_character_difficulties(&self) -> Option<HashMap<char, CharacterDifficulty>> {
+        self.stats_db.as_ref()?.get_character_difficulties().ok()
+    }
+
+    /// One-line hint naming the `n` characters most in need of practice
+    pub fn get_weakest_characters_hint(&self, n: usize, layout: KeyboardLayout) -> String {
+        let weakest = self
+            .stats_db
+            .as_ref()
+            .and_then(|db| db.get_weakest_characters_summary(n).ok())
+            .unwrap_or_default();
+        if weakest.is_empty() {
+            return "focus on: not enough data".to_string();
+        }
+        let chars: Vec<char> = weakest.iter().map(|(c, _)| *c).collect();
+        format!("focus on: {}", format_by_finger(&chars, layout))
+    }
+
+    /// One-line list of the `n` slowest words on record, or `None` before any are recorded
+    pub fn get_slowest_words_hint(&self, n: usize) -> Option<String> {
+        let slowest = self
+            .stats_db
+            .as_ref()
+            .and_then(|db| db.get_slowest_words(n).ok())
+            .unwrap_or_default();
+        if slowest.is_empty() {
+            return None;
+        }
+        let words: Vec<String> = slowest
+            .iter()
+            .map(|w| format!("{} ({:.0}ms)", w.word, w.avg_time_ms))
+            .collect();
+        Some(format!("slowest words: {}", words.join(", ")))
+    }
+
     pub fn get_session_delta_summary(&self) -> String {
         if let Some(summary) = self.get_char_summary_with_deltas() {
             let mut improvements = 0;
@@ -352,7 +827,7 @@ impl Thok {
     }
 
     pub fn get_stats_database_path(&self) -> Option<std::path::PathBuf> {
-        crate::stats::StatsDb::get_database_path()
+        crate::stats::StatsDb::get_database_path(self.profile.as_deref())
     }
 
     fn auto_compact_database(&mut self) {
@@ -373,6 +848,29 @@ impl Thok {
     }
 }
 
+/// e.g. `s, x (left ring); j (right index)`, with characters off the main block listed last
+fn format_by_finger(chars: &[char], layout: KeyboardLayout) -> String {
+    layout
+        .group_by_finger(chars)
+        .into_iter()
+        .map(|(finger, group)| {
+            let keys = group
+                .iter()
+                .map(|c| match c {
+                    ' ' => "space".to_string(),
+                    c => c.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            match finger {
+                Some(finger) => format!("{keys} ({})", finger.name()),
+                None => keys,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -424,7 +922,7 @@ mod tests {
         assert_eq!(thok.session.state.std_dev, 0.0);
         assert!(!thok.has_started());
         assert!(!thok.has_finished());
-        assert!(!thok.session.config.strict);
+        assert!(!thok.session.config.is_strict());
     }
 
     #[test]
@@ -555,6 +1053,22 @@ mod tests {
         assert_eq!(thok.session.state.seconds_remaining.unwrap(), expected_time);
     }
 
+    #[test]
+    fn test_on_tick_uses_configured_tick() {
+        let mut thok = Thok::new("test".to_string(), 1, Some(10.0), false);
+        thok.session.config.tick_ms = 25;
+        thok.session.start_countdown(1.0);
+
+        thok.on_tick();
+        assert_eq!(thok.session.state.countdown_remaining, Some(0.975));
+
+        thok.session.state.countdown_remaining = None;
+        for _ in 0..4 {
+            thok.on_tick();
+        }
+        assert!((thok.session.state.seconds_remaining.unwrap() - 9.9).abs() < 1e-9);
+    }
+
     #[test]
     fn test_calc_results_basic() {
         let mut thok = Thok::new("test".to_string(), 1, None, false);
@@ -699,6 +1213,153 @@ mod tests {
         assert!(thok.session.state.corrected_positions.contains(&1));
     }
 
+    #[test]
+    fn test_normal_mode_retype_marks_correction() {
+        let mut thok = Thok::with_stats_store(
+            "test".to_string(),
+            1,
+            None,
+            false,
+            Box::new(crate::stats::NoopStatsStore),
+        );
+
+        thok.write('t');
+        thok.write('x');
+        thok.backspace();
+        assert!(thok.corrected_positions().is_empty());
+
+        // A wrong retype leaves the error pending; the right one corrects it
+        thok.write('y');
+        assert!(thok.corrected_positions().is_empty());
+        thok.backspace();
+        thok.write('e');
+        assert_eq!(
+            thok.corrected_positions(),
+            &std::collections::HashSet::from([1])
+        );
+    }
+
+    #[test]
+    fn test_normal_mode_retyping_correct_char_is_not_a_correction() {
+        let mut thok = Thok::with_stats_store(
+            "test".to_string(),
+            1,
+            None,
+            false,
+            Box::new(crate::stats::NoopStatsStore),
+        );
+
+        thok.write('t');
+        thok.write('e');
+        thok.backspace();
+        thok.write('e');
+        assert!(thok.corrected_positions().is_empty());
+    }
+
+    #[test]
+    fn test_max_errors_ends_the_test() {
+        let mut thok = Thok::new("hello".to_string(), 1, None, false);
+        thok.session.config.max_errors = Some(1);
+
+        thok.write('x');
+        thok.backspace();
+        thok.write('h');
+        assert_eq!(thok.session.errors(), 1);
+        assert!(!thok.has_finished());
+
+        // Backspaced errors still count, so the second one goes over the limit
+        thok.write('x');
+        assert!(thok.has_finished());
+        assert!(thok.session.has_too_many_errors());
+        assert_eq!(
+            crate::exit_status::SessionResult::from_thok(&thok).errors,
+            2
+        );
+    }
+
+    #[test]
+    fn test_max_errors_forgiving_corrected_ones() {
+        let mut thok = Thok::new("hello".to_string(), 1, None, true);
+        thok.session.config.max_errors = Some(1);
+        thok.session.config.forgive_corrected = true;
+
+        // Fixed errors don't count, so only a standing one can go over the limit
+        for c in "xhxexl".chars() {
+            thok.write(c);
+        }
+        assert_eq!(thok.session.state.wrong_keystrokes, 3);
+        assert_eq!(thok.session.errors(), 0);
+        assert!(!thok.has_finished());
+
+        thok.write('x');
+        assert_eq!(thok.session.errors(), 1);
+        assert!(!thok.has_finished());
+
+        thok.session.config.max_errors = Some(0);
+        assert!(thok.has_finished());
+    }
+
+    #[test]
+    fn test_rewind_to_first_error() {
+        let mut thok = Thok::with_stats_store(
+            "hello world".to_string(),
+            2,
+            None,
+            false,
+            Box::new(crate::stats::NoopStatsStore),
+        );
+        thok.session.config.policy = crate::typing_policy::TypingPolicy::Lenient;
+
+        // Errors at 2, 8 and 9; the first is fixed by backspacing, so 8 is the first one left
+        for c in "hex".chars() {
+            thok.write(c);
+        }
+        thok.backspace();
+        for c in "llo woxxd".chars() {
+            thok.write(c);
+        }
+        assert_eq!(thok.cursor_pos(), 11);
+
+        thok.rewind_to_first_error();
+        assert_eq!(thok.cursor_pos(), 8);
+        assert_eq!(thok.input().len(), 8);
+        assert!(thok.input().iter().all(|i| i.outcome == Outcome::Correct));
+
+        // Retyping over the rewound error counts as a correction, like backspacing would
+        for c in "rld".chars() {
+            thok.write(c);
+        }
+        assert!(thok.has_finished());
+        assert_eq!(
+            thok.corrected_positions(),
+            &std::collections::HashSet::from([2, 8, 9])
+        );
+
+        // Nothing to rewind to: the input is left alone
+        thok.rewind_to_first_error();
+        assert_eq!(thok.cursor_pos(), 11);
+    }
+
+    #[test]
+    fn test_rewind_to_first_error_in_strict_mode() {
+        let mut thok = Thok::new("test".to_string(), 1, None, true);
+
+        thok.write('t');
+        thok.write('e');
+        thok.write('x');
+        thok.write('y');
+        assert_eq!(thok.cursor_pos(), 2);
+        assert_eq!(thok.input().len(), 3);
+
+        thok.rewind_to_first_error();
+        assert_eq!(thok.cursor_pos(), 2);
+        assert_eq!(thok.input().len(), 2);
+
+        thok.write('s');
+        assert_eq!(thok.cursor_pos(), 3);
+        assert!(thok.corrected_positions().contains(&2));
+    }
+
     #[test]
     fn test_strict_mode_backspace() {
         let mut thok = Thok::new("test".to_string(), 1, None, true);
@@ -724,6 +1385,44 @@ mod tests {
         assert_eq!(strict_thok.session.state.cursor_pos, 0);
     }
 
+    #[test]
+    fn test_policy_cursor_and_completion() {
+        use crate::typing_policy::TypingPolicy;
+
+        let typed_with_error = |policy: TypingPolicy| {
+            let mut thok = Thok::with_stats_store(
+                "ab".to_string(),
+                1,
+                None,
+                false,
+                Box::new(crate::stats::NoopStatsStore),
+            );
+            thok.session.config.policy = policy;
+            thok.write('x');
+            let cursor_after_error = thok.cursor_pos();
+            thok.write('b');
+            (thok, cursor_after_error)
+        };
+
+        let (normal, cursor) = typed_with_error(TypingPolicy::Normal);
+        assert_eq!(cursor, 1);
+        assert!(normal.has_finished());
+
+        let (strict, cursor) = typed_with_error(TypingPolicy::Strict);
+        assert_eq!(cursor, 0);
+        assert!(!strict.has_finished());
+
+        // Lenient moves on like normal but holds the test open until the error is fixed
+        let (mut lenient, cursor) = typed_with_error(TypingPolicy::Lenient);
+        assert_eq!(cursor, 1);
+        assert!(!lenient.has_finished());
+        lenient.backspace();
+        lenient.backspace();
+        lenient.write('a');
+        lenient.write('b');
+        assert!(lenient.has_finished());
+    }
+
     #[test]
     fn test_edge_case_empty_prompt() {
         let thok = Thok::new("".to_string(), 0, None, false);
@@ -936,46 +1635,1031 @@ mod tests {
     }
 
     #[test]
-    fn test_boundary_conditions_time_precision() {
-        let mut thok = Thok::new("test".to_string(), 1, Some(0.001), false);
-
-        assert!(thok.session.config.number_of_secs == Some(0.001));
+    fn test_write_after_completion_is_noop() {
+        for strict in [false, true] {
+            let mut thok = Thok::new("abc".to_string(), 1, None, strict);
+            for c in "abc".chars() {
+                thok.write(c);
+            }
+            assert!(thok.has_finished());
 
-        thok.session.state.started_at = Some(SystemTime::now());
-        thok.on_tick();
+            let input_before = thok.session.state.input.clone();
+            thok.write('d');
+            thok.write('e');
 
-        assert!(thok.has_finished());
+            assert_eq!(thok.session.state.cursor_pos, 3);
+            assert_eq!(thok.session.state.input, input_before);
+        }
     }
 
     #[test]
-    fn test_database_compaction_methods() {
-        let mut thok = Thok::new("test".to_string(), 1, None, false);
+    fn test_write_refused_when_cursor_at_end_of_timed_prompt() {
+        // A timed session that hasn't run out of time still refuses input past the prompt
+        let mut thok = Thok::new("ab".to_string(), 1, Some(30.0), false);
+        thok.write('a');
+        thok.write('b');
+        thok.session.state.input.pop();
+        thok.session.state.cursor_pos = 2;
+        assert!(!thok.has_finished());
 
-        if thok.has_stats_database() {
-            let info = thok.get_database_info();
-            if let Some((session_count, db_size, db_size_mb)) = info {
-                assert!(session_count >= 0);
-                assert!(db_size >= 0);
-                assert!(db_size_mb >= 0.0);
-            }
+        thok.write('z');
+
+        assert_eq!(thok.session.state.cursor_pos, 2);
+        assert_eq!(thok.session.state.input.len(), 1);
+    }
+
+    #[test]
+    fn test_longest_streak_clean_run() {
+        let mut thok = Thok::new("abc".to_string(), 1, None, false);
+        for c in "abc".chars() {
+            thok.write(c);
         }
+        assert_eq!(thok.longest_streak(), 3);
+    }
 
-        let compaction_result = thok.compact_database();
-        if thok.has_stats_database() {
-            assert!(compaction_result);
-        } else {
-            assert!(!compaction_result);
+    #[test]
+    fn test_longest_streak_broken_by_error() {
+        let mut thok = Thok::new("abcde".to_string(), 1, None, false);
+        for c in "abxde".chars() {
+            thok.write(c);
         }
+        assert_eq!(thok.longest_streak(), 2);
+        assert_eq!(thok.session.state.current_streak, 2);
     }
 
     #[test]
-    fn test_inter_keystroke_timing() {
-        let mut thok = Thok::new("hello".to_string(), 1, None, false);
+    fn test_longest_streak_strict_correction() {
+        let mut thok = Thok::new("abcd".to_string(), 1, None, true);
+        thok.write('a');
+        thok.write('x');
+        thok.write('y');
+        assert_eq!(thok.session.state.current_streak, 0);
+        for c in "bcd".chars() {
+            thok.write(c);
+        }
+        assert_eq!(thok.longest_streak(), 3);
+    }
 
-        println!("Testing inter-keystroke timing (simulating main app behavior)...");
+    #[test]
+    fn test_backspace_does_not_inflate_streak() {
+        let mut thok = Thok::new("abcd".to_string(), 1, None, false);
+        thok.write('a');
+        thok.write('b');
+        thok.backspace();
+        thok.backspace();
+        thok.write('a');
+        thok.write('b');
+        assert_eq!(thok.longest_streak(), 2);
+    }
 
-        thok.write('h');
-        thread::sleep(Duration::from_millis(150));
+    fn timed_run(store: Box<dyn StatsStore>, typed: &str, per_char: std::time::Duration) -> Thok {
+        let clock = crate::clock::MockClock::default();
+        let mut thok = Thok::with_stats_store("abcd".to_string(), 1, None, false, store);
+        thok.session.clock = std::sync::Arc::new(clock.clone());
+        for c in typed.chars() {
+            clock.advance(per_char);
+            thok.write(c);
+        }
+        thok.calc_results();
+        thok
+    }
+
+    #[test]
+    fn test_personal_best_celebrates_despite_errors() {
+        let store = Box::new(crate::stats::InMemoryStatsStore::default());
+
+        // The first run sets the bar but is not itself a personal best
+        let mut thok = timed_run(store, "abcd", std::time::Duration::from_millis(500));
+        assert!(!thok.is_personal_best);
+
+        // Faster but with a mistake: still a personal best
+        let store = thok.stats_db.take().unwrap();
+        let mut thok = timed_run(store, "abxd", std::time::Duration::from_millis(100));
+        assert!(thok.accuracy() < 100.0);
+        assert!(thok.is_personal_best);
+        thok.start_celebration_if_worthy(80, 24);
+        assert!(thok.celebration.is_active);
+
+        // Slower again: no personal best, no celebration for an imperfect run
+        let store = thok.stats_db.take().unwrap();
+        let mut thok = timed_run(store, "abxd", std::time::Duration::from_millis(400));
+        assert!(!thok.is_personal_best);
+        thok.start_celebration_if_worthy(80, 24);
+        assert!(!thok.celebration.is_active);
+    }
+
+    #[test]
+    fn test_suspicious_run_is_never_a_personal_best() {
+        let run = |store: Box<dyn StatsStore>, gap_ms: u64| {
+            let prompt = "the quick brown fox jumps";
+            let clock = crate::clock::MockClock::default();
+            let mut thok = Thok::with_stats_store(prompt.to_string(), 5, None, false, store);
+            thok.session.clock = std::sync::Arc::new(clock.clone());
+            for c in prompt.chars() {
+                thok.write(c);
+                clock.advance(Duration::from_millis(gap_ms));
+            }
+            thok.calc_results();
+            thok
+        };
+        let store = Box::new(crate::stats::InMemoryStatsStore::default());
+        let thok = run(store, 300);
+
+        // A paste beats the bar on paper but is not a real result
+        let thok = run(thok.stats_db.unwrap(), 1);
+        assert!(thok.is_session_suspicious());
+        assert!(!thok.is_personal_best);
+
+        let thok = run(thok.stats_db.unwrap(), 150);
+        assert!(thok.is_personal_best);
+    }
+
+    #[test]
+    fn test_stats_letters_only_skips_space_and_punctuation() {
+        let prompt = "ab, c";
+        let mut thok = Thok::with_stats_store(
+            prompt.to_string(),
+            2,
+            None,
+            false,
+            Box::new(crate::stats::InMemoryStatsStore::default()),
+        );
+        thok.stats_letters_only = true;
+        for c in prompt.chars() {
+            thok.write(c);
+        }
+        thok.calc_results();
+
+        let summary = thok
+            .stats_db
+            .as_ref()
+            .unwrap()
+            .get_all_char_summary()
+            .unwrap();
+        let mut recorded: Vec<char> = summary.iter().map(|(c, ..)| *c).collect();
+        recorded.sort();
+        assert_eq!(recorded, vec!['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn test_restart_same_prompt_keeps_prompt_config_and_store() {
+        let mut thok = Thok::with_stats_store(
+            "ab".to_string(),
+            1,
+            Some(30.0),
+            true,
+            Box::new(crate::stats::InMemoryStatsStore::default()),
+        );
+        thok.write('a');
+        thok.write('b');
+        thok.calc_results();
+        assert!(thok.wpm() > 0.0);
+
+        thok.restart_same_prompt();
+        assert_eq!(thok.session.prompt, "ab");
+        assert_eq!(thok.session.config.number_of_secs, Some(30.0));
+        assert!(thok.session.config.is_strict());
+        assert_eq!(thok.session.state.seconds_remaining, Some(30.0));
+        assert!(thok.session.state.input.is_empty());
+        assert_eq!(thok.session.state.cursor_pos, 0);
+        assert_eq!(thok.wpm(), 0.0);
+        assert!(!thok.has_started());
+
+        // The abandoned attempt's keystroke never reaches the store
+        thok.write('a');
+        thok.restart_same_prompt();
+        thok.write('a');
+        thok.write('b');
+        thok.calc_results();
+        let summary = thok
+            .stats_db
+            .as_ref()
+            .unwrap()
+            .get_all_char_summary()
+            .unwrap();
+        let attempts = summary.iter().find(|(c, ..)| *c == 'a').unwrap().3;
+        assert_eq!(attempts, 2);
+    }
+
+    #[test]
+    fn test_keystroke_stats_match_input() {
+        let mut thok = Thok::with_stats_store(
+            "abcde".to_string(),
+            1,
+            None,
+            false,
+            Box::new(crate::stats::NoopStatsStore),
+        );
+        assert_eq!(thok.keystroke_stats(), KeystrokeStats::default());
+
+        // A mistake fixed in strict mode, then one left standing
+        thok.session.config.policy = crate::typing_policy::TypingPolicy::Strict;
+        for c in "axbcdf".chars() {
+            thok.write(c);
+        }
+        thok.session.config.policy = crate::typing_policy::TypingPolicy::Normal;
+        thok.write('x');
+
+        let input = thok.input();
+        let stats = thok.keystroke_stats();
+        assert_eq!(stats.total, input.len());
+        assert_eq!(
+            stats.correct,
+            input
+                .iter()
+                .filter(|i| i.outcome == Outcome::Correct)
+                .count()
+        );
+        assert_eq!(stats.correct + stats.incorrect, stats.total);
+        assert_eq!(
+            stats,
+            KeystrokeStats {
+                total: 5,
+                correct: 4,
+                incorrect: 1,
+                corrected: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_per_minute_wpm_over_three_minutes() {
+        let clock = crate::clock::MockClock::default();
+        let mut thok = Thok::new("a".repeat(175), 35, None, false);
+        thok.stats_db = Some(Box::new(crate::stats::NoopStatsStore));
+        thok.session.clock = std::sync::Arc::new(clock.clone());
+
+        // 10 words in the first minute, 20 in the second, 5 in the first half of the third
+        for (count, gap_ms) in [(50, 1000), (100, 500), (25, 1000)] {
+            for _ in 0..count {
+                thok.write('a');
+                clock.advance(Duration::from_millis(gap_ms));
+            }
+            // Pause until the next minute starts; the test ends 2.5 minutes in
+            clock.advance(Duration::from_secs(if count == 25 { 5 } else { 10 }));
+        }
+        thok.calc_results();
+
+        assert_eq!(thok.per_minute_wpm(), vec![10.0, 20.0, 10.0]);
+    }
+
+    #[test]
+    fn test_per_minute_wpm_folds_in_a_short_last_minute() {
+        let clock = crate::clock::MockClock::default();
+        let mut thok = Thok::new("a".repeat(65), 13, None, false);
+        thok.stats_db = Some(Box::new(crate::stats::NoopStatsStore));
+        thok.session.clock = std::sync::Arc::new(clock.clone());
+
+        // A key a second for 61 seconds: the one-second tail is not a minute of its own
+        for _ in 0..61 {
+            thok.write('a');
+            clock.advance(Duration::from_secs(1));
+        }
+        thok.calc_results();
+
+        let per_minute = thok.per_minute_wpm();
+        assert_eq!(per_minute.len(), 1);
+        assert!((per_minute[0] - 12.0).abs() < 0.01, "{per_minute:?}");
+    }
+
+    #[test]
+    fn test_pasted_burst_marks_session_suspicious() {
+        let prompt = "the quick brown fox jumps";
+        let typed = |gap_ms: u64| {
+            let clock = crate::clock::MockClock::default();
+            let mut thok = Thok::with_stats_store(
+                prompt.to_string(),
+                5,
+                None,
+                false,
+                Box::new(crate::stats::InMemoryStatsStore::default()),
+            );
+            thok.session.clock = std::sync::Arc::new(clock.clone());
+            for c in prompt.chars() {
+                thok.write(c);
+                clock.advance(Duration::from_millis(gap_ms));
+            }
+            thok.calc_results();
+            thok
+        };
+
+        // A paste lands every character within a millisecond or two
+        let pasted = typed(1);
+        assert!(pasted.is_session_suspicious());
+        assert_eq!(
+            pasted.stats_db.as_ref().unwrap().get_best_wpm().unwrap(),
+            None
+        );
+
+        // Spaced out enough not to look like a burst, but far beyond any human's speed
+        let scripted = typed(20);
+        assert!(scripted.wpm() > crate::session::MAX_PLAUSIBLE_WPM);
+        assert!(scripted.is_session_suspicious());
+
+        // 150ms a key is a brisk but human 80 wpm
+        let typed_by_hand = typed(150);
+        assert!(!typed_by_hand.is_session_suspicious());
+        assert!(typed_by_hand.wpm() > 0.0);
+    }
+
+    #[test]
+    fn test_autorepeat_keystrokes_left_out_of_stats() {
+        let clock = crate::clock::MockClock::default();
+        let mut thok = Thok::with_stats_store(
+            "aaaab".to_string(),
+            1,
+            None,
+            false,
+            Box::new(crate::stats::InMemoryStatsStore::default()),
+        );
+        thok.session.clock = std::sync::Arc::new(clock.clone());
+
+        // A held 'a': the first press is real, the rest arrive faster than anyone types
+        for _ in 0..4 {
+            thok.write('a');
+            clock.advance(Duration::from_millis(5));
+        }
+        clock.advance(Duration::from_millis(200));
+        thok.write('b');
+        assert_eq!(thok.suspicious_inputs(), 3);
+        assert!(thok.has_finished());
+        thok.calc_results();
+
+        let summary = thok
+            .stats_db
+            .as_ref()
+            .unwrap()
+            .get_all_char_summary()
+            .unwrap();
+        let attempts: Vec<(char, i64)> = summary.iter().map(|(c, .., n)| (*c, *n)).collect();
+        assert!(attempts.contains(&('a', 1)));
+        assert!(attempts.contains(&('b', 1)));
+    }
+
+    #[test]
+    fn test_code_symbols_are_recorded() {
+        let prompt = "x => a::b();";
+        let mut thok = Thok::with_stats_store(
+            prompt.to_string(),
+            3,
+            None,
+            false,
+            Box::new(crate::stats::InMemoryStatsStore::default()),
+        );
+        for c in prompt.chars() {
+            thok.write(c);
+        }
+        thok.calc_results();
+
+        let summary = thok
+            .stats_db
+            .as_ref()
+            .unwrap()
+            .get_all_char_summary()
+            .unwrap();
+        for symbol in ['=', '>', ':', '(', ')', ';'] {
+            assert!(
+                summary.iter().any(|(c, ..)| *c == symbol),
+                "'{symbol}' recorded"
+            );
+        }
+    }
+
+    #[test]
+    fn test_strict_correction_counts_error_and_fix() {
+        let mut thok = Thok::with_stats_store(
+            "abcd".to_string(),
+            1,
+            None,
+            true,
+            Box::new(crate::stats::InMemoryStatsStore::default()),
+        );
+        // Strict mode holds the cursor on a miss, so each error is fixed by the next press
+        for c in ['a', 'x', 'b', 'q', 'c', 'd'] {
+            thok.write(c);
+        }
+
+        let summary: HashMap<char, (f64, Option<f64>)> = thok
+            .stats_db
+            .as_ref()
+            .unwrap()
+            .get_char_summary_with_deltas()
+            .unwrap()
+            .into_iter()
+            .map(|s| (s.character, (s.miss_rate, s.correction_rate)))
+            .collect();
+        assert_eq!(summary[&'a'], (0.0, None));
+        assert_eq!(summary[&'b'], (50.0, Some(100.0)));
+        assert_eq!(summary[&'c'], (50.0, Some(100.0)));
+        assert_eq!(summary[&'d'], (0.0, None));
+    }
+
+    #[test]
+    fn test_accented_prompt_completes_and_records_chars() {
+        let mut thok = Thok::with_stats_store(
+            "camión".to_string(),
+            1,
+            None,
+            false,
+            Box::new(crate::stats::InMemoryStatsStore::default()),
+        );
+        for c in "camión".chars() {
+            thok.write(c);
+        }
+        assert!(thok.has_finished());
+        assert!(thok.input().iter().all(|i| i.outcome == Outcome::Correct));
+
+        thok.calc_results();
+        assert_eq!(thok.accuracy(), 100.0);
+
+        let summary = thok
+            .stats_db
+            .as_ref()
+            .unwrap()
+            .get_all_char_summary()
+            .unwrap();
+        let accented = summary
+            .iter()
+            .find(|(c, ..)| *c == 'ó')
+            .expect("'ó' recorded");
+        assert_eq!(accented.3, 1);
+        assert_eq!(accented.2, 0.0);
+    }
+
+    #[test]
+    fn test_prompt_progress_clamps_at_end() {
+        let mut thok = Thok::new("abcd".to_string(), 1, None, false);
+        assert_eq!(thok.prompt_progress(), 0.0);
+        thok.write('a');
+        assert_eq!(thok.prompt_progress(), 0.25);
+        for c in "bcd".chars() {
+            thok.write(c);
+        }
+        assert_eq!(thok.prompt_progress(), 1.0);
+        assert_eq!(thok.words_remaining(), 0);
+        assert_eq!(thok.time_progress(), None);
+    }
+
+    #[test]
+    fn test_words_remaining() {
+        let mut thok = Thok::new("ab cd ef".to_string(), 3, None, false);
+        assert_eq!(thok.words_remaining(), 3);
+        thok.write('a');
+        assert_eq!(thok.words_remaining(), 3);
+        for c in "b c".chars() {
+            thok.write(c);
+        }
+        assert_eq!(thok.words_remaining(), 2);
+    }
+
+    /// `word_outcomes` with the accuracies rounded to whole percentages
+    fn rounded_word_outcomes(thok: &Thok) -> Vec<(String, f64)> {
+        thok.word_outcomes()
+            .into_iter()
+            .map(|(word, accuracy)| (word, accuracy.round()))
+            .collect()
+    }
+
+    #[test]
+    fn test_word_outcomes() {
+        let mut thok = Thok::new("hi, you there".to_string(), 3, None, false);
+        for c in "hi, yuo".chars() {
+            thok.write(c);
+        }
+        assert_eq!(
+            rounded_word_outcomes(&thok),
+            vec![("hi,".to_string(), 100.0), ("you".to_string(), 33.0)]
+        );
+
+        // A mistyped space counts against the word before it
+        let mut thok = Thok::new("ab cd".to_string(), 2, None, false);
+        for c in "abxcd".chars() {
+            thok.write(c);
+        }
+        assert_eq!(
+            rounded_word_outcomes(&thok),
+            vec![("ab".to_string(), 67.0), ("cd".to_string(), 100.0)]
+        );
+    }
+
+    #[test]
+    fn test_word_outcomes_give_half_credit_for_fixed_mistakes() {
+        let mut thok = Thok::new("ab cd".to_string(), 2, None, true);
+        // Strict mode keeps the cursor on the mistake until it is typed right
+        for c in "axb cd".chars() {
+            thok.write(c);
+        }
+        assert!(thok.has_finished());
+        assert_eq!(
+            rounded_word_outcomes(&thok),
+            vec![("ab".to_string(), 83.0), ("cd".to_string(), 100.0)]
+        );
+        assert!(Thok::new("ab".to_string(), 1, None, false)
+            .word_outcomes()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_elapsed_secs_starts_at_zero_and_only_grows() {
+        let clock = crate::clock::MockClock::default();
+        let mut thok = Thok::new("abc".to_string(), 1, Some(30.0), false);
+        thok.session.clock = std::sync::Arc::new(clock.clone());
+        clock.advance(std::time::Duration::from_secs(3));
+        assert_eq!(thok.elapsed_secs(), 0.0);
+
+        thok.write('a');
+        let mut last = thok.elapsed_secs();
+        assert!(last < 1e-9);
+        for _ in 0..5 {
+            clock.advance(std::time::Duration::from_millis(250));
+            let elapsed = thok.elapsed_secs();
+            assert!(elapsed > last, "{elapsed} after {last}");
+            last = elapsed;
+        }
+        assert!((last - 1.25).abs() < 1e-9);
+
+        assert_eq!(thok.number_of_words(), 1);
+        assert_eq!(thok.number_of_secs(), Some(30.0));
+    }
+
+    #[test]
+    fn test_ghost_position() {
+        // 60 wpm is 5 characters a second
+        assert_eq!(super::ghost_position(60.0, 0.0), 0);
+        assert_eq!(super::ghost_position(60.0, 1.0), 5);
+        assert_eq!(super::ghost_position(60.0, 2.5), 12);
+        assert_eq!(super::ghost_position(120.0, 1.0), 10);
+        assert_eq!(super::ghost_position(0.0, 10.0), 0);
+    }
+
+    #[test]
+    fn test_ghost_pos_follows_elapsed_time() {
+        let clock = crate::clock::MockClock::default();
+        let mut thok = Thok::new("abcdefghij".to_string(), 1, None, false);
+        thok.session.clock = std::sync::Arc::new(clock.clone());
+        thok.write('a');
+        assert_eq!(thok.ghost_pos(), None);
+
+        thok.ghost_wpm = Some(60.0);
+        assert_eq!(thok.ghost_pos(), Some(0));
+        clock.advance(std::time::Duration::from_secs(1));
+        assert_eq!(thok.ghost_pos(), Some(5));
+        // Past the end, the ghost waits on the last character
+        clock.advance(std::time::Duration::from_secs(5));
+        assert_eq!(thok.ghost_pos(), Some(9));
+    }
+
+    #[test]
+    fn test_time_progress_for_timed_tests() {
+        let mut thok = Thok::new("abcd".to_string(), 1, Some(10.0), false);
+        assert_eq!(thok.time_progress(), Some(0.0));
+        thok.write('a');
+        for _ in 0..25 {
+            thok.on_tick();
+        }
+        let progress = thok.time_progress().unwrap();
+        assert!((progress - 0.25).abs() < 1e-9, "{progress}");
+    }
+
+    #[test]
+    fn test_word_stats_split_on_whitespace_only() {
+        let clock = crate::clock::MockClock::default();
+        let mut thok = Thok::with_stats_store(
+            "hi, yo there".to_string(),
+            3,
+            None,
+            false,
+            Box::new(crate::stats::InMemoryStatsStore::default()),
+        );
+        thok.session.clock = std::sync::Arc::new(clock.clone());
+        // Each keystroke 100ms after the previous one, with a typo in the second word
+        for c in "hi, yx th".chars() {
+            clock.advance(std::time::Duration::from_millis(100));
+            thok.write(c);
+        }
+
+        let stats = thok.session.word_stats();
+        let summary: Vec<(&str, u64, bool)> = stats
+            .iter()
+            .map(|s| (s.word.as_str(), s.time_ms, s.clean))
+            .collect();
+        // The first word is timed from the first keystroke; "there" is unfinished
+        assert_eq!(summary, vec![("hi,", 200, true), ("yo", 200, false)]);
+
+        thok.calc_results();
+        assert_eq!(
+            thok.get_slowest_words_hint(3).as_deref(),
+            Some("slowest words: yo (200ms), hi, (200ms)")
+        );
+    }
+
+    #[test]
+    fn test_inter_key_intervals() {
+        let clock = crate::clock::MockClock::default();
+        let mut thok = Thok::with_stats_store(
+            "abcd".to_string(),
+            1,
+            None,
+            false,
+            Box::new(crate::stats::NoopStatsStore),
+        );
+        thok.session.clock = std::sync::Arc::new(clock.clone());
+        assert!(thok.inter_key_intervals().is_empty());
+
+        thok.write('a');
+        assert!(thok.inter_key_intervals().is_empty());
+        for (c, ms) in [('b', 120), ('c', 80), ('d', 450)] {
+            clock.advance(std::time::Duration::from_millis(ms));
+            thok.write(c);
+        }
+        assert_eq!(thok.inter_key_intervals(), vec![120, 80, 450]);
+    }
+
+    #[test]
+    fn test_accuracy_precise_keeps_fraction() {
+        let mut thok = Thok::with_stats_store(
+            "abcd".to_string(),
+            1,
+            None,
+            false,
+            Box::new(crate::stats::NoopStatsStore),
+        );
+        for c in "abxd".chars() {
+            thok.write(c);
+        }
+        thok.calc_results();
+        assert_eq!(thok.accuracy_precise(), 75.0);
+        assert_eq!(thok.accuracy(), 75.0);
+
+        let mut thok = Thok::with_stats_store(
+            "abc".to_string(),
+            1,
+            None,
+            false,
+            Box::new(crate::stats::NoopStatsStore),
+        );
+        for c in "axc".chars() {
+            thok.write(c);
+        }
+        thok.calc_results();
+        assert!((thok.accuracy_precise() - 200.0 / 3.0).abs() < 1e-9);
+        assert_eq!(thok.accuracy(), 67.0);
+    }
+
+    #[test]
+    fn test_save_results_rotates_log_with_old_columns() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("log.csv");
+        let old_log =
+            "date,num_words,num_secs,elapsed_secs,wpm,accuracy,std_dev\nMon,15,,12.00,60,95,1.20\n";
+        std::fs::write(&log, old_log).unwrap();
+
+        let mut thok = Thok::new("ab".to_string(), 1, None, false);
+        for c in "ab".chars() {
+            thok.write(c);
+        }
+        thok.calc_results();
+        thok.save_results_to(&log).unwrap();
+
+        let rotated = dir.path().join("log.csv.v1");
+        assert_eq!(std::fs::read_to_string(&rotated).unwrap(), old_log);
+        let current = std::fs::read_to_string(&log).unwrap();
+        let lines: Vec<&str> = current.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], LOG_HEADER.join(","));
+        assert_eq!(lines[1].split(',').count(), LOG_HEADER.len());
+
+        // A current log is appended to as before
+        thok.save_results_to(&log).unwrap();
+        assert_eq!(std::fs::read_to_string(&log).unwrap().lines().count(), 3);
+        assert!(!dir.path().join("log.csv.v2").exists());
+
+        // Another stale log doesn't overwrite the first rotation
+        std::fs::write(&log, old_log).unwrap();
+        thok.save_results_to(&log).unwrap();
+        assert_eq!(std::fs::read_to_string(&rotated).unwrap(), old_log);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("log.csv.v2")).unwrap(),
+            old_log
+        );
+    }
+
+    #[test]
+    fn test_checkpoint_round_trip_keeps_relative_times() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("checkpoint.json");
+        let clock = crate::clock::MockClock::default();
+        let mut thok = Thok::new("abcd".to_string(), 1, Some(30.0), true);
+        thok.session.clock = std::sync::Arc::new(clock.clone());
+        assert!(thok.session.checkpoint().is_none());
+
+        for c in "axb".chars() {
+            clock.advance(std::time::Duration::from_millis(400));
+            thok.write(c);
+        }
+        crate::session::save_checkpoint(&thok.session, &path).unwrap();
+        let checkpoint = crate::session::load_checkpoint(&path).unwrap();
+        assert_eq!(checkpoint.cursor_pos, 2);
+        assert_eq!(checkpoint.corrected_positions, vec![1]);
+        assert_eq!(checkpoint.input.last().unwrap().offset_ms, 800);
+
+        // Much later, the resumed test picks up from its last keystroke
+        let clock = crate::clock::MockClock::default();
+        clock.advance(std::time::Duration::from_secs(3600));
+        let mut resumed = Thok::new(String::new(), 1, Some(30.0), true);
+        resumed.session.clock = std::sync::Arc::new(clock.clone());
+        resumed.session.restore(&checkpoint);
+
+        assert_eq!(resumed.session.prompt, "abcd");
+        assert_eq!(resumed.cursor_pos(), 2);
+        assert!(resumed.corrected_positions().contains(&1));
+        assert_eq!(resumed.seconds_remaining(), Some(29.2));
+        let started_at = resumed.started_at().unwrap();
+        assert_eq!(
+            resumed.session.now().duration_since(started_at).unwrap(),
+            std::time::Duration::from_millis(800)
+        );
+
+        for c in "cd".chars() {
+            resumed.write(c);
+        }
+        assert!(resumed.has_finished());
+
+        crate::session::clear_checkpoint(&path);
+        assert!(crate::session::load_checkpoint(&path).is_none());
+    }
+
+    #[test]
+    fn test_format_by_finger() {
+        assert_eq!(
+            format_by_finger(&['s', ' ', 'x', 'é'], KeyboardLayout::Qwerty),
+            "s, x (left ring); space (thumb); é"
+        );
+        assert_eq!(
+            format_by_finger(&['s', 'x'], KeyboardLayout::Dvorak),
+            "s (right pinky); x (left index)"
+        );
+    }
+
+    #[test]
+    fn test_net_wpm_penalizes_uncorrected_errors() {
+        let per_char = std::time::Duration::from_millis(100);
+
+        let clean = timed_run(Box::new(crate::stats::NoopStatsStore), "abcd", per_char);
+        assert!(clean.wpm() > 0.0);
+        assert_eq!(clean.net_wpm(), clean.wpm());
+
+        let sloppy = timed_run(Box::new(crate::stats::NoopStatsStore), "abxd", per_char);
+        assert!(sloppy.wpm() > 0.0);
+        assert!(sloppy.net_wpm() < sloppy.wpm());
+        assert!(sloppy.net_wpm() >= 0.0);
+    }
+
+    fn counting_bell() -> (Bell, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        let rings = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = rings.clone();
+        let bell = Bell(Box::new(move || {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        }));
+        (bell, rings)
+    }
+
+    #[test]
+    fn test_strict_bell_rings_on_incorrect_only() {
+        let mut thok = Thok::new("ab".to_string(), 1, None, true);
+        let (bell, rings) = counting_bell();
+        thok.bell = Some(bell);
+
+        thok.write('x');
+        assert_eq!(thok.input()[0].outcome, Outcome::Incorrect);
+        assert_eq!(thok.cursor_pos(), 0);
+        assert_eq!(rings.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        thok.write('a');
+        thok.write('b');
+        assert_eq!(rings.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_bell_silent_without_sink_or_outside_strict() {
+        let mut thok = Thok::new("ab".to_string(), 1, None, true);
+        thok.write('x');
+        assert_eq!(thok.input()[0].outcome, Outcome::Incorrect);
+        assert!(thok.bell.is_none());
+
+        let mut thok = Thok::new("ab".to_string(), 1, None, false);
+        let (bell, rings) = counting_bell();
+        thok.bell = Some(bell);
+        thok.write('x');
+        assert_eq!(thok.input()[0].outcome, Outcome::Incorrect);
+        assert_eq!(rings.load(std::sync::atomic::Ordering::SeqCst), 0);
+    }
+
+    fn extending_thok(prompt: &str, number_of_secs: Option<f64>) -> Thok {
+        let mut thok = Thok::new(prompt.to_string(), 1, number_of_secs, false);
+        thok.prompt_extender = Some(PromptExtender(Box::new(|| "more words".to_string())));
+        thok
+    }
+
+    #[test]
+    fn test_timed_prompt_extends_instead_of_finishing() {
+        let mut thok = extending_thok("ab", Some(30.0));
+        thok.write('a');
+        thok.write('b');
+
+        assert!(!thok.has_finished());
+        assert_eq!(thok.session.prompt, "ab more words");
+
+        thok.write(' ');
+        thok.write('m');
+        assert_eq!(thok.session.state.cursor_pos, 4);
+        assert_eq!(thok.session.state.input[3].outcome, Outcome::Correct);
+    }
+
+    #[test]
+    fn test_untimed_prompt_ignores_extender() {
+        let mut thok = extending_thok("ab", None);
+        thok.write('a');
+        thok.write('b');
+
+        assert!(thok.has_finished());
+        assert_eq!(thok.session.prompt, "ab");
+    }
+
+    #[test]
+    fn test_countdown_blocks_input_until_expired() {
+        let mut thok = Thok::with_stats_store(
+            "hi".to_string(),
+            1,
+            Some(30.0),
+            false,
+            Box::new(crate::stats::NoopStatsStore),
+        );
+        thok.session.start_countdown(0.3);
+
+        thok.write('h');
+        assert!(thok.input().is_empty());
+        assert!(!thok.has_started());
+
+        for _ in 0..3 {
+            thok.on_tick();
+        }
+        assert_eq!(thok.countdown_remaining(), None);
+        assert_eq!(thok.seconds_remaining(), Some(30.0));
+
+        thok.write('h');
+        assert_eq!(thok.input().len(), 1);
+        assert!(thok.has_started());
+    }
+
+    #[test]
+    fn test_pause_freezes_timer_and_excludes_paused_time() {
+        let clock = crate::clock::MockClock::default();
+        let mut thok = Thok::with_stats_store(
+            "hello".to_string(),
+            1,
+            Some(30.0),
+            false,
+            Box::new(crate::stats::NoopStatsStore),
+        );
+        thok.session.clock = std::sync::Arc::new(clock.clone());
+
+        // Nothing to pause before the test starts
+        assert!(!thok.toggle_pause());
+
+        thok.write('h');
+        let started_at = thok.started_at().unwrap();
+        clock.advance(Duration::from_secs(2));
+
+        assert!(thok.toggle_pause());
+        for _ in 0..10 {
+            thok.on_tick();
+        }
+        assert_eq!(thok.seconds_remaining(), Some(30.0));
+
+        thok.write('e');
+        assert_eq!(thok.input().len(), 1);
+
+        clock.advance(Duration::from_secs(60));
+        assert!(!thok.toggle_pause());
+        assert_eq!(
+            thok.started_at().unwrap(),
+            started_at + Duration::from_secs(60)
+        );
+
+        thok.on_tick();
+        assert!(thok.seconds_remaining().unwrap() < 30.0);
+        thok.write('e');
+        assert_eq!(thok.input().len(), 2);
+    }
+
+    #[test]
+    fn test_results_leave_out_paused_time() {
+        let run = |pause: bool| {
+            let clock = crate::clock::MockClock::default();
+            let mut thok = Thok::with_stats_store(
+                "a".repeat(39),
+                8,
+                None,
+                false,
+                Box::new(crate::stats::NoopStatsStore),
+            );
+            thok.session.clock = std::sync::Arc::new(clock.clone());
+            for typed in 0..39 {
+                if pause && typed == 20 {
+                    assert!(thok.toggle_pause());
+                    let paused_elapsed = thok.elapsed_secs();
+                    clock.advance(Duration::from_secs(30));
+                    // The clock stands still while paused
+                    assert_eq!(thok.elapsed_secs(), paused_elapsed);
+                    assert!(!thok.toggle_pause());
+                }
+                thok.write('a');
+                clock.advance(Duration::from_millis(100));
+            }
+            thok.calc_results();
+            thok
+        };
+
+        // The same keys without the 30 s pause give the same results, second by second
+        let paused = run(true);
+        let straight = run(false);
+        assert_eq!(paused.wpm_coords(), straight.wpm_coords());
+        assert_eq!(paused.wpm_coords().len(), 4);
+        assert_eq!(paused.wpm(), straight.wpm());
+        assert_eq!(paused.std_dev(), straight.std_dev());
+    }
+
+    #[test]
+    fn test_live_wpm_and_accuracy() {
+        let clock = crate::clock::MockClock::default();
+        let mut thok = Thok::with_stats_store(
+            "hello world".to_string(),
+            2,
+            None,
+            false,
+            Box::new(crate::stats::NoopStatsStore),
+        );
+        thok.session.clock = std::sync::Arc::new(clock.clone());
+
+        assert_eq!(thok.live_wpm(), 0.0);
+        assert_eq!(thok.live_accuracy(), 0.0);
+
+        // First keystroke starts the clock; no time has passed yet
+        thok.write('h');
+        assert_eq!(thok.live_wpm(), 0.0);
+
+        for c in "elxo".chars() {
+            clock.advance(Duration::from_millis(250));
+            thok.write(c);
+        }
+        // 4 correct chars in 1s => 0.8 words * 60
+        assert_eq!(thok.live_wpm(), 48.0);
+        assert_eq!(thok.live_accuracy(), 80.0);
+        assert_eq!(thok.wpm(), 0.0);
+    }
+
+    #[test]
+    fn test_boundary_conditions_time_precision() {
+        let mut thok = Thok::new("test".to_string(), 1, Some(0.001), false);
+
+        assert!(thok.session.config.number_of_secs == Some(0.001));
+
+        thok.session.state.started_at = Some(SystemTime::now());
+        thok.on_tick();
+
+        assert!(thok.has_finished());
+    }
+
+    #[test]
+    fn test_database_compaction_methods() {
+        let mut thok = Thok::new("test".to_string(), 1, None, false);
+
+        if thok.has_stats_database() {
+            let info = thok.get_database_info();
+            if let Some((session_count, db_size, db_size_mb)) = info {
+                assert!(session_count >= 0);
+                assert!(db_size >= 0);
+                assert!(db_size_mb >= 0.0);
+            }
+        }
+
+        let compaction_result = thok.compact_database();
+        if thok.has_stats_database() {
+            assert!(compaction_result);
+        } else {
+            assert!(!compaction_result);
+        }
+    }
+
+    #[test]
+    fn test_inter_keystroke_timing() {
+        let mut thok = Thok::new("hello".to_string(), 1, None, false);
+
+        println!("Testing inter-keystroke timing (simulating main app behavior)...");
+
+        thok.write('h');
+        thread::sleep(Duration::from_millis(150));
         thok.write('e');
         thread::sleep(Duration::from_millis(120));
         thok.write('l');
@@ -1035,7 +2719,14 @@ mod tests {
 
     #[test]
     fn test_celebration_animation_imperfect_session() {
-        let mut thok = Thok::new("hello".to_string(), 1, None, false);
+        // No stored history, so this run cannot count as a personal best
+        let mut thok = Thok::with_stats_store(
+            "hello".to_string(),
+            1,
+            None,
+            false,
+            Box::new(crate::stats::NoopStatsStore),
+        );
 
         thok.write('h');
         thok.write('x');
@@ -1054,6 +2745,29 @@ mod tests {
         assert!(thok.celebration.particles.is_empty());
     }
 
+    #[test]
+    fn test_celebration_style_none_skips_perfect_session() {
+        let mut thok = Thok::with_stats_store(
+            "hi".to_string(),
+            1,
+            None,
+            false,
+            Box::new(crate::stats::NoopStatsStore),
+        );
+        thok.celebration.style = CelebrationStyle::None;
+        thok.write('h');
+        thok.write('i');
+        thok.calc_results();
+        assert_eq!(thok.accuracy(), 100.0);
+
+        thok.start_celebration_if_worthy(80, 24);
+        assert!(!thok.celebration.is_active);
+
+        // Retrying keeps the chosen style
+        thok.restart_same_prompt();
+        assert_eq!(thok.celebration.style, CelebrationStyle::None);
+    }
+
     #[test]
     fn test_fresh_database_with_realistic_timing() {
         let mut thok = Thok::new("hello world test".to_string(), 3, None, false);
@@ -1116,4 +2830,95 @@ mod tests {
         assert!(!was_idle, "Should return false when not exiting idle state");
         assert!(!thok.session.state.is_idle);
     }
+
+    fn idle_test_thok(idle_timeout_secs: f64) -> (Thok, crate::clock::MockClock) {
+        let clock = crate::clock::MockClock::default();
+        let mut thok = Thok::with_stats_store(
+            "test prompt".to_string(),
+            2,
+            None,
+            false,
+            Box::new(crate::stats::NoopStatsStore),
+        );
+        thok.session.clock = std::sync::Arc::new(clock.clone());
+        thok.session.config.idle_timeout_secs = idle_timeout_secs;
+        thok.write('t');
+        (thok, clock)
+    }
+
+    #[test]
+    fn test_idle_timeout_is_configurable() {
+        let (mut thok, clock) = idle_test_thok(30.0);
+        clock.advance(Duration::from_secs(31));
+        thok.on_tick();
+        assert!(thok.is_idle(), "default timeout should idle after 31s");
+
+        let (mut thok, clock) = idle_test_thok(600.0);
+        for _ in 0..10 {
+            clock.advance(Duration::from_secs(30));
+            thok.on_tick();
+        }
+        assert!(!thok.is_idle(), "large timeout should not idle after 300s");
+    }
+
+    #[test]
+    fn test_idle_keeps_active_time_of_timed_test() {
+        let clock = crate::clock::MockClock::default();
+        let mut thok = Thok::with_stats_store(
+            "test prompt".to_string(),
+            2,
+            Some(60.0),
+            false,
+            Box::new(crate::stats::NoopStatsStore),
+        );
+        thok.session.clock = std::sync::Arc::new(clock.clone());
+        thok.session.config.idle_timeout_secs = 30.0;
+        let tick_for = |thok: &mut Thok, secs: u64| {
+            for _ in 0..secs * 10 {
+                clock.advance(Duration::from_millis(100));
+                thok.on_tick();
+            }
+        };
+
+        thok.write('t');
+        tick_for(&mut thok, 5);
+        thok.write('e');
+        // Long enough to go idle, then a good while longer
+        tick_for(&mut thok, 120);
+        assert!(thok.is_idle());
+        // Idle time stands still like paused time
+        assert_eq!(thok.elapsed_secs(), 5.0);
+        let remaining = thok.seconds_remaining().unwrap();
+        assert!((remaining - 55.0).abs() < 0.01, "got {remaining}");
+
+        assert!(thok.mark_activity());
+        assert!(!thok.is_idle());
+        assert_eq!(thok.seconds_remaining(), Some(remaining));
+        assert_eq!(thok.elapsed_secs(), 5.0);
+        assert_eq!(thok.session.state.input.len(), 2);
+
+        tick_for(&mut thok, 1);
+        assert!((thok.seconds_remaining().unwrap() - 54.0).abs() < 0.01);
+        assert_eq!(thok.elapsed_secs(), 6.0);
+
+        for c in "st prompt".chars() {
+            thok.write(c);
+            tick_for(&mut thok, 1);
+        }
+        thok.calc_results();
+        // The keys from before going idle stay in their own seconds: only 't' in the first, and
+        // the 15 active seconds make up the one minute
+        assert_eq!(thok.wpm_coords()[0].wpm, 12.0);
+        assert_eq!(thok.per_minute_wpm(), vec![11.0 / 5.0 * 4.0]);
+    }
+
+    #[test]
+    fn test_zero_idle_timeout_disables_idle() {
+        let (mut thok, clock) = idle_test_thok(0.0);
+        for _ in 0..100 {
+            clock.advance(Duration::from_secs(3600));
+            thok.on_tick();
+        }
+        assert!(!thok.is_idle());
+    }
 }
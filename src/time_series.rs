@@ -21,3 +21,46 @@ impl From<TimeSeriesPoint> for (f64, f64) {
         (p.t, p.wpm)
     }
 }
+
+/// Each point's WPM replaced by the mean of it and up to `window - 1` points before it; the
+/// series keeps its length and timestamps
+pub fn rolling_average(points: &[TimeSeriesPoint], window: usize) -> Vec<TimeSeriesPoint> {
+    let window = window.max(1);
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let trailing = &points[(i + 1).saturating_sub(window)..=i];
+            let wpm = trailing.iter().map(|q| q.wpm).sum::<f64>() / trailing.len() as f64;
+            TimeSeriesPoint::new(p.t, wpm)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::std_dev;
+
+    #[test]
+    fn test_rolling_average_flattens_bursts() {
+        let raw: Vec<TimeSeriesPoint> = (1..=12)
+            .map(|t| TimeSeriesPoint::new(t as f64, if t % 2 == 0 { 90.0 } else { 30.0 }))
+            .collect();
+        let smoothed = rolling_average(&raw, 3);
+
+        assert_eq!(smoothed.len(), raw.len());
+        assert!(smoothed.iter().zip(&raw).all(|(s, r)| s.t == r.t));
+        assert_eq!(smoothed[0].wpm, 30.0);
+        assert_eq!(smoothed[1].wpm, 60.0);
+        assert_eq!(smoothed[2].wpm, 50.0);
+
+        let wpms = |series: &[TimeSeriesPoint]| series.iter().map(|p| p.wpm).collect::<Vec<_>>();
+        assert!(std_dev(&wpms(&smoothed)).unwrap() < std_dev(&wpms(&raw)).unwrap());
+
+        // A window of one (or zero) leaves the series as it was
+        assert_eq!(rolling_average(&raw, 1), raw);
+        assert_eq!(rolling_average(&raw, 0), raw);
+        assert!(rolling_average(&[], 3).is_empty());
+    }
+}
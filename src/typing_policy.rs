@@ -1,8 +1,41 @@
 use crate::stats::{extract_context, CharStat};
-use crate::thok::{Input, Outcome, Thok};
+use crate::thok::{Bell, Input, Outcome, Thok};
 use chrono::Local;
+use clap::ValueEnum;
 use std::time::SystemTime;
 
+/// How wrong keys are handled, selected with `--policy`
+#[derive(Debug, Copy, Clone, Default, PartialEq, ValueEnum, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum TypingPolicy {
+    /// Wrong keys are marked and the cursor moves on
+    #[default]
+    Normal,
+    /// The cursor stays on a wrong key until it is typed correctly
+    Strict,
+    /// Like normal, but the test only finishes once every error has been fixed
+    Lenient,
+}
+
+impl TypingPolicy {
+    /// The policy after this one, for the results screen's `3` key; wraps around
+    pub fn next(self) -> Self {
+        match self {
+            TypingPolicy::Normal => TypingPolicy::Strict,
+            TypingPolicy::Strict => TypingPolicy::Lenient,
+            TypingPolicy::Lenient => TypingPolicy::Normal,
+        }
+    }
+
+    /// Whether a test whose input covers the whole prompt may finish
+    pub fn accepts_completion(&self, input: &[Input]) -> bool {
+        match self {
+            TypingPolicy::Normal | TypingPolicy::Strict => true,
+            TypingPolicy::Lenient => input.iter().all(|i| i.outcome == Outcome::Correct),
+        }
+    }
+}
+
 const DEFAULT_KEYPRESS_MS: u64 = 150;
 
 fn calculate_time_to_press(thok: &Thok, now: SystemTime) -> u64 {
@@ -39,11 +72,18 @@ struct PreparedInput {
 }
 
 fn prepare_input(thok: &mut Thok, c: char) -> Option<PreparedInput> {
-    if thok.has_finished() {
+    // Refuse input past the end of the prompt so `cursor_pos <= prompt_char_count` always holds,
+    // even for library callers that keep writing after completion. Paused tests and tests still
+    // counting down take no input.
+    if thok.has_finished()
+        || thok.session.is_at_end()
+        || thok.session.state.paused
+        || thok.session.is_counting_down()
+    {
         return None;
     }
 
-    let idx = if thok.session.config.strict {
+    let idx = if thok.session.config.is_strict() {
         thok.session.state.cursor_pos
     } else {
         thok.session.state.input.len()
@@ -53,24 +93,47 @@ fn prepare_input(thok: &mut Thok, c: char) -> Option<PreparedInput> {
         thok.start();
     }
 
-    let now = SystemTime::now();
+    let now = thok.session.now();
     let expected_char = thok.get_expected_char(idx);
     let outcome = if c == expected_char {
         Outcome::Correct
     } else {
         Outcome::Incorrect
     };
+    // A correct press over an error still on screen (strict) or backspaced (normal) fixes it
+    let cursor = thok.session.state.cursor_pos;
+    let was_correction = outcome == Outcome::Correct
+        && (thok.session.state.erased_errors.contains(&cursor)
+            || (thok.session.config.is_strict()
+                && thok
+                    .session
+                    .state
+                    .input
+                    .get(cursor)
+                    .is_some_and(|input| input.outcome == Outcome::Incorrect)));
+
+    thok.session.record_streak(outcome);
+    if outcome == Outcome::Incorrect {
+        thok.session.state.wrong_keystrokes += 1;
+    }
 
     let time_to_press_ms = calculate_time_to_press(thok, now);
 
+    let autorepeat = thok.session.is_autorepeat(c, now);
+    if autorepeat {
+        thok.session.state.suspicious_inputs += 1;
+    }
+
     // Record char stat
-    if let Some(ref mut stats_db) = thok.stats_db {
+    let tracked = !autorepeat && (!thok.stats_letters_only || expected_char.is_alphanumeric());
+    if let Some(stats_db) = thok.stats_db.as_mut().filter(|_| tracked) {
         let (context_before, context_after) = extract_context(&thok.session.prompt, idx, 3);
         let stat = CharStat {
             character: expected_char.to_lowercase().next().unwrap_or(expected_char),
             time_to_press_ms,
             was_correct: outcome == Outcome::Correct,
             was_uppercase: expected_char.is_uppercase(),
+            was_correction,
             timestamp: Local::now(),
             context_before,
             context_after,
@@ -88,6 +151,8 @@ pub fn write_normal(thok: &mut Thok, c: char) {
     let Some(prepared) = prepare_input(thok, c) else {
         return;
     };
+    thok.session
+        .record_retype(thok.session.state.cursor_pos, prepared.outcome);
     thok.session.state.input.insert(
         thok.session.state.cursor_pos,
         Input {
@@ -112,6 +177,8 @@ pub fn write_strict(thok: &mut Thok, c: char) {
         keypress_start: thok.session.state.keypress_start_time,
     };
 
+    thok.session
+        .record_retype(thok.session.state.cursor_pos, prepared.outcome);
     if prepared.outcome == Outcome::Correct {
         let had_error = thok.session.state.cursor_pos < thok.session.state.input.len()
             && thok.session.state.input[thok.session.state.cursor_pos].outcome
@@ -128,19 +195,23 @@ pub fn write_strict(thok: &mut Thok, c: char) {
             thok.session.state.input.push(input);
         }
         thok.increment_cursor();
-    } else if thok.session.state.cursor_pos < thok.session.state.input.len() {
-        thok.session.state.input[thok.session.state.cursor_pos] = input;
     } else {
-        thok.session.state.input.push(input);
+        if thok.session.state.cursor_pos < thok.session.state.input.len() {
+            thok.session.state.input[thok.session.state.cursor_pos] = input;
+        } else {
+            thok.session.state.input.push(input);
+        }
+        if let Some(Bell(ring)) = thok.bell.as_mut() {
+            ring();
+        }
     }
 
     thok.session.state.keypress_start_time = None;
 }
 
 pub fn apply_write(thok: &mut Thok, c: char) {
-    if thok.session.config.strict {
-        write_strict(thok, c)
-    } else {
-        write_normal(thok, c)
+    match thok.session.config.policy {
+        TypingPolicy::Strict => write_strict(thok, c),
+        TypingPolicy::Normal | TypingPolicy::Lenient => write_normal(thok, c),
     }
 }
@@ -1,5 +1,11 @@
 pub mod character_stats;
 pub mod charting;
+pub mod fingers;
+pub mod heatmap;
+pub mod history;
+pub mod pomodoro;
+pub mod replay;
+pub mod rounds;
 pub mod screen;
 
 use ratatui::{
@@ -7,44 +13,104 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Axis, Chart, Dataset, GraphType, Paragraph, Widget, Wrap},
+    widgets::{
+        Axis, BarChart, Block, Chart, Dataset, GraphType, LineGauge, Paragraph, Row, Table, Widget,
+        Wrap,
+    },
 };
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 use webbrowser::Browser;
 
-use crate::{thok::Outcome, App, AppState};
+use crate::{
+    theme::Theme,
+    thok::{Outcome, Thok},
+    App, AppState, CursorStyle,
+};
+
+/// Drawn just left of the current character with `--cursor bar`
+const BAR_CURSOR: &str = "▏";
+
+/// Columns taken by the per-minute WPM table beside the results chart
+const PER_MINUTE_WIDTH: u16 = 13;
+
+/// Top-right corner note over the dimmed prompt with `--hide-cursor-on-idle`
+const IDLE_BADGE: &str = "idle - any key resumes";
+
+/// Stands in for a line break to type in `--code-file` prompts
+const NEWLINE_GLYPH: char = '↵';
+
+/// Shortest terminal that keeps the status bar; below it the row goes back to the prompt
+const STATUS_BAR_MIN_HEIGHT: u16 = 12;
 
 const HORIZONTAL_MARGIN: u16 = 5;
 const VERTICAL_MARGIN: u16 = 2;
 
+/// Below either of these the layouts can't fit, so only a notice is drawn
+const MIN_WIDTH: u16 = HORIZONTAL_MARGIN * 2 + 10;
+const MIN_HEIGHT: u16 = 5;
+const TOO_SMALL_MESSAGE: &str = "Terminal too small";
+
+/// Results note for a test cut short by `--max-errors`
+const TOO_MANY_ERRORS: &str = "failed: too many errors";
+/// Most lines the per-word review takes on the results screen before it is cut off
+const MAX_REVIEW_LINES: usize = 4;
+
 impl Widget for &App {
     fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+            render_too_small(area, buf);
+            return;
+        }
+
         let thok = &self.thok;
+        let theme = &self.render_settings.theme;
         // styles
         let bold_style = Style::default().add_modifier(Modifier::BOLD);
 
-        let green_bold_style = Style::default().patch(bold_style).fg(Color::Green);
-        let red_bold_style = Style::default().patch(bold_style).fg(Color::Red);
+        let correct_bold_style = Style::default().patch(bold_style).fg(theme.correct);
+        let incorrect_bold_style = Style::default().patch(bold_style).fg(theme.incorrect);
 
         let dim_bold_style = Style::default()
             .patch(bold_style)
+            .fg(theme.dim)
             .add_modifier(Modifier::DIM);
 
         let underlined_dim_bold_style = Style::default()
             .patch(dim_bold_style)
+            .fg(theme.cursor)
             .add_modifier(Modifier::UNDERLINED);
 
-        let italic_style = Style::default().add_modifier(Modifier::ITALIC);
+        // Block drops the dim so the inverted cell stands out from the untyped text
+        let block_cursor_style = Style::default()
+            .patch(bold_style)
+            .fg(theme.cursor)
+            .add_modifier(Modifier::REVERSED);
+
+        let bar_marker_style = Style::default().patch(bold_style).fg(theme.cursor);
 
-        let magenta_style = Style::default().fg(Color::Magenta);
+        let italic_style = Style::default().add_modifier(Modifier::ITALIC);
 
-        match (!thok.has_finished(), thok.is_idle()) {
-            (true, true) => {
-                // Idle state - show idle message
+        let accent_style = Style::default().fg(theme.accent);
+
+        let overlay = if let Some(remaining) = thok.countdown_remaining() {
+            Some(format!("Get ready... {}", remaining.ceil() as u64))
+        } else if thok.is_paused() {
+            Some("PAUSED - Press Ctrl+P to resume".to_string())
+        } else if thok.is_idle() && !self.render_settings.hide_cursor_on_idle {
+            Some("IDLE - Press any key to continue typing".to_string())
+        } else {
+            None
+        };
+        // With --hide-cursor-on-idle the prompt stays, dimmed and without a cursor, under a badge
+        let soft_idle = thok.is_idle() && overlay.is_none();
+
+        match (!thok.has_finished(), overlay) {
+            (true, Some(message)) => {
+                // Countdown, paused or idle - show the matching message
                 let idle_message = Paragraph::new(Span::styled(
-                    "IDLE - Press any key to continue typing",
+                    message,
                     Style::default()
-                        .fg(Color::Yellow)
+                        .fg(theme.warning)
                         .add_modifier(Modifier::BOLD | Modifier::ITALIC),
                 ))
                 .alignment(Alignment::Center)
@@ -52,21 +118,56 @@ impl Widget for &App {
 
                 idle_message.render(area, buf);
             }
-            (true, false) => {
+            (true, None) => {
+                // The bottom row holds the status bar, while there is height to spare
+                let (area, status_row) = if area.height >= STATUS_BAR_MIN_HEIGHT {
+                    (
+                        Rect {
+                            height: area.height - 1,
+                            ..area
+                        },
+                        Some(Rect {
+                            y: area.bottom() - 1,
+                            height: 1,
+                            ..area
+                        }),
+                    )
+                } else {
+                    (area, None)
+                };
+                if let Some(row) = status_row {
+                    render_status_bar(thok, row, buf, dim_bold_style);
+                }
+
                 let max_chars_per_line = area.width - (HORIZONTAL_MARGIN * 2);
-                let mut prompt_occupied_lines =
-                    ((thok.session.prompt.width() as f64 / max_chars_per_line as f64).ceil() + 1.0)
-                        as u16;
+                // The bar cursor takes a column of its own
+                let bar_width = u16::from(self.render_settings.cursor == CursorStyle::Bar);
+                let line_starts = wrap_line_starts(
+                    &thok.session.prompt,
+                    max_chars_per_line.saturating_sub(bar_width) as usize,
+                );
 
-                let time_left_lines = if thok.session.config.number_of_secs.is_some() {
+                let time_left_lines = if thok.number_of_secs().is_some() {
                     2
                 } else {
                     0
                 };
 
-                if thok.session.prompt.width() <= max_chars_per_line as usize {
-                    prompt_occupied_lines = 1;
-                }
+                let spelled_word = if self.render_settings.spell_out {
+                    current_word(&thok.session.prompt, thok.cursor_pos()).map(|w| spell_out(&w))
+                } else {
+                    None
+                };
+                let spell_out_lines = if spelled_word.is_some() { 2 } else { 0 };
+
+                // Long prompts scroll: leave room for the live stats above and progress bars below
+                let max_prompt_lines = area
+                    .height
+                    .saturating_sub(time_left_lines + spell_out_lines + 4)
+                    .max(1);
+                let prompt_occupied_lines = (line_starts.len() as u16).min(max_prompt_lines);
+                let scroll =
+                    prompt_scroll_offset(&line_starts, thok.cursor_pos(), prompt_occupied_lines);
 
                 let chunks = Layout::default()
                     .direction(Direction::Vertical)
@@ -77,6 +178,7 @@ impl Widget for &App {
                                 ((area.height as f64 - prompt_occupied_lines as f64) / 2.0) as u16,
                             ),
                             Constraint::Length(time_left_lines),
+                            Constraint::Length(spell_out_lines),
                             Constraint::Length(prompt_occupied_lines),
                             Constraint::Length(
                                 ((area.height as f64 - prompt_occupied_lines as f64) / 2.0) as u16,
@@ -86,70 +188,169 @@ impl Widget for &App {
                     )
                     .split(area);
 
-                // Preallocate spans vector to avoid reallocations
-                let input_len = thok.input().len();
-                let mut spans = Vec::with_capacity(input_len + 2); // +2 for cursor and remaining
+                // Every span is tagged with the prompt position it belongs to, so the spans can
+                // be split into the wrapped lines afterwards
+                let mut spans: Vec<(usize, Span)> =
+                    Vec::with_capacity(thok.session.prompt.len() + 1);
 
-                // Build spans for typed input, avoiding repeated string conversions
                 for (idx, input) in thok.input().iter().enumerate() {
                     match input.outcome {
                         Outcome::Incorrect => {
-                            let char_str = if input.char == ' ' {
-                                "·"
-                            } else {
-                                // For single chars, convert to String once
-                                spans.push(Span::styled(input.char.to_string(), red_bold_style));
-                                continue;
+                            let shown = match self.render_settings.incorrect_glyph {
+                                Some(glyph) => glyph,
+                                None if self.render_settings.show_expected => {
+                                    prompt_glyph(thok.get_expected_char(idx))
+                                }
+                                None => input.char,
                             };
-                            spans.push(Span::styled(char_str, red_bold_style));
+                            let shown = if shown == ' ' { '·' } else { shown };
+                            let char_str =
+                                fit_to_width(shown, prompt_glyph(thok.get_expected_char(idx)));
+                            spans.push((idx, Span::styled(char_str, incorrect_bold_style)));
                         }
                         Outcome::Correct => {
-                            let expected = thok.get_expected_char(idx);
-                            let style = if thok.session.config.strict
+                            let expected = prompt_glyph(thok.get_expected_char(idx));
+                            let style = if thok.session.config.is_strict()
                                 && thok.corrected_positions().contains(&idx)
                             {
-                                // Show corrected errors with orange color (much more distinct from green)
-                                Style::default()
-                                    .patch(bold_style)
-                                    .fg(Color::Rgb(255, 165, 0))
+                                // Show corrected errors in their own color, distinct from correct
+                                Style::default().patch(bold_style).fg(theme.corrected)
                             } else {
-                                green_bold_style
+                                correct_bold_style
                             };
-                            spans.push(Span::styled(expected.to_string(), style));
+                            spans.push((idx, Span::styled(expected.to_string(), style)));
                         }
                     }
                 }
 
-                spans.push(Span::styled(
-                    thok.get_expected_char(thok.cursor_pos()).to_string(),
-                    underlined_dim_bold_style,
-                ));
-
-                // Append the remaining prompt after the cursor using character indexing to avoid
-                // slicing by byte indices (handles Unicode safely)
-                // Preallocate remaining string with approximate capacity
                 let cursor_pos = thok.cursor_pos();
-                let remaining_len = thok
-                    .session
-                    .prompt
-                    .chars()
-                    .count()
-                    .saturating_sub(cursor_pos + 1);
-                let mut remaining = String::with_capacity(remaining_len);
-                remaining.extend(thok.session.prompt.chars().skip(cursor_pos + 1));
-                spans.push(Span::styled(remaining, dim_bold_style));
-
-                let widget = Paragraph::new(Line::from(spans))
-                    .alignment(if prompt_occupied_lines == 1 {
+                let cursor_char = prompt_glyph(thok.get_expected_char(cursor_pos)).to_string();
+                let caret_visible = self.caret_visible() && !soft_idle;
+                match self.render_settings.cursor {
+                    CursorStyle::Block if caret_visible => {
+                        spans.push((cursor_pos, Span::styled(cursor_char, block_cursor_style)));
+                    }
+                    CursorStyle::Underline if caret_visible => {
+                        spans.push((
+                            cursor_pos,
+                            Span::styled(cursor_char, underlined_dim_bold_style),
+                        ));
+                    }
+                    CursorStyle::Bar => {
+                        // A blank keeps the marker's column so the line doesn't shift as it blinks
+                        let marker = if caret_visible { BAR_CURSOR } else { " " };
+                        spans.push((cursor_pos, Span::styled(marker, bar_marker_style)));
+                        spans.push((cursor_pos, Span::styled(cursor_char, dim_bold_style)));
+                    }
+                    // Off phase of the blink: the character looks like the rest of the prompt
+                    _ => spans.push((cursor_pos, Span::styled(cursor_char, dim_bold_style))),
+                }
+
+                // The rest of the prompt, by char rather than byte index so Unicode is safe
+                spans.extend(
+                    thok.session
+                        .prompt
+                        .chars()
+                        .enumerate()
+                        .skip(cursor_pos + 1)
+                        .map(|(idx, c)| {
+                            (
+                                idx,
+                                Span::styled(prompt_glyph(c).to_string(), dim_bold_style),
+                            )
+                        }),
+                );
+
+                // The ghost shows where a typist at your best WPM would be by now
+                if let Some(ghost) = thok.ghost_pos().filter(|pos| *pos != cursor_pos) {
+                    let ghost_style = Style::default().bg(theme.accent).fg(Color::Black);
+                    for (_, span) in spans.iter_mut().filter(|(pos, _)| *pos == ghost) {
+                        span.style = span.style.patch(ghost_style);
+                    }
+                }
+
+                if soft_idle {
+                    let idle_style = Style::default().fg(theme.dim).add_modifier(Modifier::DIM);
+                    for (_, span) in spans.iter_mut() {
+                        span.style = span.style.patch(idle_style);
+                    }
+                }
+
+                let mut spans = spans.into_iter().peekable();
+                let lines: Vec<Line> = (0..line_starts.len())
+                    .map(|line| {
+                        let end = line_starts.get(line + 1).copied().unwrap_or(usize::MAX);
+                        let mut line_spans = Vec::new();
+                        while let Some((_, span)) = spans.next_if(|(pos, _)| *pos < end) {
+                            line_spans.push(span);
+                        }
+                        Line::from(line_spans)
+                    })
+                    .collect();
+
+                let widget = Paragraph::new(lines)
+                    .alignment(if line_starts.len() == 1 {
                         // when the prompt is small enough to fit on one line
                         // centering the text gives a nice zen feeling
                         Alignment::Center
                     } else {
                         Alignment::Left
                     })
-                    .wrap(Wrap { trim: true });
+                    .scroll((scroll, 0));
+
+                widget.render(chunks[3], buf);
+
+                if soft_idle {
+                    Paragraph::new(Span::styled(
+                        IDLE_BADGE,
+                        Style::default()
+                            .fg(theme.warning)
+                            .add_modifier(Modifier::ITALIC),
+                    ))
+                    .alignment(Alignment::Right)
+                    .render(Rect { height: 1, ..area }, buf);
+                }
+
+                if let Some(spelled) = spelled_word {
+                    Paragraph::new(Span::styled(spelled, italic_style.patch(bold_style)))
+                        .alignment(Alignment::Center)
+                        .render(chunks[2], buf);
+                }
+
+                if chunks[0].height > 0 {
+                    let attempt = if self.attempt > 1 {
+                        format!("attempt {}   ", self.attempt)
+                    } else {
+                        String::new()
+                    };
+                    let round = match &self.rounds {
+                        _ if self.warming_up => "warmup   ".to_string(),
+                        Some(rounds) => format!("round {}/{}   ", rounds.current(), rounds.target),
+                        None => String::new(),
+                    };
+                    let elapsed = if thok.session.config.zen {
+                        format!("{:.0}s   ", thok.elapsed_secs())
+                    } else {
+                        String::new()
+                    };
+                    let live_stats = Paragraph::new(Span::styled(
+                        format!(
+                            "{round}{attempt}{elapsed}{:.0} wpm   {:.0}% acc",
+                            thok.live_wpm(),
+                            thok.live_accuracy()
+                        ),
+                        dim_bold_style,
+                    ))
+                    .alignment(Alignment::Center);
 
-                widget.render(chunks[2], buf);
+                    live_stats.render(
+                        Rect {
+                            height: 1,
+                            ..chunks[0]
+                        },
+                        buf,
+                    );
+                }
 
                 if thok.seconds_remaining().is_some() {
                     let timer = Paragraph::new(Span::styled(
@@ -160,24 +361,123 @@ impl Widget for &App {
 
                     timer.render(chunks[1], buf);
                 }
+
+                // Thin progress bars along the bottom: time used (timed tests) above prompt typed
+                let bars = [
+                    thok.time_progress()
+                        .map(|ratio| ("time".to_string(), ratio)),
+                    // An endless prompt has no end to measure progress against
+                    (!thok.session.config.zen).then(|| {
+                        (
+                            format!("{} words left", thok.words_remaining()),
+                            thok.prompt_progress(),
+                        )
+                    }),
+                ];
+                let bars: Vec<(String, f64)> = bars.into_iter().flatten().collect();
+                let bottom = chunks[4];
+                if bottom.height as usize >= bars.len() {
+                    for (i, (label, ratio)) in bars.iter().enumerate() {
+                        LineGauge::default()
+                            .ratio(*ratio)
+                            .label(Span::styled(label.as_str(), dim_bold_style))
+                            .filled_style(accent_style)
+                            .unfilled_style(dim_bold_style)
+                            .render(
+                                Rect {
+                                    y: bottom.bottom() - (bars.len() - i) as u16,
+                                    height: 1,
+                                    ..bottom
+                                },
+                                buf,
+                            );
+                    }
+                }
+            }
+            (false, _) if self.render_settings.minimal => {
+                // Only the headline numbers and the controls, centered
+                let chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .horizontal_margin(HORIZONTAL_MARGIN)
+                    .vertical_margin(VERTICAL_MARGIN)
+                    .constraints([
+                        Constraint::Min(0),
+                        Constraint::Length(1),
+                        Constraint::Min(0),
+                        Constraint::Length(2),
+                    ])
+                    .split(area);
+
+                let mut headline = vec![Span::styled(
+                    format!("{} wpm   {}% acc", thok.wpm(), thok.accuracy()),
+                    bold_style,
+                )];
+                if thok.session.has_too_many_errors() {
+                    headline.push(Span::styled(
+                        format!("   {TOO_MANY_ERRORS}"),
+                        Style::default().fg(theme.incorrect).patch(bold_style),
+                    ));
+                }
+                Paragraph::new(Line::from(headline))
+                    .alignment(Alignment::Center)
+                    .render(chunks[1], buf);
+                results_legend(self, bold_style, italic_style)
+                    .alignment(Alignment::Center)
+                    .wrap(Wrap { trim: true })
+                    .render(chunks[3], buf);
+
+                if thok.celebration.is_active {
+                    render_celebration_particles(&thok.celebration, area, buf);
+                }
             }
             (false, _) => {
                 // Check if we're in the Results state to show settings
                 let show_settings = matches!(self.state, AppState::Results);
 
+                let slowest_words = thok.get_slowest_words_hint(3);
+                let repeat_summary = self
+                    .repeat_summary()
+                    .or_else(|| self.auto_advance_summary());
+                let held_keys = (thok.suspicious_inputs() > 0).then(|| {
+                    format!(
+                        "{} held-key repeats left out of the stats",
+                        thok.suspicious_inputs()
+                    )
+                });
+                let too_many_errors = thok.session.has_too_many_errors();
+                let review = word_review(thok, theme);
+                let review_width = area.width.saturating_sub(HORIZONTAL_MARGIN * 2 + 2) as usize;
+                let review_lines = if review.spans.is_empty() {
+                    0
+                } else {
+                    let words: Vec<&str> = thok.session.prompt.split_whitespace().collect();
+                    wrap_line_starts(&words.join(" "), review_width)
+                        .len()
+                        .min(MAX_REVIEW_LINES) as u16
+                        + 2
+                };
+                let stats_lines = 3
+                    + u16::from(too_many_errors)
+                    + u16::from(self.quote.is_some())
+                    + u16::from(slowest_words.is_some())
+                    + u16::from(repeat_summary.is_some())
+                    + u16::from(held_keys.is_some())
+                    + u16::from(self.progression_hint.is_some());
                 let constraints = if show_settings {
                     vec![
-                        Constraint::Min(1),    // chart
-                        Constraint::Length(1), // stats
-                        Constraint::Length(1), // session delta summary
-                        Constraint::Length(3), // settings info box
-                        Constraint::Length(1), // padding
-                        Constraint::Length(1), // legend
+                        Constraint::Min(1),               // chart
+                        Constraint::Length(review_lines), // per-word review
+                        Constraint::Length(stats_lines), // stats, keystrokes, focus hint (+ slowest words, repeats, held keys, progression, quote)
+                        Constraint::Length(1),           // session delta summary
+                        Constraint::Length(3),           // settings info box
+                        Constraint::Length(1),           // padding
+                        Constraint::Length(1),           // legend
                     ]
                 } else {
                     vec![
                         Constraint::Min(1),
-                        Constraint::Length(1),
+                        Constraint::Length(review_lines),
+                        Constraint::Length(stats_lines),
                         Constraint::Length(1), // for session delta summary
                         Constraint::Length(1), // for padding
                         Constraint::Length(1),
@@ -196,13 +496,39 @@ impl Widget for &App {
                     thok.seconds_remaining(),
                 );
 
-                let tuples: Vec<(f64, f64)> =
-                    thok.wpm_coords().iter().map(|p| (p.t, p.wpm)).collect();
-                let datasets = vec![Dataset::default()
+                let goal_wpm = self.render_settings.goal_wpm;
+                let highest_wpm = goal_wpm.map_or(highest_wpm, |goal| highest_wpm.max(goal.ceil()));
+
+                let tuples: Vec<(f64, f64)> = match self.render_settings.smooth {
+                    Some(window) => thok
+                        .wpm_coords_smoothed(window)
+                        .into_iter()
+                        .map(Into::into)
+                        .collect(),
+                    None => thok.wpm_coords().iter().map(|p| (p.t, p.wpm)).collect(),
+                };
+                let goal_tuples = goal_wpm
+                    .map(|goal| crate::ui::charting::goal_line(goal, [1.0, overall_duration]));
+                let mut datasets = vec![Dataset::default()
                     .marker(ratatui::symbols::Marker::Braille)
-                    .style(magenta_style)
+                    .style(accent_style)
                     .graph_type(GraphType::Line)
                     .data(&tuples)];
+                if let (Some(goal), Some(goal_tuples)) = (goal_wpm, goal_tuples.as_ref()) {
+                    // Green once the test finished at or above the goal, red while short of it
+                    let color = if thok.wpm() >= goal {
+                        theme.correct
+                    } else {
+                        theme.incorrect
+                    };
+                    datasets.push(
+                        Dataset::default()
+                            .marker(ratatui::symbols::Marker::Braille)
+                            .style(Style::default().fg(color))
+                            .graph_type(GraphType::Scatter)
+                            .data(goal_tuples),
+                    );
+                }
 
                 let chart = Chart::new(datasets)
                     .x_axis(
@@ -230,42 +556,135 @@ impl Widget for &App {
                             ]),
                     );
 
-                chart.render(chunks[0], buf);
+                // Tests longer than a minute get a per-minute WPM table at the end of the row
+                let per_minute = thok.per_minute_wpm();
+                let chart_row = if per_minute.len() > 1 {
+                    let panels = Layout::default()
+                        .direction(Direction::Horizontal)
+                        .constraints([Constraint::Min(0), Constraint::Length(PER_MINUTE_WIDTH)])
+                        .split(chunks[0]);
+                    let rows = per_minute.iter().enumerate().map(|(minute, wpm)| {
+                        Row::new(vec![(minute + 1).to_string(), format!("{wpm:.0}")])
+                    });
+                    Table::new(rows, [Constraint::Length(3), Constraint::Length(5)])
+                        .header(Row::new(vec!["min", "wpm"]).style(dim_bold_style))
+                        .block(Block::bordered().title("per min"))
+                        .render(panels[1], buf);
+                    panels[0]
+                } else {
+                    chunks[0]
+                };
+
+                // Keystroke rhythm histogram beside the WPM chart, once there are gaps to show
+                let intervals = thok.inter_key_intervals();
+                let chart_area = if intervals.is_empty() {
+                    chart_row
+                } else {
+                    let panels = Layout::default()
+                        .direction(Direction::Horizontal)
+                        .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
+                        .split(chart_row);
+                    let buckets = crate::ui::charting::rhythm_buckets(&intervals);
+                    let data: Vec<(&str, u64)> =
+                        buckets.iter().map(|(l, c)| (l.as_str(), *c)).collect();
+                    let inner_width = panels[1].width.saturating_sub(2);
+                    let bar_width = (inner_width / data.len() as u16).saturating_sub(1).max(1);
+                    BarChart::default()
+                        .block(Block::bordered().title("gap ms"))
+                        .data(data.as_slice())
+                        .bar_width(bar_width)
+                        .bar_style(accent_style)
+                        .label_style(dim_bold_style)
+                        .render(panels[1], buf);
+                    panels[0]
+                };
+                chart.render(chart_area, buf);
 
-                let stats = Paragraph::new(Span::styled(
+                let mut stats_text = vec![Line::from(Span::styled(
                     format!(
-                        "{} wpm   {}% acc   {:.2} sd",
+                        "{} wpm   {} net   {}% acc   {:.2} sd   {} streak",
                         thok.wpm(),
+                        thok.net_wpm(),
                         thok.accuracy(),
-                        thok.std_dev()
+                        thok.std_dev(),
+                        thok.longest_streak()
                     ),
                     bold_style,
-                ))
-                .alignment(Alignment::Center);
+                ))];
+                let keys = thok.keystroke_stats();
+                stats_text.push(Line::from(Span::styled(
+                    format!(
+                        "{} keys   {} correct   {} incorrect   {} corrected",
+                        keys.total, keys.correct, keys.incorrect, keys.corrected
+                    ),
+                    dim_bold_style,
+                )));
+                if too_many_errors {
+                    stats_text.push(Line::from(Span::styled(
+                        TOO_MANY_ERRORS,
+                        Style::default().fg(theme.incorrect).patch(bold_style),
+                    )));
+                }
+                stats_text.push(Line::from(Span::styled(
+                    thok.get_weakest_characters_hint(3, self.render_settings.layout),
+                    italic_style,
+                )));
+                if let Some(slowest) = slowest_words {
+                    stats_text.push(Line::from(Span::styled(slowest, italic_style)));
+                }
+                if let Some(repeats) = repeat_summary {
+                    stats_text.push(Line::from(Span::styled(repeats, italic_style)));
+                }
+                if let Some(held) = held_keys {
+                    stats_text.push(Line::from(Span::styled(
+                        held,
+                        Style::default().fg(theme.warning),
+                    )));
+                }
+                if let Some(hint) = &self.progression_hint {
+                    stats_text.push(Line::from(Span::styled(
+                        hint.clone(),
+                        Style::default().fg(theme.correct),
+                    )));
+                }
+                if let Some(quote) = &self.quote {
+                    stats_text.push(Line::from(Span::styled(
+                        format!("— {}", quote.author),
+                        italic_style,
+                    )));
+                }
+                let stats = Paragraph::new(stats_text).alignment(Alignment::Center);
+
+                if review_lines > 0 {
+                    Paragraph::new(review)
+                        .block(Block::bordered().title("words"))
+                        .wrap(Wrap { trim: true })
+                        .render(chunks[1], buf);
+                }
 
-                stats.render(chunks[1], buf);
+                stats.render(chunks[2], buf);
 
                 // Render session delta summary
                 let delta_summary = thok.get_session_delta_summary();
                 let delta_widget = Paragraph::new(Span::styled(
                     delta_summary,
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme.title)
                         .add_modifier(Modifier::ITALIC),
                 ))
                 .alignment(Alignment::Center);
 
-                delta_widget.render(chunks[2], buf);
+                delta_widget.render(chunks[3], buf);
 
                 // Render settings info box if in Results state
                 if show_settings {
                     let settings_text = format!(
-                        "Settings: Words: {} | Lang: {} | Random: {} | Caps: {} | Strict: {} | Symbols: {} | Subst: {}\n(w) Words (l) Language (1) Random (2) Caps (3) Strict (4) Symbols (5) Substitute",
+                        "Settings: Words: {} | Lang: {} | Random: {} | Caps: {} | Policy: {} | Symbols: {} | Subst: {}\n(w) Words (l) Language (1) Random (2) Caps (3) Policy (4) Symbols (5) Substitute",
                         self.runtime_settings.number_of_words,
                         self.runtime_settings.supported_language,
                         if self.runtime_settings.random_words { "ON" } else { "OFF" },
                         if self.runtime_settings.capitalize { "ON" } else { "OFF" },
-                        if self.runtime_settings.strict { "ON" } else { "OFF" },
+                        self.runtime_settings.policy,
                         if self.runtime_settings.symbols { "ON" } else { "OFF" },
                         if self.runtime_settings.substitute { "ON" } else { "OFF" }
                     );
@@ -273,26 +692,18 @@ impl Widget for &App {
                     let settings_widget = Paragraph::new(settings_text)
                         .style(
                             Style::default()
-                                .fg(Color::Gray)
+                                .fg(theme.dim)
                                 .add_modifier(Modifier::ITALIC),
                         )
                         .alignment(Alignment::Center)
                         .wrap(Wrap { trim: true });
 
-                    settings_widget.render(chunks[3], buf);
+                    settings_widget.render(chunks[4], buf);
                 }
 
-                let legend_chunk_index = if show_settings { 5 } else { 4 };
-                let legend = Paragraph::new(Span::styled(
-                    String::from(if Browser::is_available() {
-                        "(r)etry / (n)ew / (s)tats / (t)weet / (esc)ape"
-                    } else {
-                        "(r)etry / (n)ew / (s)tats / (esc)ape"
-                    }),
-                    italic_style,
-                ));
-
-                legend.render(chunks[legend_chunk_index], buf);
+                let legend_chunk_index = if show_settings { 6 } else { 5 };
+                results_legend(self, bold_style, italic_style)
+                    .render(chunks[legend_chunk_index], buf);
 
                 // Render celebration animation if active
                 if thok.celebration.is_active {
@@ -303,6 +714,200 @@ impl Widget for &App {
     }
 }
 
+/// Controls line under the results: the retry or next-round notice when one is pending
+fn results_legend(app: &App, bold_style: Style, italic_style: Style) -> Paragraph<'static> {
+    let theme = &app.render_settings.theme;
+    let min_accuracy = app.cli.as_ref().and_then(|cli| cli.min_accuracy);
+    let pending_round = app.rounds.as_ref().filter(|r| !r.is_over());
+    match (app.auto_retry_at, min_accuracy, pending_round) {
+        (Some(_), Some(min), _) => Paragraph::new(Span::styled(
+            format!(
+                "Below {min}% accuracy - retrying (attempt {}) / (esc)ape",
+                app.attempt + 1
+            ),
+            Style::default().fg(theme.warning).patch(bold_style),
+        )),
+        (_, _, Some(rounds)) => Paragraph::new(Span::styled(
+            format!(
+                "Round {}/{} done - (enter) next round / (esc)ape",
+                rounds.summaries.len(),
+                rounds.target
+            ),
+            Style::default().fg(theme.warning).patch(bold_style),
+        )),
+        _ if app.auto_advance_at.is_some() => Paragraph::new(Span::styled(
+            "Next prompt coming up / (esc)ape",
+            Style::default().fg(theme.warning).patch(bold_style),
+        )),
+        _ => Paragraph::new(Span::styled(
+            String::from(if can_share(app) {
+                "(r)etry / (n)ew / (s)tats / (h)istory / (k)eys / (p)lay / (t)weet / (esc)ape"
+            } else {
+                "(r)etry / (n)ew / (s)tats / (h)istory / (k)eys / (p)lay / (esc)ape"
+            }),
+            italic_style,
+        )),
+    }
+}
+
+/// The prompt's words for review after a test, each on a green background when typed
+/// without a mistake and red otherwise; words the test never reached stay dim
+fn word_review(thok: &Thok, theme: &Theme) -> Line<'static> {
+    let outcomes = thok.word_outcomes();
+    if outcomes.is_empty() {
+        return Line::default();
+    }
+    let mut spans = Vec::new();
+    for (idx, word) in thok.session.prompt.split_whitespace().enumerate() {
+        if idx > 0 {
+            spans.push(Span::raw(" "));
+        }
+        let style = match outcomes.get(idx) {
+            Some((_, accuracy)) if *accuracy >= 100.0 => {
+                Style::default().fg(Color::Black).bg(theme.correct)
+            }
+            Some(_) => Style::default().fg(Color::Black).bg(theme.incorrect),
+            None => Style::default().fg(theme.dim).add_modifier(Modifier::DIM),
+        };
+        spans.push(Span::styled(word.to_string(), style));
+    }
+    Line::from(spans)
+}
+
+/// Whether `t` on the results screen has somewhere to send the results
+fn can_share(app: &App) -> bool {
+    !app.render_settings.share_template.trim().is_empty() && Browser::is_available()
+}
+
+/// How a prompt character is drawn: newlines in code prompts show as a typeable `↵`
+fn prompt_glyph(c: char) -> char {
+    if c == '\n' {
+        NEWLINE_GLYPH
+    } else {
+        c
+    }
+}
+
+/// One row of session info under the prompt: elapsed time, time left on timed tests, words
+/// typed out of the prompt's total (just the count for endless prompts) and accuracy so far
+fn render_status_bar(thok: &Thok, area: Rect, buf: &mut Buffer, style: Style) {
+    let mut fields = vec![format!("{:.0}s", thok.elapsed_secs())];
+    if let Some(remaining) = thok.seconds_remaining() {
+        fields.push(format!("{:.0}s left", remaining.max(0.0).ceil()));
+    }
+    let words_done = thok.words_total() - thok.words_remaining();
+    fields.push(if thok.session.config.zen {
+        format!("{words_done} words")
+    } else {
+        format!("{words_done}/{} words", thok.words_total())
+    });
+    fields.push(format!("{:.0}% acc", thok.live_accuracy()));
+
+    Paragraph::new(Span::styled(fields.join(" │ "), style))
+        .alignment(Alignment::Center)
+        .render(area, buf);
+}
+
+/// Char index where each line of `prompt` starts when word-wrapped to `width` columns.
+/// A word keeps the whitespace after it on its own line; words wider than a line are split.
+/// A newline always ends its line.
+fn wrap_line_starts(prompt: &str, width: usize) -> Vec<usize> {
+    let width = width.max(1);
+    let chars: Vec<char> = prompt.chars().collect();
+    let char_width = |c: &char| prompt_glyph(*c).width().unwrap_or(0);
+    let mut starts = vec![0];
+    let mut line_width = 0;
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        let word_end = chars[idx..]
+            .iter()
+            .position(|c| c.is_whitespace())
+            .map_or(chars.len(), |p| idx + p);
+        let token_end = match chars[word_end..]
+            .iter()
+            .position(|c| !c.is_whitespace() || *c == '\n')
+        {
+            Some(p) if chars[word_end + p] == '\n' => word_end + p + 1,
+            Some(p) => word_end + p,
+            None => chars.len(),
+        };
+        let token_width: usize = chars[idx..token_end].iter().map(char_width).sum();
+
+        if line_width > 0 && line_width + token_width > width {
+            starts.push(idx);
+            line_width = 0;
+        }
+        if token_width > width {
+            for (offset, c) in chars[idx..token_end].iter().enumerate() {
+                if line_width > 0 && line_width + char_width(c) > width {
+                    starts.push(idx + offset);
+                    line_width = 0;
+                }
+                line_width += char_width(c);
+            }
+        } else {
+            line_width += token_width;
+        }
+        if chars[token_end - 1] == '\n' && token_end < chars.len() {
+            starts.push(token_end);
+            line_width = 0;
+        }
+        idx = token_end;
+    }
+    starts
+}
+
+/// `shown` drawn in the columns `expected` takes up, so wide prompt characters don't shift the
+/// rest of the line: narrower characters are padded, wider ones give way to `expected`
+pub(crate) fn fit_to_width(shown: char, expected: char) -> String {
+    let shown_width = shown.width().unwrap_or(0);
+    let expected_width = expected.width().unwrap_or(0);
+    if shown_width > expected_width {
+        expected.to_string()
+    } else {
+        format!("{shown}{}", " ".repeat(expected_width - shown_width))
+    }
+}
+
+/// First wrapped line to show so the cursor's line stays centered in `visible` lines
+fn prompt_scroll_offset(line_starts: &[usize], cursor_pos: usize, visible: u16) -> u16 {
+    let cursor_line = line_starts.partition_point(|start| *start <= cursor_pos) - 1;
+    let last_offset = line_starts.len().saturating_sub(visible as usize);
+    cursor_line
+        .saturating_sub(visible as usize / 2)
+        .min(last_offset) as u16
+}
+
+/// The prompt word under (or, on whitespace, right after) the cursor
+fn current_word(prompt: &str, cursor_pos: usize) -> Option<String> {
+    let chars: Vec<char> = prompt.chars().collect();
+    if cursor_pos >= chars.len() {
+        return None;
+    }
+    let mut start = cursor_pos;
+    while start < chars.len() && chars[start].is_whitespace() {
+        start += 1;
+    }
+    while start > 0 && !chars[start - 1].is_whitespace() {
+        start -= 1;
+    }
+    let end = chars[start..]
+        .iter()
+        .position(|c| c.is_whitespace())
+        .map_or(chars.len(), |offset| start + offset);
+
+    (start < end).then(|| chars[start..end].iter().collect())
+}
+
+/// Letters of a word separated by spaces, e.g. "h e l l o"
+fn spell_out(word: &str) -> String {
+    word.chars()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Render celebration particles on top of the results screen
 fn render_celebration_particles(
     celebration: &crate::celebration::CelebrationAnimation,
@@ -357,6 +962,25 @@ fn render_celebration_particles(
     }
 }
 
+/// A centered notice in place of the normal layout, wrapped to fit very narrow terminals
+fn render_too_small(area: Rect, buf: &mut Buffer) {
+    if area.width == 0 || area.height == 0 {
+        return;
+    }
+    let lines = (TOO_SMALL_MESSAGE.width() as u16).div_ceil(area.width);
+    let top = area.height.saturating_sub(lines) / 2;
+    let centered = Rect {
+        y: area.y + top,
+        height: area.height - top,
+        ..area
+    };
+    Paragraph::new(TOO_SMALL_MESSAGE)
+        .style(Style::default().add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .render(centered, buf);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -365,7 +989,7 @@ mod tests {
     use std::time::SystemTime;
 
     fn create_test_app(prompt: &str, finished: bool) -> App {
-        use crate::{RuntimeSettings, SupportedLanguage};
+        use crate::{typing_policy::TypingPolicy, RuntimeSettings, SupportedLanguage};
         let mut thok = Thok::new(prompt.to_string(), 1, None, false);
 
         if finished {
@@ -388,6 +1012,50 @@ mod tests {
             ];
         }
 
+        let runtime_settings = RuntimeSettings {
+            number_of_words: 15,
+            number_of_sentences: None,
+            number_of_secs: None,
+            supported_language: SupportedLanguage::English,
+            progression: false,
+            random_words: false,
+            capitalize: false,
+            policy: TypingPolicy::Normal,
+            symbols: false,
+            balanced_symbols: false,
+            uppercase_drill: false,
+            title_case: false,
+            targeted_sentences: false,
+            substitute: false,
+            fill_time: false,
+            zen: false,
+            quote: false,
+            wordlist: None,
+            prompt_file: None,
+            numbers: false,
+            number_format: crate::word_generator::NumberFormat::default(),
+            sentence_words: false,
+            keys: None,
+            mix: None,
+            target_aggression: 0.3,
+            recency_halflife: None,
+            target: crate::language::SelectionTarget::Combined,
+            seed: None,
+            profile: None,
+            idle_timeout: crate::session::DEFAULT_IDLE_TIMEOUT_SECS,
+            tick_ms: crate::thok::TICK_RATE_MS,
+            countdown: None,
+            bell: false,
+            repeat: false,
+            stats_letters_only: false,
+            max_errors: None,
+            forgive_corrected: false,
+            ghost: false,
+            celebration: crate::celebration::CelebrationStyle::Confetti,
+        };
+        let generator =
+            crate::word_generator::WordGenerator::new(runtime_settings.to_word_gen_config(None));
+
         App {
             cli: None,
             thok,
@@ -397,18 +1065,29 @@ mod tests {
                 crate::AppState::Typing
             },
             char_stats_state: crate::CharStatsState::default(),
-            runtime_settings: RuntimeSettings {
-                number_of_words: 15,
-                number_of_sentences: None,
-                number_of_secs: None,
-                supported_language: SupportedLanguage::English,
-                random_words: false,
-                capitalize: false,
-                strict: false,
-                symbols: false,
-                substitute: false,
-            },
+            heatmap_metric: crate::HeatmapMetric::default(),
+            runtime_settings,
+            render_settings: crate::RenderSettings::default(),
             config_store: Box::new(crate::config::FileConfigStore::default()),
+            history: Vec::new(),
+            pomodoro: None,
+            rounds: None,
+            last_result: None,
+            last_report: None,
+            last_keylog: None,
+            quote: None,
+            replay: None,
+            attempt: 1,
+            auto_retry_at: None,
+            repeat_wpms: Vec::new(),
+            auto_advance_at: None,
+            auto_advanced: Vec::new(),
+            checkpoint_path: None,
+            keystrokes_since_checkpoint: 0,
+            warming_up: false,
+            caret_ticks: 0,
+            progression_hint: None,
+            generator,
         }
     }
 
@@ -448,6 +1127,76 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_wrap_line_starts() {
+        assert_eq!(wrap_line_starts("", 10), vec![0]);
+        assert_eq!(wrap_line_starts("aaa bbb ccc", 8), vec![0, 8]);
+        // A word's trailing space has to fit too
+        assert_eq!(wrap_line_starts("aaa bbb ccc", 7), vec![0, 4]);
+        assert_eq!(wrap_line_starts("aaa bbb ccc", 6), vec![0, 4, 8]);
+        // Words wider than a line are split
+        assert_eq!(wrap_line_starts("abcdefgh ij", 3), vec![0, 3, 6, 9]);
+        // Newlines in code prompts always break the line
+        assert_eq!(wrap_line_starts("a {\n    b\n}", 40), vec![0, 4, 10]);
+        assert_eq!(prompt_scroll_offset(&[0, 4, 8, 12, 16], 13, 3), 2);
+        assert_eq!(prompt_scroll_offset(&[0, 4, 8, 12, 16], 17, 3), 2);
+        assert_eq!(prompt_scroll_offset(&[0, 4, 8], 9, 5), 0);
+    }
+
+    #[test]
+    fn test_long_prompt_scrolls_with_cursor() {
+        // 60 four-column words, 7 to a 30-column line: 9 lines, 6 of which fit in 10 rows
+        let prompt: String = (0..60).map(|i| format!("w{i:02} ")).collect();
+        let prompt = prompt.trim_end();
+        let area = Rect::new(0, 0, 40, 10);
+        let mut app = create_test_app(prompt, false);
+
+        let rendered = render_to_string(&app, area);
+        assert!(rendered.contains("w00"));
+        assert!(!rendered.contains("w55"));
+
+        // Cursor at the start of the fifth line: the first line scrolls away
+        for c in prompt.chars().take(28 * 4) {
+            app.thok.write(c);
+        }
+        let rendered = render_to_string(&app, area);
+        assert!(!rendered.contains("w00"));
+        assert!(rendered.contains("w28"));
+
+        // Backspacing over the line boundary scrolls back up
+        app.thok.backspace();
+        let rendered = render_to_string(&app, area);
+        assert!(rendered.contains("w00"));
+
+        // Near the end the last lines stay in view
+        for c in prompt.chars().skip(28 * 4 - 1).take(30 * 4) {
+            app.thok.write(c);
+        }
+        let rendered = render_to_string(&app, area);
+        assert!(rendered.contains("w59"));
+        assert!(!rendered.contains("w20"));
+    }
+
+    #[test]
+    fn test_render_too_small_terminal() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let app = create_test_app("test prompt", false);
+        let mut terminal = Terminal::new(TestBackend::new(10, 3)).unwrap();
+        terminal.draw(|f| f.render_widget(&app, f.area())).unwrap();
+
+        let rendered: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(rendered.contains("Terminal"), "{rendered}");
+        assert!(rendered.contains("too small"), "{rendered}");
+        assert!(!rendered.contains("test"), "{rendered}");
+    }
+
     #[test]
     fn test_render_edge_case_prompts() {
         for prompt in [
@@ -480,6 +1229,58 @@ mod tests {
         assert!(rendered.contains("25.5"));
     }
 
+    #[test]
+    fn test_typing_view_shows_live_readout() {
+        let rendered = render_to_string(&create_test_app("hello world", false), STD_AREA);
+        assert!(rendered.contains("0 wpm"));
+        assert!(rendered.contains("0% acc"));
+    }
+
+    #[test]
+    fn test_zen_shows_elapsed_instead_of_words_left() {
+        let mut app = create_test_app("test prompt", false);
+        assert!(render_to_string(&app, STD_AREA).contains("words left"));
+
+        app.thok.session.config.zen = true;
+        app.thok.write('t');
+        let rendered = render_to_string(&app, STD_AREA);
+        assert!(rendered.contains("0s   "));
+        assert!(!rendered.contains("words left"));
+    }
+
+    #[test]
+    fn test_paused_shows_overlay() {
+        let mut app = create_test_app("hello world", false);
+        app.thok.session.state.paused = true;
+        let rendered = render_to_string(&app, STD_AREA);
+        assert!(rendered.contains("PAUSED"));
+        assert!(!rendered.contains("hello world"));
+    }
+
+    #[test]
+    fn test_countdown_shows_overlay() {
+        let mut app = create_test_app("hello world", false);
+        app.thok.session.start_countdown(3.0);
+        let rendered = render_to_string(&app, STD_AREA);
+        assert!(rendered.contains("Get ready... 3"));
+        assert!(!rendered.contains("hello world"));
+    }
+
+    #[test]
+    fn test_typing_shows_progress_bars() {
+        let mut app = create_test_app("hello world", false);
+        app.thok.write('h');
+        let rendered = render_to_string(&app, STD_AREA);
+        assert!(rendered.contains("2 words left"));
+        assert!(!rendered.contains("time"));
+
+        app.thok.session.config.number_of_secs = Some(30.0);
+        app.thok.session.state.seconds_remaining = Some(15.0);
+        let rendered = render_to_string(&app, STD_AREA);
+        assert!(rendered.contains("time"));
+        assert!(rendered.contains("2 words left"));
+    }
+
     #[test]
     fn test_partial_typing_shows_prompt() {
         let mut app = create_test_app("hello world", false);
@@ -521,6 +1322,215 @@ mod tests {
         assert!(!rendered.trim().is_empty());
     }
 
+    fn app_with_error_at_second_char() -> App {
+        let mut app = create_test_app("test", false);
+        for (char, outcome) in [('t', Outcome::Correct), ('x', Outcome::Incorrect)] {
+            app.thok.session.state.input.push(Input {
+                char,
+                outcome,
+                timestamp: SystemTime::now(),
+                keypress_start: None,
+            });
+        }
+        app.thok.session.state.cursor_pos = 2;
+        app
+    }
+
+    #[test]
+    fn test_wide_prompt_characters_keep_columns() {
+        assert_eq!(fit_to_width('x', 'a'), "x");
+        assert_eq!(fit_to_width('x', '世'), "x ");
+        assert_eq!(fit_to_width('世', 'a'), "a");
+
+        let mut app = create_test_app("a世b", false);
+        app.thok.write('a');
+        app.thok.write('x');
+        let mut buffer = Buffer::empty(STD_AREA);
+        (&app).render(STD_AREA, &mut buffer);
+        let cells = buffer.content();
+        let x = cells.iter().position(|c| c.symbol() == "x").unwrap();
+        // The wrong key fills both columns of '世', so 'b' stays where the prompt puts it
+        assert_eq!(cells[x - 1].symbol(), "a");
+        assert_eq!(cells[x + 2].symbol(), "b");
+
+        app.thok.backspace();
+        app.thok.write('世');
+        let mut buffer = Buffer::empty(STD_AREA);
+        (&app).render(STD_AREA, &mut buffer);
+        let cells = buffer.content();
+        let wide = cells.iter().position(|c| c.symbol() == "世").unwrap();
+        assert_eq!(cells[wide - 1].symbol(), "a");
+        assert_eq!(cells[wide + 2].symbol(), "b");
+    }
+
+    #[test]
+    fn test_incorrect_glyph_replaces_typed_char() {
+        let mut app = app_with_error_at_second_char();
+        app.render_settings.incorrect_glyph = Some('▢');
+
+        let rendered = render_to_string(&app, STD_AREA);
+        assert!(rendered.contains("t▢st"));
+        assert!(!rendered.contains('x'));
+    }
+
+    #[test]
+    fn test_show_expected_on_error() {
+        let mut app = app_with_error_at_second_char();
+        let rendered = render_to_string(&app, STD_AREA);
+        assert!(rendered.contains("txst"));
+
+        app.render_settings.show_expected = true;
+        let rendered = render_to_string(&app, STD_AREA);
+        assert!(rendered.contains("test"));
+        assert!(!rendered.contains('x'));
+    }
+
+    #[test]
+    fn test_theme_colors_typed_characters() {
+        let mut app = app_with_error_at_second_char();
+        let theme = crate::theme::Theme::from(crate::theme::ThemeName::Solarized);
+        app.render_settings.theme = theme;
+
+        let mut buffer = Buffer::empty(STD_AREA);
+        (&app).render(STD_AREA, &mut buffer);
+        let fg_of = |symbol: &str| {
+            buffer
+                .content()
+                .iter()
+                .find(|c| c.symbol() == symbol)
+                .map(|c| c.fg)
+        };
+        assert_eq!(fg_of("t"), Some(theme.correct));
+        assert_eq!(fg_of("x"), Some(theme.incorrect));
+    }
+
+    #[test]
+    fn test_cursor_styles() {
+        let mut app = app_with_error_at_second_char();
+        let cursor_cell = |app: &App| {
+            let mut buffer = Buffer::empty(STD_AREA);
+            app.render(STD_AREA, &mut buffer);
+            let rendered: String = buffer.content().iter().map(|c| c.symbol()).collect();
+            let start = rendered.find("tx").expect("typed text rendered");
+            let cells: Vec<_> = buffer.content().iter().collect();
+            let idx = rendered[..start].chars().count() + 2;
+            (rendered, cells[idx].clone())
+        };
+
+        let (_, cell) = cursor_cell(&app);
+        assert_eq!(cell.symbol(), "s");
+        assert!(cell.modifier.contains(Modifier::UNDERLINED));
+
+        app.render_settings.cursor = CursorStyle::Block;
+        let (_, cell) = cursor_cell(&app);
+        assert_eq!(cell.symbol(), "s");
+        assert!(cell.modifier.contains(Modifier::REVERSED));
+        assert!(!cell.modifier.contains(Modifier::DIM));
+
+        app.render_settings.cursor = CursorStyle::Bar;
+        let (rendered, cell) = cursor_cell(&app);
+        assert!(rendered.contains("tx▏st"));
+        assert_eq!(cell.symbol(), BAR_CURSOR);
+    }
+
+    #[test]
+    fn test_caret_blinks_with_ticks() {
+        let mut app = app_with_error_at_second_char();
+        app.render_settings.blink_ms = 500;
+        let cursor_modifier = |app: &App| {
+            let mut buffer = Buffer::empty(STD_AREA);
+            app.render(STD_AREA, &mut buffer);
+            let rendered: String = buffer.content().iter().map(|c| c.symbol()).collect();
+            let start = rendered.find("tx").expect("typed text rendered");
+            buffer.content()[rendered[..start].chars().count() + 2].modifier
+        };
+
+        // 100ms ticks: on for five, off for five
+        for (ticks, visible) in [(0, true), (4, true), (5, false), (9, false), (10, true)] {
+            app.caret_ticks = ticks;
+            assert_eq!(app.caret_visible(), visible, "after {ticks} ticks");
+            assert_eq!(
+                cursor_modifier(&app).contains(Modifier::UNDERLINED),
+                visible,
+                "after {ticks} ticks"
+            );
+        }
+
+        app.render_settings.cursor = CursorStyle::Bar;
+        app.caret_ticks = 5;
+        assert!(render_to_string(&app, STD_AREA).contains("tx st"));
+
+        // --blink-ms 0 keeps the caret solid
+        app.render_settings.blink_ms = 0;
+        assert!(app.caret_visible());
+    }
+
+    #[test]
+    fn test_status_bar_under_prompt_until_terminal_is_short() {
+        let mut app = create_test_app("one two three", false);
+        for c in "one t".chars() {
+            app.thok.write(c);
+        }
+        app.thok.write('x');
+
+        let rendered = render_to_string(&app, STD_AREA);
+        let last_row: String = rendered
+            .chars()
+            .skip((STD_AREA.width * (STD_AREA.height - 1)) as usize)
+            .collect();
+        assert!(last_row.contains("1/3 words"), "{last_row}");
+        assert!(last_row.contains("83% acc"), "{last_row}");
+        assert!(!last_row.contains("left"));
+
+        let short = Rect::new(0, 0, 80, STATUS_BAR_MIN_HEIGHT - 1);
+        let rendered = render_to_string(&app, short);
+        assert!(!rendered.contains("words │"));
+        assert!(rendered.contains("three"));
+    }
+
+    #[test]
+    fn test_idle_blanks_screen_or_dims_prompt() {
+        let mut app = create_test_app("idle prompt", false);
+        app.thok.session.state.is_idle = true;
+
+        let rendered = render_to_string(&app, STD_AREA);
+        assert!(rendered.contains("IDLE - Press any key"));
+        assert!(!rendered.contains("idle prompt"));
+
+        app.render_settings.hide_cursor_on_idle = true;
+        let rendered = render_to_string(&app, STD_AREA);
+        assert!(!rendered.contains("IDLE - Press any key"));
+        assert!(rendered.contains("idle prompt"));
+        assert!(rendered.contains(IDLE_BADGE));
+    }
+
+    #[test]
+    fn test_current_word_tracks_cursor() {
+        assert_eq!(current_word("hello world", 0), Some("hello".to_string()));
+        assert_eq!(current_word("hello world", 3), Some("hello".to_string()));
+        assert_eq!(current_word("hello world", 5), Some("world".to_string()));
+        assert_eq!(current_word("hello world", 11), None);
+        assert_eq!(current_word("", 0), None);
+    }
+
+    #[test]
+    fn test_spell_out_shows_current_word_spaced() {
+        let mut app = create_test_app("hello world", false);
+        let rendered = render_to_string(&app, STD_AREA);
+        assert!(!rendered.contains("h e l l o"));
+
+        app.render_settings.spell_out = true;
+        let rendered = render_to_string(&app, STD_AREA);
+        assert!(rendered.contains("h e l l o"));
+        assert!(rendered.contains("hello world"));
+
+        for c in "hello ".chars() {
+            app.thok.write(c);
+        }
+        let rendered = render_to_string(&app, STD_AREA);
+        assert!(rendered.contains("w o r l d"));
+    }
+
     // -- Finished/results rendering --
 
     #[test]
@@ -530,6 +1540,158 @@ mod tests {
         assert!(rendered.contains("95")); // accuracy
     }
 
+    #[test]
+    fn test_goal_wpm_line_colored_by_final_wpm() {
+        let braille_fgs = |app: &App| {
+            let mut buffer = Buffer::empty(STD_AREA);
+            app.render(STD_AREA, &mut buffer);
+            buffer
+                .content()
+                .iter()
+                .filter(|c| {
+                    c.symbol()
+                        .chars()
+                        .all(|ch| ('\u{2801}'..='\u{28FF}').contains(&ch))
+                })
+                .map(|c| c.fg)
+                .collect::<Vec<_>>()
+        };
+        let mut app = create_test_app("test", true);
+        let theme = app.render_settings.theme;
+        assert!(!braille_fgs(&app).contains(&theme.incorrect));
+
+        // Final WPM is 42: a goal of 60 is missed and stretches the y axis
+        app.render_settings.goal_wpm = Some(60.0);
+        assert!(braille_fgs(&app).contains(&theme.incorrect));
+        assert!(render_to_string(&app, STD_AREA).contains("60"));
+
+        app.render_settings.goal_wpm = Some(30.0);
+        let fgs = braille_fgs(&app);
+        assert!(fgs.contains(&theme.correct));
+        assert!(!fgs.contains(&theme.incorrect));
+    }
+
+    #[test]
+    fn test_finished_shows_quote_attribution() {
+        let mut app = create_test_app("test", true);
+        app.quote = Some(crate::language::Quote {
+            text: "test".to_string(),
+            author: "Ada Lovelace".to_string(),
+            source: "Notes".to_string(),
+        });
+        let rendered = render_to_string(&app, STD_AREA);
+        assert!(rendered.contains("— Ada Lovelace"));
+    }
+
+    #[test]
+    fn test_finished_shows_longest_streak() {
+        let rendered = render_to_string(&create_test_app("test", true), STD_AREA);
+        assert!(rendered.contains("streak"));
+    }
+
+    #[test]
+    fn test_missed_min_accuracy_shows_retry_message() {
+        use clap::Parser;
+        let mut app = create_test_app("test", true);
+        app.cli = Some(crate::Cli::parse_from(["klik", "--min-accuracy", "95"]));
+        app.auto_retry_at = Some(std::time::SystemTime::now());
+        app.attempt = 2;
+        let rendered = render_to_string(&app, STD_AREA);
+        assert!(rendered.contains("Below 95% accuracy - retrying (attempt 3)"));
+        assert!(!rendered.contains("(r)etry"));
+    }
+
+    #[test]
+    fn test_finished_shows_focus_hint() {
+        let rendered = render_to_string(&create_test_app("test", true), STD_AREA);
+        assert!(rendered.contains("focus on:"));
+    }
+
+    #[test]
+    fn test_minimal_results_show_only_headline_and_controls() {
+        let mut app = create_test_app("test", true);
+        app.render_settings.minimal = true;
+        let rendered = render_to_string(&app, STD_AREA);
+
+        assert!(rendered.contains("42 wpm   95% acc"));
+        assert!(rendered.contains("(s)tats"));
+        assert!(!rendered.contains("seconds")); // chart axis
+        assert!(!rendered.contains("Settings:"));
+        assert!(!rendered.contains(" net "));
+
+        assert!(rendered.find("wpm").unwrap() < rendered.find("(r)etry").unwrap());
+    }
+
+    #[test]
+    fn test_results_note_too_many_errors() {
+        let mut app = create_test_app("test", true);
+        assert!(!render_to_string(&app, STD_AREA).contains("too many errors"));
+
+        app.thok.session.config.max_errors = Some(1);
+        app.thok.session.state.wrong_keystrokes = 2;
+        assert!(render_to_string(&app, STD_AREA).contains("failed: too many errors"));
+
+        app.render_settings.minimal = true;
+        assert!(render_to_string(&app, STD_AREA).contains("95% acc   failed: too many errors"));
+    }
+
+    #[test]
+    fn test_ghost_marks_pace_of_best_wpm() {
+        let mut app = create_test_app("abcdefghij", false);
+        let clock = crate::clock::MockClock::default();
+        app.thok.session.clock = std::sync::Arc::new(clock.clone());
+        app.thok.write('a');
+        clock.advance(std::time::Duration::from_secs(1));
+
+        let ghost_bg = |app: &App| {
+            let mut buffer = Buffer::empty(STD_AREA);
+            app.render(STD_AREA, &mut buffer);
+            let rendered: String = buffer.content().iter().map(|c| c.symbol()).collect();
+            let idx = rendered[..rendered.find("abcdefghij").unwrap()]
+                .chars()
+                .count()
+                + 5;
+            buffer.content()[idx].bg
+        };
+        // No best on record yet, so no ghost
+        assert_eq!(ghost_bg(&app), Color::Reset);
+
+        app.thok.ghost_wpm = Some(60.0);
+        assert_eq!(ghost_bg(&app), app.render_settings.theme.accent);
+    }
+
+    #[test]
+    fn test_results_review_tints_words_by_accuracy() {
+        let mut app = create_test_app("good bad", true);
+        app.thok.session.state.input[6].outcome = Outcome::Incorrect;
+
+        let mut buffer = Buffer::empty(STD_AREA);
+        (&app).render(STD_AREA, &mut buffer);
+        let rendered: String = buffer.content().iter().map(|c| c.symbol()).collect();
+        assert!(rendered.contains("words"));
+        let bg_at = |word: &str| {
+            let idx = rendered[..rendered.find(word).unwrap()].chars().count();
+            buffer.content()[idx].bg
+        };
+        assert_eq!(bg_at("good "), app.render_settings.theme.correct);
+        assert_eq!(bg_at("bad"), app.render_settings.theme.incorrect);
+
+        // Nothing to review before any input
+        app.thok.session.state.input.clear();
+        assert!(!render_to_string(&app, STD_AREA).contains("good bad"));
+    }
+
+    #[test]
+    fn test_results_per_minute_table_for_long_tests() {
+        let mut app = create_test_app("test", true);
+        assert!(!render_to_string(&app, STD_AREA).contains("per min"));
+
+        app.thok.session.state.per_minute_wpm = vec![62.0, 55.4, 48.0];
+        let rendered = render_to_string(&app, STD_AREA);
+        assert!(rendered.contains("per min"));
+        assert!(["62", "55", "48"].iter().all(|wpm| rendered.contains(wpm)));
+    }
+
     #[test]
     fn test_finished_shows_legend() {
         let rendered = render_to_string(&create_test_app("test", true), STD_AREA);
@@ -1,37 +1,32 @@
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout},
-    style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Sparkline, Table},
     Frame,
 };
 
 use crate::stats::CharSummaryWithDeltas;
+use crate::theme::{StatsThresholds, Theme};
 use crate::{App, SortBy};
 
+/// Session dates shown in the selected character's history sparkline
+const HISTORY_SESSIONS: usize = 30;
+
 /// Pure presenter for a single character stats row
-/// Returns a Row given the raw tuple from Thok summary
-pub fn present_row(data: &CharSummaryWithDeltas) -> Row<'static> {
+/// Returns a Row given the raw tuple from Thok summary, colored from `theme` at `thresholds`
+pub fn present_row(
+    data: &CharSummaryWithDeltas,
+    theme: &Theme,
+    thresholds: &StatsThresholds,
+) -> Row<'static> {
     let char_display = if data.character == ' ' {
         "SPACE".to_string()
     } else {
         data.character.to_string()
     };
 
-    let time_color = if data.avg_time < 150.0 {
-        Color::Green
-    } else if data.avg_time < 250.0 {
-        Color::Yellow
-    } else {
-        Color::Red
-    };
-
-    let miss_color = if data.miss_rate == 0.0 {
-        Color::Green
-    } else if data.miss_rate < 10.0 {
-        Color::Yellow
-    } else {
-        Color::Red
-    };
+    let time_color = theme.time_color(data.avg_time, thresholds);
+    let miss_color = theme.miss_rate_color(data.miss_rate, thresholds);
 
     // Format time with delta
     let time_display = if let Some(delta) = data.time_delta {
@@ -70,12 +65,12 @@ pub fn present_row(data: &CharSummaryWithDeltas) -> Row<'static> {
         data.attempts.to_string()
     };
 
-    // Color deltas: green for improvement, red for regression
+    // Color deltas: correct for improvement, incorrect for regression
     let time_style = if let Some(delta) = data.time_delta {
         if delta < -5.0 {
-            Style::default().fg(Color::Green)
+            Style::default().fg(theme.correct)
         } else if delta > 5.0 {
-            Style::default().fg(Color::Red)
+            Style::default().fg(theme.incorrect)
         } else {
             Style::default()
         }
@@ -85,9 +80,9 @@ pub fn present_row(data: &CharSummaryWithDeltas) -> Row<'static> {
 
     let miss_style = if let Some(delta) = data.miss_delta {
         if delta < -1.0 {
-            Style::default().fg(Color::Green)
+            Style::default().fg(theme.correct)
         } else if delta > 1.0 {
-            Style::default().fg(Color::Red)
+            Style::default().fg(theme.incorrect)
         } else {
             Style::default()
         }
@@ -100,6 +95,11 @@ pub fn present_row(data: &CharSummaryWithDeltas) -> Row<'static> {
         Cell::from(time_display).style(time_style),
         Cell::from(miss_display).style(miss_style),
         Cell::from(attempts_display),
+        Cell::from(
+            data.correction_rate
+                .map_or_else(|| "—".to_string(), |rate| format!("{rate:.0}")),
+        ),
+        Cell::from(format!("{:.1}", data.difficulty())),
         Cell::from(
             data.latest_datetime
                 .clone()
@@ -111,6 +111,7 @@ pub fn present_row(data: &CharSummaryWithDeltas) -> Row<'static> {
 /// Render the Character Statistics screen
 pub fn render_character_stats(app: &mut App, f: &mut Frame) {
     let area = f.area();
+    let theme = app.render_settings.theme;
 
     // Create layout
     let chunks = Layout::default()
@@ -119,6 +120,7 @@ pub fn render_character_stats(app: &mut App, f: &mut Frame) {
         .constraints([
             Constraint::Length(3), // Title
             Constraint::Min(0),    // Stats table
+            Constraint::Length(5), // Selected character's history
             Constraint::Length(4), // Instructions
         ])
         .split(area);
@@ -134,6 +136,7 @@ pub fn render_character_stats(app: &mut App, f: &mut Frame) {
         SortBy::AvgTime => "Avg Time",
         SortBy::MissRate => "Miss Rate",
         SortBy::Attempts => "Attempts",
+        SortBy::Difficulty => "Difficulty",
     };
     let title_text = format!("Character Statistics (Sort: {sort_by_text} {sort_direction})");
 
@@ -141,7 +144,7 @@ pub fn render_character_stats(app: &mut App, f: &mut Frame) {
         .block(Block::default().borders(Borders::ALL).title("Stats"))
         .style(
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.title)
                 .add_modifier(Modifier::BOLD),
         )
         .alignment(Alignment::Center);
@@ -190,6 +193,17 @@ pub fn render_character_stats(app: &mut App, f: &mut Frame) {
                     cmp.reverse()
                 }
             }),
+            SortBy::Difficulty => summary.sort_by(|a, b| {
+                let cmp = a
+                    .difficulty()
+                    .partial_cmp(&b.difficulty())
+                    .unwrap_or(std::cmp::Ordering::Equal);
+                if ascending {
+                    cmp
+                } else {
+                    cmp.reverse()
+                }
+            }),
         }
 
         // Calculate scrolling bounds
@@ -197,10 +211,15 @@ pub fn render_character_stats(app: &mut App, f: &mut Frame) {
         let total_rows = summary.len();
         let max_scroll = total_rows.saturating_sub(table_height);
 
-        // Clamp scroll offset
-        if app.char_stats_state.scroll_offset > max_scroll {
-            app.char_stats_state.scroll_offset = max_scroll;
+        // Keep the selection on the table and scrolled into view
+        let state = &mut app.char_stats_state;
+        state.selected = state.selected.min(total_rows.saturating_sub(1));
+        if state.selected < state.scroll_offset {
+            state.scroll_offset = state.selected;
+        } else if state.selected >= state.scroll_offset + table_height {
+            state.scroll_offset = state.selected + 1 - table_height.max(1);
         }
+        state.scroll_offset = state.scroll_offset.min(max_scroll);
 
         // Create header with sort indicators
         let char_indicator = if matches!(app.char_stats_state.sort_by, SortBy::Character) {
@@ -223,26 +242,42 @@ pub fn render_character_stats(app: &mut App, f: &mut Frame) {
         } else {
             ""
         };
+        let difficulty_indicator = if matches!(app.char_stats_state.sort_by, SortBy::Difficulty) {
+            sort_direction
+        } else {
+            ""
+        };
 
         let header = Row::new(vec![
             Cell::from(format!("Char {char_indicator}")),
             Cell::from(format!("Avg Time (ms) {time_indicator}")),
             Cell::from(format!("Miss Rate (%) {miss_indicator}")),
             Cell::from(format!("Attempts {attempts_indicator}")),
+            Cell::from("Fixed (%)"),
+            Cell::from(format!("Difficulty {difficulty_indicator}")),
             Cell::from("Last Typed"),
         ])
         .style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.warning)
                 .add_modifier(Modifier::BOLD),
         );
 
         // Visible rows
+        let selected = app.char_stats_state.selected;
         let visible_rows: Vec<Row> = summary
             .iter()
+            .enumerate()
             .skip(app.char_stats_state.scroll_offset)
             .take(table_height)
-            .map(|data| present_row(data))
+            .map(|(idx, data)| {
+                let row = present_row(data, &theme, &app.render_settings.thresholds);
+                if idx == selected {
+                    row.style(Style::default().add_modifier(Modifier::REVERSED))
+                } else {
+                    row
+                }
+            })
             .collect();
 
         // Create the table
@@ -251,6 +286,8 @@ pub fn render_character_stats(app: &mut App, f: &mut Frame) {
             Constraint::Length(18), // Avg Time
             Constraint::Length(18), // Miss Rate
             Constraint::Length(12), // Attempts
+            Constraint::Length(9),  // Fixed
+            Constraint::Length(12), // Difficulty
             Constraint::Min(10),    // Last Typed
         ];
 
@@ -264,20 +301,76 @@ pub fn render_character_stats(app: &mut App, f: &mut Frame) {
             .column_spacing(2);
 
         f.render_widget(table, chunks[1]);
+
+        if let Some(data) = summary.get(selected) {
+            let history = app
+                .thok
+                .get_char_history(data.character, HISTORY_SESSIONS)
+                .unwrap_or_default();
+            render_char_history(f, chunks[2], data.character, &history, &theme);
+        }
     } else {
         // No data state
         let no_data =
             Paragraph::new("No character statistics available yet. Type to collect data.")
                 .alignment(Alignment::Center)
-                .style(Style::default().fg(Color::Gray));
+                .style(Style::default().fg(theme.dim));
         f.render_widget(no_data, chunks[1]);
     }
 
     // Instructions
     let instructions = Paragraph::new(
-        "(↑/↓) scroll  (PgUp/PgDn) page  (Home) top  (1-4) sort  (b/backspace) back  (n) new  (r) retry",
+        "(↑/↓) select  (PgUp/PgDn) page  (Home) top  (1-5) sort  (f) fingers  (b/backspace) back  (n) new  (r) retry",
     )
     .alignment(Alignment::Center)
     .wrap(ratatui::widgets::Wrap { trim: true });
-    f.render_widget(instructions, chunks[2]);
+    f.render_widget(instructions, chunks[3]);
+}
+
+/// Sparkline of `character`'s average time per session date, oldest first; slower sessions
+/// draw taller bars. With fewer than two sessions there is no trend, so a message is shown.
+pub fn render_char_history(
+    f: &mut Frame,
+    area: Rect,
+    character: char,
+    history: &[(String, f64)],
+    theme: &Theme,
+) {
+    let name = if character == ' ' {
+        "SPACE".to_string()
+    } else {
+        format!("'{character}'")
+    };
+    let block = Block::default().borders(Borders::ALL);
+
+    match history {
+        [(first_date, first), .., (last_date, last)] => {
+            let title = format!(
+                "{name} avg time, {} sessions: {first:.0}ms ({first_date}) → {last:.0}ms ({last_date})",
+                history.len()
+            );
+            let data: Vec<u64> = history.iter().map(|(_, avg)| avg.round() as u64).collect();
+            let sparkline = Sparkline::default()
+                .block(block.title(title))
+                .data(&data)
+                .style(Style::default().fg(theme.accent));
+            f.render_widget(sparkline, area);
+        }
+        [(date, avg)] => {
+            let message = Paragraph::new(format!(
+                "{name}: {avg:.0}ms on {date}. Only one session so far, so there is no trend yet."
+            ))
+            .block(block.title(format!("{name} history")))
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(theme.dim));
+            f.render_widget(message, area);
+        }
+        [] => {
+            let message = Paragraph::new(format!("No past sessions for {name} yet."))
+                .block(block.title(format!("{name} history")))
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(theme.dim));
+            f.render_widget(message, area);
+        }
+    }
 }
@@ -21,6 +21,15 @@ pub fn compute_chart_params(
     (overall_duration, highest_wpm.round())
 }
 
+/// Points spaced along `goal` between `x_bounds`, drawn as a scatter so the line reads as dashed
+pub fn goal_line(goal: f64, x_bounds: [f64; 2]) -> Vec<(f64, f64)> {
+    const DASHES: usize = 24;
+    let step = (x_bounds[1] - x_bounds[0]) / DASHES as f64;
+    (0..=DASHES)
+        .map(|i| (x_bounds[0] + step * i as f64, goal))
+        .collect()
+}
+
 /// Format a simple numeric label consistently
 pub fn format_label(val: f64) -> String {
     if (val - val.round()).abs() < f64::EPSILON {
@@ -30,6 +39,33 @@ pub fn format_label(val: f64) -> String {
     }
 }
 
+/// Width of each bucket in the keystroke rhythm histogram
+pub const RHYTHM_BUCKET_MS: u64 = 100;
+/// Number of buckets; the last one collects every gap at or above its lower edge
+pub const RHYTHM_BUCKETS: usize = 6;
+
+/// Count inter-keystroke gaps into `RHYTHM_BUCKET_MS`-wide buckets, labelled by lower edge
+pub fn rhythm_buckets(intervals: &[u64]) -> Vec<(String, u64)> {
+    let mut counts = [0u64; RHYTHM_BUCKETS];
+    for ms in intervals {
+        let bucket = ((ms / RHYTHM_BUCKET_MS) as usize).min(RHYTHM_BUCKETS - 1);
+        counts[bucket] += 1;
+    }
+    counts
+        .iter()
+        .enumerate()
+        .map(|(i, count)| {
+            let lower = i as u64 * RHYTHM_BUCKET_MS;
+            let label = if i == RHYTHM_BUCKETS - 1 {
+                format!("{lower}+")
+            } else {
+                lower.to_string()
+            };
+            (label, *count)
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -41,6 +77,24 @@ mod tests {
         assert_eq!(y, 0.0);
     }
 
+    #[test]
+    fn test_rhythm_buckets() {
+        let buckets = rhythm_buckets(&[0, 99, 100, 250, 499, 500, 2000]);
+        let counts: Vec<u64> = buckets.iter().map(|(_, c)| *c).collect();
+        assert_eq!(counts, vec![2, 1, 1, 0, 1, 2]);
+        assert_eq!(buckets[0].0, "0");
+        assert_eq!(buckets[5].0, "500+");
+        assert!(rhythm_buckets(&[]).iter().all(|(_, c)| *c == 0));
+    }
+
+    #[test]
+    fn test_goal_line_spans_x_range() {
+        let line = goal_line(50.0, [1.0, 25.0]);
+        assert_eq!(line.first(), Some(&(1.0, 50.0)));
+        assert_eq!(line.last(), Some(&(25.0, 50.0)));
+        assert!(line.iter().all(|(_, y)| *y == 50.0));
+    }
+
     #[test]
     fn test_format_label() {
         assert_eq!(format_label(1.0), "1");
@@ -0,0 +1,83 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::App;
+
+/// Render the per-finger press time and miss rate for the selected `--layout`
+pub fn render_finger_stats(app: &mut App, f: &mut Frame) {
+    let area = f.area();
+    let theme = app.render_settings.theme;
+    let thresholds = app.render_settings.thresholds;
+    let layout = app.render_settings.layout;
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(0),    // Finger table
+            Constraint::Length(2), // Instructions
+        ])
+        .split(area);
+
+    let title = Paragraph::new(format!("Finger Statistics ({layout})"))
+        .block(Block::default().borders(Borders::ALL).title("Fingers"))
+        .style(
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Center);
+    f.render_widget(title, chunks[0]);
+
+    let summary = app.thok.get_finger_summary(layout).unwrap_or_default();
+    if summary.is_empty() {
+        let no_data =
+            Paragraph::new("No character statistics available yet. Type to collect data.")
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(theme.dim));
+        f.render_widget(no_data, chunks[1]);
+    } else {
+        let header = Row::new(vec![
+            Cell::from("Finger"),
+            Cell::from("Avg Time (ms)"),
+            Cell::from("Miss Rate (%)"),
+        ])
+        .style(
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        );
+        let rows: Vec<Row> = summary
+            .iter()
+            .map(|(finger, avg_time, miss_rate)| {
+                Row::new(vec![
+                    Cell::from(finger.name()).style(Style::default().add_modifier(Modifier::BOLD)),
+                    Cell::from(format!("{avg_time:.1}"))
+                        .style(Style::default().fg(theme.time_color(*avg_time, &thresholds))),
+                    Cell::from(format!("{miss_rate:.1}"))
+                        .style(Style::default().fg(theme.miss_rate_color(*miss_rate, &thresholds))),
+                ])
+            })
+            .collect();
+        let widths = [
+            Constraint::Length(14), // Finger
+            Constraint::Length(16), // Avg Time
+            Constraint::Length(16), // Miss Rate
+        ];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(Block::default().borders(Borders::ALL).title("By Finger"))
+            .column_spacing(2);
+        f.render_widget(table, chunks[1]);
+    }
+
+    let instructions = Paragraph::new("(b/backspace) back to characters  (n) new  (r) retry")
+        .alignment(Alignment::Center)
+        .wrap(ratatui::widgets::Wrap { trim: true });
+    f.render_widget(instructions, chunks[2]);
+}
@@ -0,0 +1,126 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use std::collections::HashMap;
+
+use crate::{App, HeatmapMetric};
+
+/// QWERTY rows with their left indent (in key widths / 2) to mimic the stagger
+const KEYBOARD_ROWS: [(&str, usize); 4] = [
+    ("1234567890-=", 0),
+    ("qwertyuiop[]\\", 1),
+    ("asdfghjkl;'", 2),
+    ("zxcvbnm,./", 3),
+];
+
+const KEY_WIDTH: usize = 5;
+
+/// Blend from green (best) to red (worst) as `value` moves from `min` to `max`
+pub fn heat_color(value: f64, min: f64, max: f64) -> Color {
+    let t = if max > min {
+        ((value - min) / (max - min)).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    Color::Rgb((255.0 * t) as u8, (200.0 * (1.0 - t)) as u8, 0)
+}
+
+/// Render the keyboard heatmap colored by the selected metric
+pub fn render_heatmap(app: &mut App, f: &mut Frame) {
+    let area = f.area();
+    let theme = app.render_settings.theme;
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(0),    // Keyboard
+            Constraint::Length(2), // Instructions
+        ])
+        .split(area);
+
+    let metric_text = match app.heatmap_metric {
+        HeatmapMetric::AvgTime => "Avg Time",
+        HeatmapMetric::MissRate => "Miss Rate",
+    };
+    let title = Paragraph::new(format!("Keyboard Heatmap ({metric_text})"))
+        .block(Block::default().borders(Borders::ALL).title("Heatmap"))
+        .style(
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Center);
+    f.render_widget(title, chunks[0]);
+
+    // (char -> metric value), keyed by lowercase so shifted letters share a key
+    let values: HashMap<char, f64> = app
+        .thok
+        .get_all_char_summary()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(_, _, _, attempts)| *attempts > 0)
+        .map(|(c, avg_time, miss_rate, _)| {
+            let value = match app.heatmap_metric {
+                HeatmapMetric::AvgTime => avg_time,
+                HeatmapMetric::MissRate => miss_rate,
+            };
+            (c.to_lowercase().next().unwrap_or(c), value)
+        })
+        .collect();
+
+    let min = values.values().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.values().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let key_style = |c: char| match values.get(&c) {
+        Some(v) => Style::default()
+            .fg(Color::Black)
+            .bg(heat_color(*v, min, max))
+            .add_modifier(Modifier::BOLD),
+        None => Style::default().fg(theme.dim).add_modifier(Modifier::DIM),
+    };
+
+    let mut lines = Vec::new();
+    for (row, indent) in KEYBOARD_ROWS {
+        let mut spans = vec![Span::raw(" ".repeat(indent * KEY_WIDTH / 2))];
+        for c in row.chars() {
+            spans.push(Span::styled(format!("  {c}  "), key_style(c)));
+            spans.push(Span::raw(" "));
+        }
+        lines.push(Line::from(spans));
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(vec![
+        Span::raw(" ".repeat(4 * KEY_WIDTH)),
+        Span::styled(format!("{:^30}", "space"), key_style(' ')),
+    ]));
+
+    let keyboard = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("green = strong, red = weak, gray = no data"),
+    );
+    f.render_widget(keyboard, chunks[1]);
+
+    let instructions = Paragraph::new("(m) toggle metric  (b/backspace) back  (n) new  (r) retry")
+        .alignment(Alignment::Center)
+        .wrap(ratatui::widgets::Wrap { trim: true });
+    f.render_widget(instructions, chunks[2]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heat_color_scale() {
+        assert_eq!(heat_color(100.0, 100.0, 300.0), Color::Rgb(0, 200, 0));
+        assert_eq!(heat_color(300.0, 100.0, 300.0), Color::Rgb(255, 0, 0));
+        // A single data point is treated as the best
+        assert_eq!(heat_color(5.0, 5.0, 5.0), Color::Rgb(0, 200, 0));
+    }
+}
@@ -0,0 +1,125 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+    Frame,
+};
+
+use crate::history::{consistency_series, wpm_series};
+use crate::ui::charting::format_label;
+use crate::App;
+
+/// Render the session History screen
+pub fn render_history(app: &mut App, f: &mut Frame) {
+    let area = f.area();
+    let theme = app.render_settings.theme;
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(0),    // Charts
+            Constraint::Length(2), // Instructions
+        ])
+        .split(area);
+
+    let title = Paragraph::new(format!("Session History ({} sessions)", app.history.len()))
+        .block(Block::default().borders(Borders::ALL).title("History"))
+        .style(
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Center);
+    f.render_widget(title, chunks[0]);
+
+    let wpm = wpm_series(&app.history);
+    let consistency = consistency_series(&app.history);
+    if wpm.is_empty() && consistency.is_empty() {
+        let no_data = Paragraph::new("No history yet. Finish a test to log one.")
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(theme.dim));
+        f.render_widget(no_data, chunks[1]);
+    } else {
+        let charts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(chunks[1]);
+        // Both charts share the x axis so sessions line up vertically
+        let first = wpm
+            .iter()
+            .chain(consistency.iter())
+            .map(|(x, _)| *x)
+            .fold(f64::INFINITY, f64::min);
+        let last = (app.history.len() as f64).max(first + 1.0);
+
+        render_series(
+            f,
+            charts[0],
+            &wpm,
+            [first, last],
+            ("wpm", "WPM over time", theme.warning),
+        );
+        render_series(
+            f,
+            charts[1],
+            &consistency,
+            [first, last],
+            ("sd", "Consistency (lower is steadier)", theme.accent),
+        );
+    }
+
+    let instructions = Paragraph::new("(b/backspace) back  (n) new  (r) retry")
+        .alignment(Alignment::Center)
+        .wrap(ratatui::widgets::Wrap { trim: true });
+    f.render_widget(instructions, chunks[2]);
+}
+
+/// One per-session line chart; `label` is (axis name, block title, line color)
+fn render_series(
+    f: &mut Frame,
+    area: Rect,
+    data: &[(f64, f64)],
+    x_bounds: [f64; 2],
+    label: (&str, &str, Color),
+) {
+    let (name, title, color) = label;
+    let bold_style = Style::default().add_modifier(Modifier::BOLD);
+    let highest = data
+        .iter()
+        .map(|(_, y)| *y)
+        .fold(0.0_f64, f64::max)
+        .ceil()
+        .max(1.0);
+
+    let datasets = vec![Dataset::default()
+        .name(name)
+        .marker(ratatui::symbols::Marker::Braille)
+        .style(Style::default().fg(color))
+        .graph_type(GraphType::Line)
+        .data(data)];
+
+    let chart = Chart::new(datasets)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .x_axis(
+            Axis::default()
+                .title("session")
+                .bounds(x_bounds)
+                .labels(vec![
+                    Span::styled(format_label(x_bounds[0]), bold_style),
+                    Span::styled(format_label(x_bounds[1]), bold_style),
+                ]),
+        )
+        .y_axis(
+            Axis::default()
+                .title(name)
+                .bounds([0.0, highest])
+                .labels(vec![
+                    Span::styled("0", bold_style),
+                    Span::styled(format_label(highest), bold_style),
+                ]),
+        );
+    f.render_widget(chart, area);
+}
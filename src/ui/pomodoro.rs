@@ -0,0 +1,73 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::App;
+
+/// Render the session-wide summary shown when a pomodoro run ends
+pub fn render_pomodoro_summary(app: &mut App, f: &mut Frame) {
+    let area = f.area();
+    let theme = app.render_settings.theme;
+    let bold_style = Style::default().add_modifier(Modifier::BOLD);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(0),    // Summary
+            Constraint::Length(2), // Instructions
+        ])
+        .split(area);
+
+    let title = Paragraph::new("Focus session complete")
+        .block(Block::default().borders(Borders::ALL).title("Pomodoro"))
+        .style(
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Center);
+    f.render_widget(title, chunks[0]);
+
+    let lines = match app.pomodoro.as_ref() {
+        Some(pomodoro) => {
+            let elapsed = pomodoro.elapsed().as_secs();
+            vec![
+                Line::from(Span::styled(
+                    format!(
+                        "{} tests in {}m {:02}s",
+                        pomodoro.tests_completed,
+                        elapsed / 60,
+                        elapsed % 60
+                    ),
+                    bold_style,
+                )),
+                Line::from(""),
+                Line::from(format!("{} words typed", pomodoro.total_words)),
+                Line::from(format!("{:.0} avg wpm", pomodoro.average_wpm())),
+                Line::from(format!("{}% acc", pomodoro.accuracy())),
+                Line::from(""),
+                Line::from(Span::styled(
+                    "Time for a break: stretch, rest your eyes, then come back refreshed.",
+                    Style::default()
+                        .fg(theme.warning)
+                        .add_modifier(Modifier::ITALIC),
+                )),
+            ]
+        }
+        None => vec![Line::from("No focus session in progress.")],
+    };
+
+    let summary = Paragraph::new(lines).alignment(Alignment::Center);
+    f.render_widget(summary, chunks[1]);
+
+    let instructions = Paragraph::new("(n) start another session  (esc) quit")
+        .alignment(Alignment::Center)
+        .wrap(ratatui::widgets::Wrap { trim: true });
+    f.render_widget(instructions, chunks[2]);
+}
@@ -0,0 +1,77 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{thok::Outcome, ui::fit_to_width, App};
+
+/// Render the prompt as far as the replay has progressed, errors in red as they were typed
+pub fn render_replay(app: &mut App, f: &mut Frame) {
+    let area = f.area();
+    let theme = app.render_settings.theme;
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(0),    // Prompt
+            Constraint::Length(2), // Instructions
+        ])
+        .split(area);
+
+    let Some(replay) = app.replay.as_ref() else {
+        return;
+    };
+
+    let status = if replay.is_done() { "done" } else { "playing" };
+    let title = Paragraph::new(format!("Replay {:.2}x ({status})", replay.speed()))
+        .block(Block::default().borders(Borders::ALL).title("Replay"))
+        .style(
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Center);
+    f.render_widget(title, chunks[0]);
+
+    let prompt: Vec<char> = app.thok.session.prompt.chars().collect();
+    let mut spans = Vec::with_capacity(replay.cursor() + 2);
+    for (idx, input) in replay.visible().iter().enumerate() {
+        let expected = prompt.get(idx).copied().unwrap_or(input.char);
+        let span = match input.outcome {
+            Outcome::Correct => Span::styled(
+                expected.to_string(),
+                Style::default()
+                    .fg(theme.correct)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Outcome::Incorrect => Span::styled(
+                fit_to_width(if input.char == ' ' { '·' } else { input.char }, expected),
+                Style::default()
+                    .fg(theme.incorrect)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        };
+        spans.push(span);
+    }
+    let remaining: String = prompt.iter().skip(replay.cursor()).collect();
+    spans.push(Span::styled(
+        remaining,
+        Style::default().fg(theme.dim).add_modifier(Modifier::DIM),
+    ));
+
+    let text = Paragraph::new(Line::from(spans))
+        .block(Block::default().borders(Borders::ALL))
+        .wrap(Wrap { trim: true });
+    f.render_widget(text, chunks[1]);
+
+    let instructions =
+        Paragraph::new("(+/-) speed  (space) play again  (b/backspace) back  (n) new  (r) retry")
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+    f.render_widget(instructions, chunks[2]);
+}
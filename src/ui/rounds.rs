@@ -0,0 +1,79 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::App;
+
+/// Render the aggregate results shown after the last of the `--rounds`
+pub fn render_rounds_summary(app: &mut App, f: &mut Frame) {
+    let area = f.area();
+    let theme = app.render_settings.theme;
+    let bold_style = Style::default().add_modifier(Modifier::BOLD);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(0),    // Summary
+            Constraint::Length(2), // Instructions
+        ])
+        .split(area);
+
+    let title = Paragraph::new("All rounds complete")
+        .block(Block::default().borders(Borders::ALL).title("Rounds"))
+        .style(
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Center);
+    f.render_widget(title, chunks[0]);
+
+    let lines = match app.rounds.as_ref() {
+        Some(rounds) => {
+            let mut lines = vec![
+                Line::from(Span::styled(
+                    format!(
+                        "{:.0} mean wpm   {:.0} median wpm   {:.2} sd",
+                        rounds.mean_wpm(),
+                        rounds.median_wpm(),
+                        rounds.wpm_std_dev()
+                    ),
+                    bold_style,
+                )),
+                Line::from(format!("{:.0}% mean acc", rounds.mean_accuracy())),
+            ];
+            if let Some((number, best)) = rounds.best_round() {
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "best: round {number} at {:.0} wpm, {:.0}% acc",
+                        best.wpm, best.accuracy
+                    ),
+                    Style::default().fg(theme.correct),
+                )));
+            }
+            lines.push(Line::from(""));
+            lines.extend(rounds.summaries.iter().enumerate().map(|(i, s)| {
+                Line::from(Span::styled(
+                    format!("round {}: {:.0} wpm, {:.0}% acc", i + 1, s.wpm, s.accuracy),
+                    Style::default().fg(theme.dim),
+                ))
+            }));
+            lines
+        }
+        None => vec![Line::from("No rounds in progress.")],
+    };
+
+    let summary = Paragraph::new(lines).alignment(Alignment::Center);
+    f.render_widget(summary, chunks[1]);
+
+    let instructions = Paragraph::new("(n) run the rounds again  (esc) quit")
+        .alignment(Alignment::Center)
+        .wrap(ratatui::widgets::Wrap { trim: true });
+    f.render_widget(instructions, chunks[2]);
+}
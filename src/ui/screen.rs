@@ -1,7 +1,15 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::Frame;
 
-use crate::{ui::character_stats::render_character_stats, App, AppState};
+use crate::{
+    share::share_url,
+    ui::{
+        character_stats::render_character_stats, fingers::render_finger_stats,
+        heatmap::render_heatmap, history::render_history, pomodoro::render_pomodoro_summary,
+        replay::render_replay, rounds::render_rounds_summary,
+    },
+    App, AppState,
+};
 
 /// A UI Screen boundary: responsible for rendering and optional key handling
 pub trait Screen {
@@ -22,8 +30,17 @@ impl Screen for TypingScreen {
 
     fn on_key(&mut self, key: KeyEvent, app: &mut App) -> Option<KeyAction> {
         match key.code {
+            // Terminals send Ctrl+Backspace either as a modified Backspace or as Ctrl+H
+            KeyCode::Backspace | KeyCode::Char('h')
+                if key.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                if !app.thok.has_finished() && !app.thok.is_paused() {
+                    app.thok.rewind_to_first_error();
+                }
+                Some(KeyAction::Continue)
+            }
             KeyCode::Backspace => {
-                if !app.thok.has_finished() {
+                if !app.thok.has_finished() && !app.thok.is_paused() {
                     app.thok.backspace();
                 }
                 Some(KeyAction::Continue)
@@ -32,11 +49,22 @@ impl Screen for TypingScreen {
                 if key.modifiers.contains(KeyModifiers::CONTROL) && c == 'c' {
                     return Some(KeyAction::Quit);
                 }
+                if key.modifiers.contains(KeyModifiers::CONTROL) && c == 'p' {
+                    app.thok.toggle_pause();
+                    return Some(KeyAction::Continue);
+                }
                 if !app.thok.has_finished() {
                     app.thok.write(c);
                 }
                 Some(KeyAction::Continue)
             }
+            // Code prompts have line breaks to type
+            KeyCode::Enter => {
+                if !app.thok.has_finished() {
+                    app.thok.write('\n');
+                }
+                Some(KeyAction::Continue)
+            }
             _ => None,
         }
     }
@@ -51,27 +79,44 @@ impl Screen for ResultsScreen {
     }
 
     fn on_key(&mut self, key: KeyEvent, app: &mut App) -> Option<KeyAction> {
+        // A missed --min-accuracy target retries on its own; only Esc (handled globally) escapes
+        if app.auto_retry_at.is_some() {
+            return Some(KeyAction::Continue);
+        }
         match key.code {
             KeyCode::Char('t') => {
-                if webbrowser::Browser::is_available() {
-                    // Construct a minimal encoded tweet without external deps.
-                    // Format: "<wpm> wpm / <acc>% acc / <sd> sd\n\nhttps://github.com/martintrojer/klik"
-                    let wpm = app.thok.wpm();
-                    let acc = app.thok.accuracy();
-                    let sd = app.thok.std_dev();
-                    let url = format!(
-                        "https://twitter.com/intent/tweet?text={wpm}%20wpm%20%2F%20{acc}%25%20acc%20%2F%20{sd:.2}%20sd%0A%0Ahttps%3A%2F%2Fgithub.com%2Fmartintrojer%2Fklik"
-                    );
+                let url = share_url(
+                    &app.render_settings.share_template,
+                    app.thok.wpm(),
+                    app.thok.accuracy(),
+                    app.thok.std_dev(),
+                );
+                if let Some(url) = url.filter(|_| webbrowser::Browser::is_available()) {
                     let _ = webbrowser::open(&url);
                 }
                 Some(KeyAction::Continue)
             }
             KeyCode::Char('r') => Some(KeyAction::Restart),
             KeyCode::Char('n') => Some(KeyAction::New),
+            // `--rounds-wait` moves on to the next round
+            KeyCode::Enter if app.rounds.is_some() => Some(KeyAction::New),
             KeyCode::Char('s') => {
                 app.state = AppState::CharacterStats;
                 Some(KeyAction::Continue)
             }
+            KeyCode::Char('h') => {
+                app.history = crate::history::load_history(app.runtime_settings.profile.as_deref());
+                app.state = AppState::History;
+                Some(KeyAction::Continue)
+            }
+            KeyCode::Char('k') => {
+                app.state = AppState::Heatmap;
+                Some(KeyAction::Continue)
+            }
+            KeyCode::Char('p') => {
+                app.start_replay();
+                Some(KeyAction::Continue)
+            }
             // Settings toggles - persist to config after each change
             KeyCode::Char('1') => {
                 app.runtime_settings.random_words = !app.runtime_settings.random_words;
@@ -84,7 +129,7 @@ impl Screen for ResultsScreen {
                 Some(KeyAction::Continue)
             }
             KeyCode::Char('3') => {
-                app.runtime_settings.strict = !app.runtime_settings.strict;
+                app.runtime_settings.policy = app.runtime_settings.policy.next();
                 app.save_config();
                 Some(KeyAction::Continue)
             }
@@ -113,7 +158,9 @@ impl Screen for ResultsScreen {
                     match app.runtime_settings.supported_language {
                         crate::SupportedLanguage::English => crate::SupportedLanguage::English1k,
                         crate::SupportedLanguage::English1k => crate::SupportedLanguage::English10k,
-                        crate::SupportedLanguage::English10k => crate::SupportedLanguage::English,
+                        crate::SupportedLanguage::English10k => crate::SupportedLanguage::Spanish,
+                        crate::SupportedLanguage::Spanish => crate::SupportedLanguage::Code,
+                        crate::SupportedLanguage::Code => crate::SupportedLanguage::English,
                     };
                 app.save_config();
                 Some(KeyAction::Continue)
@@ -141,53 +188,215 @@ impl Screen for CharacterStatsScreen {
                 Some(KeyAction::Continue)
             }
             KeyCode::Up => {
-                if app.char_stats_state.scroll_offset > 0 {
-                    app.char_stats_state.scroll_offset -= 1;
-                }
+                app.char_stats_state.selected = app.char_stats_state.selected.saturating_sub(1);
                 Some(KeyAction::Continue)
             }
             KeyCode::Down => {
-                app.char_stats_state.scroll_offset += 1;
+                app.char_stats_state.selected += 1;
                 Some(KeyAction::Continue)
             }
             KeyCode::PageUp => {
-                app.char_stats_state.scroll_offset =
-                    app.char_stats_state.scroll_offset.saturating_sub(10);
+                app.char_stats_state.selected = app.char_stats_state.selected.saturating_sub(10);
                 Some(KeyAction::Continue)
             }
             KeyCode::PageDown => {
-                app.char_stats_state.scroll_offset += 10;
+                app.char_stats_state.selected += 10;
                 Some(KeyAction::Continue)
             }
             KeyCode::Home => {
                 app.char_stats_state.scroll_offset = 0;
+                app.char_stats_state.selected = 0;
                 Some(KeyAction::Continue)
             }
             KeyCode::Char('1') => {
                 app.char_stats_state.sort_by = crate::SortBy::Character;
                 app.char_stats_state.scroll_offset = 0;
+                app.char_stats_state.selected = 0;
                 Some(KeyAction::Continue)
             }
             KeyCode::Char('2') => {
                 app.char_stats_state.sort_by = crate::SortBy::AvgTime;
                 app.char_stats_state.scroll_offset = 0;
+                app.char_stats_state.selected = 0;
                 Some(KeyAction::Continue)
             }
             KeyCode::Char('3') => {
                 app.char_stats_state.sort_by = crate::SortBy::MissRate;
                 app.char_stats_state.scroll_offset = 0;
+                app.char_stats_state.selected = 0;
                 Some(KeyAction::Continue)
             }
             KeyCode::Char('4') => {
                 app.char_stats_state.sort_by = crate::SortBy::Attempts;
                 app.char_stats_state.scroll_offset = 0;
+                app.char_stats_state.selected = 0;
+                Some(KeyAction::Continue)
+            }
+            KeyCode::Char('5') => {
+                app.char_stats_state.sort_by = crate::SortBy::Difficulty;
+                app.char_stats_state.scroll_offset = 0;
+                app.char_stats_state.selected = 0;
                 Some(KeyAction::Continue)
             }
             KeyCode::Char(' ') => {
                 app.char_stats_state.sort_ascending = !app.char_stats_state.sort_ascending;
                 app.char_stats_state.scroll_offset = 0;
+                app.char_stats_state.selected = 0;
+                Some(KeyAction::Continue)
+            }
+            KeyCode::Char('f') => {
+                app.state = AppState::FingerStats;
+                Some(KeyAction::Continue)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Per-finger stats screen - a sub-view of the character stats
+pub struct FingerStatsScreen;
+
+impl Screen for FingerStatsScreen {
+    fn render(&self, app: &mut App, f: &mut Frame) {
+        render_finger_stats(app, f);
+    }
+
+    fn on_key(&mut self, key: KeyEvent, app: &mut App) -> Option<KeyAction> {
+        match key.code {
+            KeyCode::Char('r') => Some(KeyAction::Restart),
+            KeyCode::Char('n') => Some(KeyAction::New),
+            KeyCode::Char('b') | KeyCode::Backspace => {
+                app.state = AppState::CharacterStats;
+                Some(KeyAction::Continue)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Session history screen - consistency trend read from the CSV log
+pub struct HistoryScreen;
+
+impl Screen for HistoryScreen {
+    fn render(&self, app: &mut App, f: &mut Frame) {
+        render_history(app, f);
+    }
+
+    fn on_key(&mut self, key: KeyEvent, app: &mut App) -> Option<KeyAction> {
+        match key.code {
+            KeyCode::Char('r') => Some(KeyAction::Restart),
+            KeyCode::Char('n') => Some(KeyAction::New),
+            KeyCode::Char('b') | KeyCode::Backspace => {
+                app.state = AppState::Results;
+                Some(KeyAction::Continue)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Keyboard heatmap screen - weak keys at a glance
+pub struct HeatmapScreen;
+
+impl Screen for HeatmapScreen {
+    fn render(&self, app: &mut App, f: &mut Frame) {
+        render_heatmap(app, f);
+    }
+
+    fn on_key(&mut self, key: KeyEvent, app: &mut App) -> Option<KeyAction> {
+        match key.code {
+            KeyCode::Char('r') => Some(KeyAction::Restart),
+            KeyCode::Char('n') => Some(KeyAction::New),
+            KeyCode::Char('m') => {
+                app.heatmap_metric = app.heatmap_metric.toggle();
+                Some(KeyAction::Continue)
+            }
+            KeyCode::Char('b') | KeyCode::Backspace => {
+                app.state = AppState::Results;
+                Some(KeyAction::Continue)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Replay screen - plays back the last test at its recorded pace
+pub struct ReplayScreen;
+
+impl Screen for ReplayScreen {
+    fn render(&self, app: &mut App, f: &mut Frame) {
+        render_replay(app, f);
+    }
+
+    fn on_key(&mut self, key: KeyEvent, app: &mut App) -> Option<KeyAction> {
+        let now = app.thok.session.now();
+        let replay = app.replay.as_mut()?;
+        match key.code {
+            KeyCode::Char('r') => Some(KeyAction::Restart),
+            KeyCode::Char('n') => Some(KeyAction::New),
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                replay.set_speed(replay.speed() * 2.0);
+                Some(KeyAction::Continue)
+            }
+            KeyCode::Char('-') => {
+                replay.set_speed(replay.speed() / 2.0);
                 Some(KeyAction::Continue)
             }
+            KeyCode::Char(' ') => {
+                replay.restart(now);
+                Some(KeyAction::Continue)
+            }
+            KeyCode::Char('b') | KeyCode::Backspace => {
+                app.replay = None;
+                app.state = AppState::Results;
+                Some(KeyAction::Continue)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Pomodoro summary screen - shown once the focus timer budget is used up
+pub struct PomodoroSummaryScreen;
+
+impl Screen for PomodoroSummaryScreen {
+    fn render(&self, app: &mut App, f: &mut Frame) {
+        render_pomodoro_summary(app, f);
+    }
+
+    fn on_key(&mut self, key: KeyEvent, app: &mut App) -> Option<KeyAction> {
+        match key.code {
+            KeyCode::Char('n') => {
+                // Start a fresh focus session with the same budget
+                app.pomodoro = app
+                    .pomodoro
+                    .as_ref()
+                    .map(|p| crate::pomodoro::PomodoroSession::new(p.budget));
+                Some(KeyAction::New)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Rounds summary screen - shown after the last of the `--rounds`
+pub struct RoundsSummaryScreen;
+
+impl Screen for RoundsSummaryScreen {
+    fn render(&self, app: &mut App, f: &mut Frame) {
+        render_rounds_summary(app, f);
+    }
+
+    fn on_key(&mut self, key: KeyEvent, app: &mut App) -> Option<KeyAction> {
+        match key.code {
+            KeyCode::Char('n') => {
+                // Start the same number of rounds over
+                app.rounds = app
+                    .rounds
+                    .as_ref()
+                    .map(|r| crate::rounds::Rounds::new(r.target));
+                Some(KeyAction::New)
+            }
             _ => None,
         }
     }
@@ -199,6 +408,12 @@ pub fn current_screen(state: &AppState) -> Box<dyn Screen> {
         AppState::Typing => Box::new(TypingScreen),
         AppState::Results => Box::new(ResultsScreen),
         AppState::CharacterStats => Box::new(CharacterStatsScreen),
+        AppState::History => Box::new(HistoryScreen),
+        AppState::PomodoroSummary => Box::new(PomodoroSummaryScreen),
+        AppState::Aggregate => Box::new(RoundsSummaryScreen),
+        AppState::Heatmap => Box::new(HeatmapScreen),
+        AppState::Replay => Box::new(ReplayScreen),
+        AppState::FingerStats => Box::new(FingerStatsScreen),
     }
 }
 
@@ -1,12 +1,117 @@
 use crate::{
     language::{
-        CompositeFormatter, IntelligentSelector, Language, RandomSelector, SubstitutionSelector,
-        WordSelector,
+        BalancedSymbolFormatter, CharacterDifficulty, CodeFormatter, CompositeFormatter,
+        IntelligentSelector, KeySet, Language, Quote, QuoteSelector, SelectionConfig,
+        SubstitutionSelector, TextFormatter, TitleCaseFormatter, UppercaseDrillFormatter,
+        WeightedSelector, WordSelector,
     },
     stats::StatsDb,
     SupportedLanguage,
 };
-use std::collections::HashMap;
+use clap::ValueEnum;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, RngCore, SeedableRng};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt, io,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Digit group sizes for `--number-groups`, e.g. `3,3,4` for phone numbers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberGroups(Vec<usize>);
+
+impl FromStr for NumberGroups {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let sizes = s
+            .split(',')
+            .map(|size| match size.trim().parse::<usize>() {
+                Ok(size) if size > 0 => Ok(size),
+                _ => Err(format!(
+                    "number groups '{s}' must be positive sizes separated by commas, e.g. 3,3,4"
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self(sizes))
+    }
+}
+
+impl fmt::Display for NumberGroups {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sizes: Vec<String> = self.0.iter().map(ToString::to_string).collect();
+        f.write_str(&sizes.join(","))
+    }
+}
+
+/// What goes between `--number-groups`
+#[derive(Debug, Copy, Clone, Default, PartialEq, ValueEnum, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum NumberSeparator {
+    #[default]
+    Dash,
+    Space,
+}
+
+impl NumberSeparator {
+    fn as_char(self) -> char {
+        match self {
+            NumberSeparator::Dash => '-',
+            NumberSeparator::Space => ' ',
+        }
+    }
+}
+
+/// Shape of each `--numbers` entry. Without a range, entries are 2-6 random digits; with one,
+/// a value in the range zero-padded to the digit count of its end, so `0..=9999` gives `0042`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NumberFormat {
+    pub range: Option<RangeInclusive<u64>>,
+    pub groups: Option<NumberGroups>,
+    pub separator: NumberSeparator,
+}
+
+impl NumberFormat {
+    /// One entry's digits, before grouping
+    fn digits(&self, rng: &mut dyn RngCore) -> String {
+        match &self.range {
+            Some(range) => {
+                let width = range.end().to_string().len();
+                format!("{:0width$}", rng.gen_range(range.clone()))
+            }
+            None => {
+                let len = rng.gen_range(2..=6);
+                (0..len)
+                    .map(|_| char::from(b'0' + rng.gen_range(0..10)))
+                    .collect()
+            }
+        }
+    }
+
+    /// Split `digits` into the configured group sizes; digits left over form a final group
+    fn group(&self, digits: &str) -> String {
+        let Some(NumberGroups(sizes)) = &self.groups else {
+            return digits.to_string();
+        };
+        let mut rest = digits;
+        let mut groups = Vec::new();
+        for size in sizes {
+            if rest.is_empty() {
+                break;
+            }
+            let (group, tail) = rest.split_at((*size).min(rest.len()));
+            groups.push(group);
+            rest = tail;
+        }
+        if !rest.is_empty() {
+            groups.push(rest);
+        }
+        groups.join(&self.separator.as_char().to_string())
+    }
+}
 
 /// Configuration for word generation
 #[derive(Debug, Clone)]
@@ -19,74 +124,302 @@ pub struct WordGenConfig {
     pub substitute: bool,
     pub capitalize: bool,
     pub symbols: bool,
+    /// With `symbols`, every inserted bracket or quote is closed later in the prompt
+    pub balanced_symbols: bool,
+    /// Capitalize letters throughout the words, favoring those with the weakest capitals
+    pub uppercase_drill: bool,
+    /// Capitalize the first letter of every word, to drill the shift key steadily
+    pub title_case: bool,
+    /// In sentence mode, prefer sentences heavy in the characters that need most practice
+    pub targeted_sentences: bool,
+    pub quote: bool,
+    /// Newline-separated word file used in place of the bundled language
+    pub wordlist: Option<PathBuf>,
+    /// Newline-separated file of prompts; each test types one line picked at random
+    pub prompt_file: Option<PathBuf>,
+    /// Digit groups instead of words; overrides the word selection strategy
+    pub numbers: bool,
+    /// Range and grouping of the `numbers` entries
+    pub number_format: NumberFormat,
+    /// Whole sentences up to `number_of_words` words instead of a word list
+    pub sentence_words: bool,
+    /// Only practice words typeable with these keys; overrides sentence mode
+    pub keys: Option<KeySet>,
+    /// Second language whose words are interleaved roughly 50/50 with `language`
+    pub mix: Option<SupportedLanguage>,
+    /// How tightly intelligent selection focuses on the hardest words
+    pub selection: SelectionConfig,
+    /// Seed for all random choices, so the same options generate the same prompt
+    pub seed: Option<u64>,
+    /// `--profile` whose character stats guide the selection; `None` for the default
+    pub profile: Option<String>,
+    /// Days over which a session's weight in the character difficulties halves; `None` weighs
+    /// all sessions alike
+    pub recency_halflife: Option<f64>,
+}
+
+/// The non-blank lines of a `--prompt-file`, trimmed; an error if there are none
+pub fn read_prompt_lines(path: &Path) -> io::Result<Vec<String>> {
+    let lines: Vec<String> = std::fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect();
+    if lines.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "prompt file contains no lines",
+        ));
+    }
+    Ok(lines)
 }
 
 /// Handles all word and prompt generation logic
 pub struct WordGenerator {
     config: WordGenConfig,
+    /// Seeded once, so each prompt from the same generator differs while `--seed` still
+    /// reproduces the whole sequence
+    rng: RefCell<StdRng>,
 }
 
 impl WordGenerator {
     pub fn new(config: WordGenConfig) -> Self {
-        Self { config }
+        let rng = RefCell::new(seeded_rng(config.seed));
+        Self { config, rng }
+    }
+
+    /// Generate from `config` from now on. The random sequence carries on unless the seed
+    /// changed, so a new test under the same `--seed` gets the next prompt rather than a repeat.
+    pub fn reconfigure(&mut self, config: WordGenConfig) {
+        if config.seed != self.config.seed {
+            self.rng = RefCell::new(seeded_rng(config.seed));
+        }
+        self.config = config;
     }
 
     /// Generate a complete prompt based on the configuration
     pub fn generate_prompt(&self) -> (String, usize) {
+        let (prompt, word_count, _) = self.generate_prompt_with_quote();
+        (prompt, word_count)
+    }
+
+    /// Like `generate_prompt`, also returning the quote the prompt came from in `--quote` mode
+    pub fn generate_prompt_with_quote(&self) -> (String, usize, Option<Quote>) {
         if let Some(ref custom_prompt) = self.config.custom_prompt {
-            return (custom_prompt.clone(), self.config.number_of_words);
+            return (custom_prompt.clone(), self.config.number_of_words, None);
         }
 
-        if let Some(sentence_count) = self.config.number_of_sentences {
-            return self.generate_sentences(sentence_count);
+        let mut rng = self.rng.borrow_mut();
+        let mut rng = &mut *rng;
+
+        // An unreadable file falls through to normal generation
+        if let Some(line) = self
+            .config
+            .prompt_file
+            .as_ref()
+            .and_then(|path| read_prompt_lines(path).ok())
+            .and_then(|lines| lines.choose(&mut rng).cloned())
+        {
+            let word_count = line.split_whitespace().count();
+            return (line, word_count, None);
         }
 
-        self.generate_words()
+        if self.config.quote {
+            // An empty corpus falls through to normal generation
+            if let Some(quote) = QuoteSelector::new().pick(&mut rng) {
+                return (quote.text.clone(), quote.word_count(), Some(quote.clone()));
+            }
+        }
+
+        if self.config.numbers {
+            return (
+                self.generate_numbers(&mut rng),
+                self.config.number_of_words,
+                None,
+            );
+        }
+
+        let (prompt, word_count) = match self.config.number_of_sentences {
+            _ if self.config.keys.is_some() => self.generate_words(&mut rng),
+            Some(sentence_count) => self.generate_sentences(sentence_count, &mut rng),
+            None if self.config.sentence_words => self
+                .language(&mut rng)
+                .get_sentences_for_words(self.config.number_of_words, &mut rng),
+            None => self.generate_words(&mut rng),
+        };
+        (prompt, word_count, None)
     }
 
-    /// Generate sentences using cgisf
-    fn generate_sentences(&self, count: usize) -> (String, usize) {
-        let language = self.config.language.as_lang();
-        let (sentences, word_count) = language.get_random_sentence(count);
-        (sentences.join(""), word_count)
+    /// The custom word list if one is configured and readable, otherwise the bundled language,
+    /// narrowed to the `--keys` set when one is given
+    fn language(&self, rng: &mut dyn RngCore) -> Language {
+        let language = self
+            .config
+            .wordlist
+            .as_ref()
+            .and_then(|path| Language::from_wordlist_file(path).ok())
+            .unwrap_or_else(|| self.config.language.as_lang());
+        self.restrict_to_keys(language, rng)
     }
 
-    /// Generate words based on selection strategy and apply formatting
-    fn generate_words(&self) -> (String, usize) {
-        let language = self.config.language.as_lang();
+    /// The `--mix` language, narrowed to the `--keys` set like the primary one
+    fn mix_language(&self, rng: &mut dyn RngCore) -> Option<Language> {
+        self.config
+            .mix
+            .map(|mix| self.restrict_to_keys(mix.as_lang(), rng))
+    }
 
-        // Step 1: Select words based on strategy
-        let words = self.select_words(&language);
+    fn restrict_to_keys(&self, language: Language, rng: &mut dyn RngCore) -> Language {
+        match &self.config.keys {
+            Some(keys) => keys.restrict(&language, rng),
+            None => language,
+        }
+    }
 
-        // Step 2: Apply formatting using the new formatter system
-        let formatter =
-            CompositeFormatter::build_from_flags(self.config.capitalize, self.config.symbols);
-        let formatted_text = formatter.format(words);
+    /// Generate `number_of_words` numbers shaped by the configured `NumberFormat`
+    fn generate_numbers(&self, rng: &mut dyn RngCore) -> String {
+        let format = &self.config.number_format;
+        (0..self.config.number_of_words)
+            .map(|_| format.group(&format.digits(rng)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
 
-        (formatted_text, self.config.number_of_words)
+    /// Generate sentences using cgisf
+    fn generate_sentences(&self, count: usize, rng: &mut dyn RngCore) -> (String, usize) {
+        let language = self.language(rng);
+        let (sentences, word_count) = if self.config.targeted_sentences {
+            language.get_targeted_sentence(
+                count,
+                &load_char_difficulties(
+                    self.config.profile.as_deref(),
+                    self.config.recency_halflife,
+                ),
+                &self.config.selection,
+                rng,
+            )
+        } else {
+            language.get_random_sentence(count, rng)
+        };
+        (sentences.join(""), word_count)
     }
 
-    /// Select words based on the configured strategy
-    fn select_words(&self, language: &Language) -> Vec<String> {
+    /// Generate words based on selection strategy and apply formatting
+    fn generate_words(&self, rng: &mut dyn RngCore) -> (String, usize) {
+        let language = self.language(rng);
+        let count = self.config.number_of_words;
+
         // Load character statistics for intelligent/substitution modes
-        let char_difficulties = match StatsDb::new() {
-            Ok(stats_db) => stats_db.get_character_difficulties().unwrap_or_default(),
-            Err(_) => HashMap::new(),
+        let char_difficulties =
+            load_char_difficulties(self.config.profile.as_deref(), self.config.recency_halflife);
+
+        // Step 1: Select words based on strategy, half from each language when mixing
+        let words = match self.mix_language(rng) {
+            Some(mix) => interleave(
+                self.select_words(&language, count.div_ceil(2), &char_difficulties, rng),
+                self.select_words(&mix, count / 2, &char_difficulties, rng),
+            ),
+            None => self.select_words(&language, count, &char_difficulties, rng),
+        };
+        // Small word pools can yield fewer words than asked for; WPM needs the real count
+        let word_count = words.len();
+
+        // Step 2: Apply formatting using the new formatter system; code tokens get their own
+        let is_code = matches!(self.config.language, SupportedLanguage::Code);
+        let formatter: Box<dyn TextFormatter> = if is_code {
+            Box::new(CodeFormatter)
+        } else if self.config.symbols && self.config.balanced_symbols {
+            Box::new(BalancedSymbolFormatter {
+                capitalize: self.config.capitalize,
+            })
+        } else {
+            CompositeFormatter::build_from_flags(self.config.capitalize, self.config.symbols)
         };
+        let formatter: Box<dyn TextFormatter> = if self.config.uppercase_drill && !is_code {
+            Box::new(
+                CompositeFormatter::new()
+                    .add_formatter(formatter)
+                    .add_formatter(Box::new(UppercaseDrillFormatter {
+                        difficulties: char_difficulties,
+                    })),
+            )
+        } else {
+            formatter
+        };
+        let formatter: Box<dyn TextFormatter> = if self.config.title_case && !is_code {
+            Box::new(
+                CompositeFormatter::new()
+                    .add_formatter(formatter)
+                    .add_formatter(Box::new(TitleCaseFormatter)),
+            )
+        } else {
+            formatter
+        };
+        let formatted_text = formatter.format(words, rng);
+
+        (formatted_text, word_count)
+    }
 
+    /// Select words based on the configured strategy
+    fn select_words(
+        &self,
+        language: &Language,
+        count: usize,
+        char_difficulties: &HashMap<char, CharacterDifficulty>,
+        rng: &mut dyn RngCore,
+    ) -> Vec<String> {
         // Choose the appropriate selector based on configuration
         let selector: Box<dyn WordSelector> = if self.config.random_words {
-            Box::new(RandomSelector)
+            Box::new(WeightedSelector)
         } else if self.config.substitute {
             Box::new(SubstitutionSelector)
         } else {
-            Box::new(IntelligentSelector)
+            Box::new(IntelligentSelector {
+                config: self.config.selection,
+            })
         };
 
-        selector.select_words(language, self.config.number_of_words, &char_difficulties)
+        selector.select_words(language, count, char_difficulties, rng)
     }
 }
 
+/// Seeded from `--seed` when given, otherwise from the OS
+fn seeded_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+/// Character difficulties from the stats database of `profile`, favoring recent sessions with
+/// a `recency_halflife`; empty when there is none yet
+fn load_char_difficulties(
+    profile: Option<&str>,
+    recency_halflife: Option<f64>,
+) -> HashMap<char, CharacterDifficulty> {
+    let Ok(stats_db) = StatsDb::new(profile) else {
+        return HashMap::new();
+    };
+    match recency_halflife {
+        Some(days) => stats_db.get_character_difficulties_decayed(days),
+        None => stats_db.get_character_difficulties(),
+    }
+    .unwrap_or_default()
+}
+
+/// Alternate words from `a` and `b`, appending whatever is left of the longer one
+fn interleave(a: Vec<String>, b: Vec<String>) -> Vec<String> {
+    let mut mixed = Vec::with_capacity(a.len() + b.len());
+    let mut b = b.into_iter();
+    for word in a {
+        mixed.push(word);
+        mixed.extend(b.next());
+    }
+    mixed.extend(b);
+    mixed
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,7 +434,297 @@ mod tests {
             substitute: false,
             capitalize: false,
             symbols: false,
+            balanced_symbols: false,
+            uppercase_drill: false,
+            title_case: false,
+            targeted_sentences: false,
+            quote: false,
+            wordlist: None,
+            prompt_file: None,
+            numbers: false,
+            number_format: NumberFormat::default(),
+            sentence_words: false,
+            keys: None,
+            mix: None,
+            selection: SelectionConfig::default(),
+            seed: None,
+            profile: None,
+            recency_halflife: None,
+        }
+    }
+
+    #[test]
+    fn test_same_seed_generates_same_prompt() {
+        let mut config = create_test_config();
+        config.number_of_words = 15;
+        config.random_words = true;
+        config.capitalize = true;
+        config.symbols = true;
+        config.seed = Some(42);
+
+        let (first, _) = WordGenerator::new(config.clone()).generate_prompt();
+        let (second, _) = WordGenerator::new(config.clone()).generate_prompt();
+        assert_eq!(first, second);
+
+        config.seed = Some(43);
+        let (other, _) = WordGenerator::new(config).generate_prompt();
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn test_seeded_generator_moves_through_its_sequence() {
+        let mut config = create_test_config();
+        config.number_of_words = 15;
+        config.random_words = true;
+        config.seed = Some(42);
+
+        let generator = WordGenerator::new(config.clone());
+        let (first, _) = generator.generate_prompt();
+        let (second, _) = generator.generate_prompt();
+        assert_ne!(first, second);
+
+        let same_seed = WordGenerator::new(config.clone());
+        assert_eq!(same_seed.generate_prompt().0, first);
+        assert_eq!(same_seed.generate_prompt().0, second);
+
+        // New settings under the same seed continue the sequence instead of restarting it
+        let mut reconfigured = WordGenerator::new(config.clone());
+        reconfigured.generate_prompt();
+        config.number_of_words = 16;
+        reconfigured.reconfigure(config);
+        assert_ne!(
+            reconfigured.generate_prompt().0.split(' ').next(),
+            first.split(' ').next()
+        );
+    }
+
+    #[test]
+    fn test_interleave_alternates_and_keeps_leftovers() {
+        let words = |s: &str| s.split(' ').map(String::from).collect::<Vec<_>>();
+        assert_eq!(interleave(words("a b c"), words("x y")), words("a x b y c"));
+        assert_eq!(interleave(words("a"), words("x y z")), words("a x y z"));
+    }
+
+    #[test]
+    fn test_code_language_generates_statements() {
+        let mut config = create_test_config();
+        config.language = SupportedLanguage::Code;
+        config.random_words = true;
+        config.number_of_words = 12;
+
+        let (prompt, word_count) = WordGenerator::new(config).generate_prompt();
+        assert_eq!(word_count, 12);
+        assert_eq!(prompt.split_whitespace().count(), 12);
+        assert!(prompt.ends_with(';'), "{prompt}");
+    }
+
+    #[test]
+    fn test_mix_draws_from_both_languages() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("words.txt");
+        std::fs::write(&path, "zebra\nquokka\nlynx\nokapi\n").unwrap();
+
+        let mut config = create_test_config();
+        config.random_words = true;
+        config.number_of_words = 7;
+        config.wordlist = Some(path);
+        config.mix = Some(SupportedLanguage::English);
+
+        let (prompt, word_count) = WordGenerator::new(config).generate_prompt();
+        let words: Vec<&str> = prompt.split(' ').collect();
+        assert_eq!(word_count, 7);
+        assert_eq!(words.len(), 7);
+
+        let custom = ["zebra", "quokka", "lynx", "okapi"];
+        // Even positions come from the primary list, odd ones from the mix language
+        for (i, word) in words.iter().enumerate() {
+            assert_eq!(custom.contains(word), i % 2 == 0, "{word} at {i}");
+        }
+    }
+
+    #[test]
+    fn test_prompt_file_draws_random_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("prompts.txt");
+        std::fs::write(
+            &path,
+            "the quick fox\n\n   \njumps over\n  the lazy dog  \n",
+        )
+        .unwrap();
+
+        let mut config = create_test_config();
+        config.prompt_file = Some(path.clone());
+        let lines = ["the quick fox", "jumps over", "the lazy dog"];
+        let mut seen = std::collections::HashSet::new();
+        for seed in 0..50 {
+            config.seed = Some(seed);
+            let (prompt, word_count) = WordGenerator::new(config.clone()).generate_prompt();
+            assert!(lines.contains(&prompt.as_str()), "{prompt:?}");
+            assert_eq!(word_count, prompt.split(' ').count());
+            seen.insert(prompt);
+        }
+        assert_eq!(seen.len(), lines.len());
+
+        std::fs::write(&path, "only line\n").unwrap();
+        let (prompt, word_count) = WordGenerator::new(config.clone()).generate_prompt();
+        assert_eq!((prompt.as_str(), word_count), ("only line", 2));
+
+        std::fs::write(&path, "\n  \n").unwrap();
+        assert!(read_prompt_lines(&path).is_err());
+    }
+
+    #[test]
+    fn test_word_count_matches_small_pool() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("words.txt");
+        std::fs::write(&path, "zebra\nlynx\n").unwrap();
+
+        let mut config = create_test_config();
+        config.random_words = true;
+        config.number_of_words = 10;
+        config.wordlist = Some(path);
+
+        let (prompt, word_count) = WordGenerator::new(config).generate_prompt();
+        assert_eq!(word_count, 2);
+        assert_eq!(prompt.split(' ').count(), 2);
+    }
+
+    #[test]
+    fn test_keys_restrict_generated_words() {
+        let keys: KeySet = "home".parse().unwrap();
+        let mut config = create_test_config();
+        config.random_words = true;
+        config.number_of_words = 10;
+        config.number_of_sentences = Some(2);
+        config.keys = Some(keys.clone());
+
+        let (prompt, word_count) = WordGenerator::new(config).generate_prompt();
+        assert_eq!(word_count, 10);
+        assert_eq!(prompt.split(' ').count(), 10);
+        for word in prompt.split(' ') {
+            assert!(keys.allows(word), "{word}");
+        }
+    }
+
+    #[test]
+    fn test_sentence_words_mode_uses_word_target() {
+        let mut config = create_test_config();
+        config.number_of_words = 30;
+        config.sentence_words = true;
+
+        let (prompt, word_count) = WordGenerator::new(config).generate_prompt();
+        assert_eq!(word_count, 30);
+        assert_eq!(prompt.split_whitespace().count(), 30);
+    }
+
+    #[test]
+    fn test_numbers_mode_generates_digit_groups() {
+        let mut config = create_test_config();
+        config.numbers = true;
+        config.random_words = true;
+        config.number_of_words = 20;
+
+        let (prompt, word_count) = WordGenerator::new(config).generate_prompt();
+        assert_eq!(word_count, 20);
+
+        let groups: Vec<&str> = prompt.split(' ').collect();
+        assert_eq!(groups.len(), 20);
+        for group in groups {
+            assert!((2..=6).contains(&group.len()), "{group}");
+            assert!(group.chars().all(|c| c.is_ascii_digit()), "{group}");
+        }
+    }
+
+    #[test]
+    fn test_number_range_and_groups() {
+        let mut config = create_test_config();
+        config.numbers = true;
+        config.number_of_words = 50;
+        config.number_format.range = Some(1900..=2099);
+
+        let (prompt, _) = WordGenerator::new(config.clone()).generate_prompt();
+        for year in prompt.split(' ') {
+            assert_eq!(year.len(), 4, "{year}");
+            assert!(
+                (1900..=2099).contains(&year.parse::<u64>().unwrap()),
+                "{year}"
+            );
         }
+
+        // Phone numbers keep their leading zeros and come out as 3-3-4 groups
+        config.number_format = NumberFormat {
+            range: Some(0..=9_999_999_999),
+            groups: Some("3,3,4".parse().unwrap()),
+            separator: NumberSeparator::Dash,
+        };
+        let (prompt, _) = WordGenerator::new(config.clone()).generate_prompt();
+        let phones: Vec<&str> = prompt.split(' ').collect();
+        assert_eq!(phones.len(), 50);
+        for phone in phones {
+            let groups: Vec<usize> = phone.split('-').map(str::len).collect();
+            assert_eq!(groups, vec![3, 3, 4], "{phone}");
+            assert!(phone.chars().all(|c| c.is_ascii_digit() || c == '-'));
+        }
+
+        config.number_format.range = Some(0..=99);
+        config.number_format.groups = Some("1".parse().unwrap());
+        config.number_format.separator = NumberSeparator::Space;
+        assert_eq!(config.number_format.group("07"), "0 7");
+        assert_eq!(config.number_format.group("0"), "0");
+        // Leading zeros are padded in, never dropped
+        let (prompt, _) = WordGenerator::new(config).generate_prompt();
+        assert_eq!(prompt.split(' ').count(), 100);
+        assert!(prompt.split(' ').all(|digit| digit.len() == 1));
+
+        assert!("3,0".parse::<NumberGroups>().is_err());
+        assert!("three".parse::<NumberGroups>().is_err());
+        assert_eq!(
+            "3, 3,4".parse::<NumberGroups>().unwrap().to_string(),
+            "3,3,4"
+        );
+    }
+
+    #[test]
+    fn test_custom_wordlist_generation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("words.txt");
+        std::fs::write(&path, "zebra\n\n  quokka  \nlynx\n").unwrap();
+
+        let mut config = create_test_config();
+        config.random_words = true;
+        config.number_of_words = 3;
+        config.wordlist = Some(path);
+
+        let (prompt, word_count) = WordGenerator::new(config).generate_prompt();
+        assert_eq!(word_count, 3);
+        for word in prompt.split_whitespace() {
+            assert!(["zebra", "quokka", "lynx"].contains(&word), "{word}");
+        }
+    }
+
+    #[test]
+    fn test_quote_mode_ignores_word_count() {
+        let mut config = create_test_config();
+        config.quote = true;
+        config.number_of_words = 1;
+
+        let generator = WordGenerator::new(config);
+        let (prompt, word_count, quote) = generator.generate_prompt_with_quote();
+
+        let quote = quote.expect("bundled corpus should supply a quote");
+        assert_eq!(prompt, quote.text);
+        assert_eq!(word_count, prompt.split_whitespace().count());
+    }
+
+    #[test]
+    fn test_custom_prompt_wins_over_quote() {
+        let mut config = create_test_config();
+        config.quote = true;
+        config.custom_prompt = Some("mine".to_string());
+
+        let (prompt, _, quote) = WordGenerator::new(config).generate_prompt_with_quote();
+        assert_eq!(prompt, "mine");
+        assert!(quote.is_none());
     }
 
     #[test]